@@ -0,0 +1,38 @@
+// Compares the blocked kernel behind matmul_into and gemm with the column by column product
+// it replaced. Run with `cargo bench --bench gemm`.
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+
+// algae is a binary crate, so the benchmark compiles the array module itself. It's built with
+// cfg(test) but without the test harness, which leaves the helpers and imports of the test
+// modules unused.
+#[allow(dead_code, unused_imports)]
+#[path = "../src/array/mod.rs"]
+pub mod array;
+#[allow(dead_code, unused_imports)]
+#[path = "../src/error/mod.rs"]
+pub mod error;
+
+use array::array::Array;
+use array::gemm::{matmul_by_columns, pseudo_random};
+
+fn square_products(criterion:&mut Criterion) {
+    let mut group = criterion.benchmark_group("square_product");
+    for n in [32, 64, 128, 256] {
+        let a = pseudo_random((n, n), 3);
+        let b = pseudo_random((n, n), 4);
+        let mut c = Array::new_filled((n, n), 0.0);
+        group.bench_with_input(BenchmarkId::new("column_product", n), &n, |bencher, _| {
+            bencher.iter(|| matmul_by_columns(black_box(&a), black_box(&b)))
+        });
+        group.bench_with_input(BenchmarkId::new("matmul_into", n), &n, |bencher, _| {
+            bencher.iter(|| Array::matmul_into(black_box(&a), black_box(&b), &mut c))
+        });
+        group.bench_with_input(BenchmarkId::new("gemm", n), &n, |bencher, _| {
+            bencher.iter(|| Array::gemm(0.5, black_box(&a), black_box(&b), 0.5, &mut c))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, square_products);
+criterion_main!(benches);
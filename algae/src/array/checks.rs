@@ -0,0 +1,31 @@
+#[cfg(test)]
+use crate::array::array::Array;
+use crate::error::error::AlgaeError;
+
+// Checks shared by the modules of the array, so their error reporting stays uniform.
+
+pub(in crate::array) fn check_square(size:(usize, usize)) -> Result<usize, AlgaeError> {
+    if size.0 != size.1 {
+        return Err(AlgaeError::NotSquare {
+            size,
+        });
+    }
+    Ok(size.0)
+}
+
+// The index is (row, col) while the size is (width, height).
+pub(in crate::array) fn check_index(index:(usize, usize), size:(usize, usize)) -> Result<(), AlgaeError> {
+    if index.0 >= size.1 || index.1 >= size.0 {
+        return Err(AlgaeError::IndexOutOfBounds {
+            index,
+            size,
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+pub(in crate::array) fn assert_close(expected:&Array<f64>, actual:&Array<f64>, tolerance:f64) {
+    assert_eq!(expected.size, actual.size, "expected {} but found {}", expected, actual);
+    assert!((expected - actual).norm() < tolerance, "expected {} but found {}", expected, actual);
+}
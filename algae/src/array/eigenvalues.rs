@@ -1,103 +1,931 @@
 use crate::array::array::Array;
-use crate::array::factorizations::LuFactorization;
-use crate::array::factorizations::LuResult;
-use crate::array::float_eq::FloatEq;
+use crate::array::factorizations::PluFactorization;
+use crate::array::float_eq::{FloatEq, Tolerance};
+use crate::error::error::AlgaeError;
 use std::ops::{Add, Sub, Neg, Mul, Div};
-use num::traits::{One, Zero};
+use num::traits::{One, Zero, ToPrimitive, Float};
+use num::Complex;
+
+// An eigenpair (value, vector). A failed iteration hands back the last estimates alongside the error.
+pub type EigenResult<T> = Result<(T, Array<T>), (AlgaeError, T, Array<T>)>;
+
+// The entry with the largest magnitude, keeping its sign, so that negative dominant
+// eigenvalues don't flip the iterate every step.
+fn dominant_entry<T: Copy + Clone + Zero + PartialOrd + Neg<Output = T>>(x:&Array<T>) -> T {
+    let magnitudes = x.map(|a| if a < T::zero() { -a } else { a });
+    match magnitudes.argmax() {
+        Some(index) => x[index],
+        None => panic!("The start vector must not be empty"),
+    }
+}
 
 impl<T: Copy + Clone + Zero + One + PartialEq + PartialOrd
- + Add<Output = T> + Sub<Output = T> + Neg<Output = T> + Mul<Output = T> + Div<Output = T> + FloatEq + std::fmt::Display> 
+ + Add<Output = T> + Sub<Output = T> + Neg<Output = T> + Mul<Output = T> + Div<Output = T> + FloatEq + std::fmt::Display + ToPrimitive> 
 Array<T> {
     pub fn power_method(array:&Array<T>, x_zero:Array<T>, iteration_cap:usize) 
-    -> Result<(T, Array<T>), (String, T, Array<T>)> {
+    -> EigenResult<T> {
+        Self::power_method_with(array, x_zero, iteration_cap, None)
+    }
+
+    // Converged once two consecutive estimates and iterates agree within the tolerance.
+    pub fn power_method_with(array:&Array<T>, x_zero:Array<T>, iteration_cap:usize, tolerance:Option<Tolerance>) 
+    -> EigenResult<T> {
+        let tolerance = tolerance.unwrap_or(T::standard_tolerance());
         let mut x_k_minus_one = x_zero;
         let mut mu_k_minus_one = T::zero();
         for _ in 0..iteration_cap {
-            let mut x_k = array.clone() * x_k_minus_one.clone();
-            let mu_k = {
-                let mut max = x_k[(0, 0)];
-                for i in 1..x_k.size.1 {
-                    if x_k[(i, 0)] > max {
-                        max = x_k[(i, 0)];
-                    }
-                }
-                max
-            };
-            x_k = x_k.clone() * (T::one() / mu_k);
-            if mu_k.float_eq(&mu_k_minus_one) && x_k.float_eq(&x_k_minus_one) {
+            let mut x_k = array * &x_k_minus_one;
+            let mu_k = dominant_entry(&x_k);
+            x_k /= mu_k;
+            if mu_k.approx_eq_with(&mu_k_minus_one, tolerance) && x_k.approx_eq_with(&x_k_minus_one, tolerance) {
                 return Ok((mu_k, x_k))
             }
             mu_k_minus_one = mu_k;
             x_k_minus_one = x_k;
         }
         Err((
-            format!("Power method exceeded iteration_cap: {}", iteration_cap),
+            AlgaeError::NoConvergence {
+                iterations:iteration_cap,
+                last_estimate:mu_k_minus_one.to_f64().unwrap_or(f64::NAN),
+            },
             mu_k_minus_one, 
             x_k_minus_one,
         ))
     }
 
     pub fn inverse_power_method(array:&Array<T>, x_zero:Array<T>, alpha:T, iteration_cap:usize) 
-    -> Result<(T, Array<T>), (String, T, Array<T>)> {
+    -> EigenResult<T> {
+        Self::inverse_power_method_with(array, x_zero, alpha, iteration_cap, None)
+    }
+
+    pub fn inverse_power_method_with(array:&Array<T>, x_zero:Array<T>, alpha:T, iteration_cap:usize, tolerance:Option<Tolerance>) 
+    -> EigenResult<T> {
+        let tolerance = tolerance.unwrap_or(T::standard_tolerance());
         let mut v_k_minus_one = alpha;
         let mut x_k_minus_one = x_zero.clone();
-        let equation_system = match LuFactorization::new(
-            array.clone() - (Array::identity(array.size.0) * alpha)
+        let equation_system = match PluFactorization::new(
+            array - &(Array::identity(array.size.0) * alpha)
         ) {
             Ok(e_s) => e_s,
-            Err(_) => return Err(("Non valid system of equations for this choice of alpha".to_string(), alpha, x_zero)),
+            Err(e) => return Err((e, alpha, x_zero)),
         };
         for _ in 0..iteration_cap {
-            let y_k = match equation_system.solve(x_k_minus_one.clone()) {
-                LuResult::Single(r) => r,
-                LuResult::Infinite((r, _)) => r,
-            };
-            let mu_k = {
-                let mut max = y_k[(0, 0)];
-                for i in 1..y_k.size.1 {
-                    if y_k[(i, 0)] > max {
-                        max = y_k[(i, 0)];
-                    }
-                }
-                max
+            let y_k = match equation_system.try_solve_view(x_k_minus_one.view()) {
+                Ok(y_k) => y_k,
+                Err(e) => return Err((e, v_k_minus_one, x_k_minus_one)),
             };
-            let v_k = (alpha + T::one()) / mu_k;
-            let x_k = y_k * (T::one() / mu_k);
-            if v_k.float_eq(&v_k_minus_one) && x_k.float_eq(&x_k_minus_one) {
+            let mu_k = dominant_entry(&y_k);
+            let v_k = alpha + T::one() / mu_k;
+            let x_k = y_k / mu_k;
+            if v_k.approx_eq_with(&v_k_minus_one, tolerance) && x_k.approx_eq_with(&x_k_minus_one, tolerance) {
                 return Ok((v_k, x_k))
             }
             v_k_minus_one = v_k;
             x_k_minus_one = x_k;
         }
         Err((
-            format!("Power method exceeded iteration_cap: {}", iteration_cap),
+            AlgaeError::NoConvergence {
+                iterations:iteration_cap,
+                last_estimate:v_k_minus_one.to_f64().unwrap_or(f64::NAN),
+            },
             v_k_minus_one,
             x_k_minus_one, 
         ))
     }
 }
 
+// Eigenvalues and eigenvectors of a general real matrix. Complex eigenvalues come in conjugate
+// pairs, every column of `vectors` is a unit eigenvector of the eigenvalue with the same index.
+pub struct EigenDecomposition<T> {
+    pub values:Vec<Complex<T>>,
+    pub vectors:Array<Complex<T>>,
+}
+
+// Eigenvalues of a symmetric matrix in ascending order with orthonormal eigenvectors as columns.
+pub struct SymmetricEigenDecomposition<T> {
+    pub values:Vec<T>,
+    pub vectors:Array<T>,
+}
+
+pub(in crate::array) fn constant<T: Float>(x:f64) -> T {
+    match T::from(x) {
+        Some(x) => x,
+        None => panic!("The constant {} isn't representable by the element type", x),
+    }
+}
+
+fn to_rows<T: Copy + Clone>(a:&Array<T>) -> Vec<Vec<T>> {
+    a.rows().map(|row| row.iter().copied().collect()).collect()
+}
+
+// Complex division (xr + i * xi) / (yr + i * yi) without intermediate overflow.
+fn cdiv<T: Float>(xr:T, xi:T, yr:T, yi:T) -> (T, T) {
+    if yr.abs() > yi.abs() {
+        let r = yi / yr;
+        let d = yr + r * yi;
+        ((xr + r * xi) / d, (xi - r * xr) / d)
+    } else {
+        let r = yr / yi;
+        let d = yi + r * yr;
+        ((r * xr + xi) / d, (r * xi - xr) / d)
+    }
+}
+
+// Householder reduction of a symmetric matrix to tridiagonal form. On return d holds the
+// diagonal, e the subdiagonal in e[1..] and v the accumulated orthogonal transformation.
+// Follows tred2 of EISPACK in the formulation of JAMA.
+#[allow(clippy::needless_range_loop)]
+fn tridiagonalize<T: Float>(v:&mut [Vec<T>], d:&mut [T], e:&mut [T]) {
+    let n = d.len();
+    d.copy_from_slice(&v[n - 1]);
+    for i in (1..n).rev() {
+        let mut scale = T::zero();
+        let mut h = T::zero();
+        for k in 0..i {
+            scale = scale + d[k].abs();
+        }
+        if scale == T::zero() {
+            e[i] = d[i - 1];
+            for j in 0..i {
+                d[j] = v[i - 1][j];
+                v[i][j] = T::zero();
+                v[j][i] = T::zero();
+            }
+        } else {
+            for k in 0..i {
+                d[k] = d[k] / scale;
+                h = h + d[k] * d[k];
+            }
+            let mut f = d[i - 1];
+            let mut g = h.sqrt();
+            if f > T::zero() {
+                g = -g;
+            }
+            e[i] = scale * g;
+            h = h - f * g;
+            d[i - 1] = f - g;
+            for j in 0..i {
+                e[j] = T::zero();
+            }
+            for j in 0..i {
+                f = d[j];
+                v[j][i] = f;
+                g = e[j] + v[j][j] * f;
+                for k in (j + 1)..i {
+                    g = g + v[k][j] * d[k];
+                    e[k] = e[k] + v[k][j] * f;
+                }
+                e[j] = g;
+            }
+            f = T::zero();
+            for j in 0..i {
+                e[j] = e[j] / h;
+                f = f + e[j] * d[j];
+            }
+            let hh = f / (h + h);
+            for j in 0..i {
+                e[j] = e[j] - hh * d[j];
+            }
+            for j in 0..i {
+                f = d[j];
+                g = e[j];
+                for k in j..i {
+                    v[k][j] = v[k][j] - (f * e[k] + g * d[k]);
+                }
+                d[j] = v[i - 1][j];
+                v[i][j] = T::zero();
+            }
+        }
+        d[i] = h;
+    }
+    for i in 0..n.saturating_sub(1) {
+        v[n - 1][i] = v[i][i];
+        v[i][i] = T::one();
+        let h = d[i + 1];
+        if h != T::zero() {
+            for k in 0..=i {
+                d[k] = v[k][i + 1] / h;
+            }
+            for j in 0..=i {
+                let mut g = T::zero();
+                for k in 0..=i {
+                    g = g + v[k][i + 1] * v[k][j];
+                }
+                for k in 0..=i {
+                    v[k][j] = v[k][j] - g * d[k];
+                }
+            }
+        }
+        for k in 0..=i {
+            v[k][i + 1] = T::zero();
+        }
+    }
+    for j in 0..n {
+        d[j] = v[n - 1][j];
+        v[n - 1][j] = T::zero();
+    }
+    v[n - 1][n - 1] = T::one();
+    e[0] = T::zero();
+}
+
+// Implicit QL iteration on the tridiagonal matrix, following tql2 of EISPACK.
+#[allow(clippy::needless_range_loop)]
+fn tridiagonal_ql<T: Float>(v:&mut [Vec<T>], d:&mut [T], e:&mut [T], iteration_cap:usize) -> Result<(), AlgaeError> {
+    let n = d.len();
+    for i in 1..n {
+        e[i - 1] = e[i];
+    }
+    e[n - 1] = T::zero();
+    let two = constant::<T>(2.0);
+    let mut f = T::zero();
+    let mut tst1 = T::zero();
+    let eps = T::epsilon();
+    for l in 0..n {
+        tst1 = tst1.max(d[l].abs() + e[l].abs());
+        let mut m = l;
+        while m < n - 1 && e[m].abs() > eps * tst1 {
+            m += 1;
+        }
+        if m > l {
+            let mut iterations = 0;
+            loop {
+                iterations += 1;
+                if iterations > iteration_cap {
+                    return Err(AlgaeError::NoConvergence {
+                        iterations:iteration_cap,
+                        last_estimate:d[l].to_f64().unwrap_or(f64::NAN),
+                    });
+                }
+                let mut g = d[l];
+                let mut p = (d[l + 1] - g) / (two * e[l]);
+                let mut r = p.hypot(T::one());
+                if p < T::zero() {
+                    r = -r;
+                }
+                d[l] = e[l] / (p + r);
+                d[l + 1] = e[l] * (p + r);
+                let dl1 = d[l + 1];
+                let mut h = g - d[l];
+                for i in (l + 2)..n {
+                    d[i] = d[i] - h;
+                }
+                f = f + h;
+                p = d[m];
+                let mut c = T::one();
+                let mut c2 = c;
+                let mut c3 = c;
+                let el1 = e[l + 1];
+                let mut s = T::zero();
+                let mut s2 = T::zero();
+                for i in (l..m).rev() {
+                    c3 = c2;
+                    c2 = c;
+                    s2 = s;
+                    g = c * e[i];
+                    h = c * p;
+                    r = p.hypot(e[i]);
+                    e[i + 1] = s * r;
+                    s = e[i] / r;
+                    c = p / r;
+                    p = c * d[i] - s * g;
+                    d[i + 1] = h + s * (c * g + s * d[i]);
+                    for row in v.iter_mut() {
+                        h = row[i + 1];
+                        row[i + 1] = s * row[i] + c * h;
+                        row[i] = c * row[i] - s * h;
+                    }
+                }
+                p = -s * s2 * c3 * el1 * e[l] / dl1;
+                e[l] = s * p;
+                d[l] = c * p;
+                if e[l].abs() <= eps * tst1 {
+                    break;
+                }
+            }
+        }
+        d[l] = d[l] + f;
+        e[l] = T::zero();
+    }
+    Ok(())
+}
+
+// Orthogonal reduction to upper Hessenberg form, following orthes of EISPACK.
+// On return h is the Hessenberg matrix and v the accumulated transformation.
+#[allow(clippy::needless_range_loop)]
+fn hessenberg<T: Float>(h:&mut [Vec<T>], v:&mut [Vec<T>]) {
+    let n = h.len();
+    if n < 3 {
+        return;
+    }
+    let high = n - 1;
+    let mut ort = vec![T::zero(); n];
+    for m in 1..high {
+        let mut scale = T::zero();
+        for i in m..=high {
+            scale = scale + h[i][m - 1].abs();
+        }
+        if scale == T::zero() {
+            continue;
+        }
+        let mut hh = T::zero();
+        for i in (m..=high).rev() {
+            ort[i] = h[i][m - 1] / scale;
+            hh = hh + ort[i] * ort[i];
+        }
+        let mut g = hh.sqrt();
+        if ort[m] > T::zero() {
+            g = -g;
+        }
+        hh = hh - ort[m] * g;
+        ort[m] = ort[m] - g;
+        for j in m..n {
+            let mut f = T::zero();
+            for i in (m..=high).rev() {
+                f = f + ort[i] * h[i][j];
+            }
+            f = f / hh;
+            for i in m..=high {
+                h[i][j] = h[i][j] - f * ort[i];
+            }
+        }
+        for row in h.iter_mut() {
+            let mut f = T::zero();
+            for j in (m..=high).rev() {
+                f = f + ort[j] * row[j];
+            }
+            f = f / hh;
+            for j in m..=high {
+                row[j] = row[j] - f * ort[j];
+            }
+        }
+        ort[m] = scale * ort[m];
+        h[m][m - 1] = scale * g;
+    }
+    for m in (1..high).rev() {
+        if h[m][m - 1] == T::zero() {
+            continue;
+        }
+        for i in (m + 1)..=high {
+            ort[i] = h[i][m - 1];
+        }
+        for j in m..=high {
+            let mut g = T::zero();
+            for i in m..=high {
+                g = g + ort[i] * v[i][j];
+            }
+            g = (g / ort[m]) / h[m][m - 1];
+            for i in m..=high {
+                v[i][j] = v[i][j] + g * ort[i];
+            }
+        }
+    }
+}
+
+// Shifted double QR iteration on the Hessenberg matrix followed by back substitution for the
+// eigenvectors, following hqr2 of EISPACK in the formulation of JAMA. A complex pair
+// d[i] ± i * e[i] with e[i] > 0 has the eigenvector v[.][i] + i * v[.][i + 1].
+fn hessenberg_qr<T: Float>(h:&mut [Vec<T>], v:&mut [Vec<T>], d:&mut [T], e:&mut [T], iteration_cap:usize)
+-> Result<(), AlgaeError> {
+    let norm = schur_iteration(h, v, d, e, iteration_cap)?;
+    schur_eigenvectors(h, v, d, e, norm);
+    Ok(())
+}
+
+// The iteration part of hqr2. On return the upper triangle of h together with the subdiagonal
+// entries of the complex pairs is the real Schur form and v holds the Schur vectors, the entries
+// below are left as they are. Returns the norm of the Hessenberg matrix.
+fn schur_iteration<T: Float>(h:&mut [Vec<T>], v:&mut [Vec<T>], d:&mut [T], e:&mut [T], iteration_cap:usize)
+-> Result<T, AlgaeError> {
+    let nn = h.len() as isize;
+    let at = |i:isize| i as usize;
+    let eps = T::epsilon();
+    let two = constant::<T>(2.0);
+    let mut exshift = T::zero();
+    let (mut p, mut q, mut r) = (T::zero(), T::zero(), T::zero());
+    let (mut s, mut z, mut w, mut x, mut y):(T, T, T, T, T);
+
+    let mut norm = T::zero();
+    for i in 0..nn {
+        for j in isize::max(i - 1, 0)..nn {
+            norm = norm + h[at(i)][at(j)].abs();
+        }
+    }
+
+    let mut n = nn - 1;
+    let mut iterations = 0;
+    let mut total = 0;
+    while n >= 0 {
+        // Looks for a single small subdiagonal element.
+        let mut l = n;
+        while l > 0 {
+            s = h[at(l - 1)][at(l - 1)].abs() + h[at(l)][at(l)].abs();
+            if s == T::zero() {
+                s = norm;
+            }
+            if h[at(l)][at(l - 1)].abs() < eps * s {
+                break;
+            }
+            l -= 1;
+        }
+
+        if l == n {
+            // One root found.
+            h[at(n)][at(n)] = h[at(n)][at(n)] + exshift;
+            d[at(n)] = h[at(n)][at(n)];
+            e[at(n)] = T::zero();
+            n -= 1;
+            iterations = 0;
+        } else if l == n - 1 {
+            // Two roots found.
+            w = h[at(n)][at(n - 1)] * h[at(n - 1)][at(n)];
+            p = (h[at(n - 1)][at(n - 1)] - h[at(n)][at(n)]) / two;
+            q = p * p + w;
+            z = q.abs().sqrt();
+            h[at(n)][at(n)] = h[at(n)][at(n)] + exshift;
+            h[at(n - 1)][at(n - 1)] = h[at(n - 1)][at(n - 1)] + exshift;
+            x = h[at(n)][at(n)];
+            if q >= T::zero() {
+                // A real pair.
+                z = if p >= T::zero() { p + z } else { p - z };
+                d[at(n - 1)] = x + z;
+                d[at(n)] = d[at(n - 1)];
+                if z != T::zero() {
+                    d[at(n)] = x - w / z;
+                }
+                e[at(n - 1)] = T::zero();
+                e[at(n)] = T::zero();
+                x = h[at(n)][at(n - 1)];
+                s = x.abs() + z.abs();
+                p = x / s;
+                q = z / s;
+                r = (p * p + q * q).sqrt();
+                p = p / r;
+                q = q / r;
+                for j in (n - 1)..nn {
+                    z = h[at(n - 1)][at(j)];
+                    h[at(n - 1)][at(j)] = q * z + p * h[at(n)][at(j)];
+                    h[at(n)][at(j)] = q * h[at(n)][at(j)] - p * z;
+                }
+                for i in 0..=n {
+                    z = h[at(i)][at(n - 1)];
+                    h[at(i)][at(n - 1)] = q * z + p * h[at(i)][at(n)];
+                    h[at(i)][at(n)] = q * h[at(i)][at(n)] - p * z;
+                }
+                for row in v.iter_mut() {
+                    z = row[at(n - 1)];
+                    row[at(n - 1)] = q * z + p * row[at(n)];
+                    row[at(n)] = q * row[at(n)] - p * z;
+                }
+            } else {
+                // A complex pair.
+                d[at(n - 1)] = x + p;
+                d[at(n)] = x + p;
+                e[at(n - 1)] = z;
+                e[at(n)] = -z;
+            }
+            n -= 2;
+            iterations = 0;
+        } else {
+            // No convergence yet.
+            x = h[at(n)][at(n)];
+            y = T::zero();
+            w = T::zero();
+            if l < n {
+                y = h[at(n - 1)][at(n - 1)];
+                w = h[at(n)][at(n - 1)] * h[at(n - 1)][at(n)];
+            }
+            // Wilkinson's original ad hoc shift.
+            if iterations == 10 {
+                exshift = exshift + x;
+                for i in 0..=n {
+                    h[at(i)][at(i)] = h[at(i)][at(i)] - x;
+                }
+                s = h[at(n)][at(n - 1)].abs() + h[at(n - 1)][at(n - 2)].abs();
+                x = constant::<T>(0.75) * s;
+                y = x;
+                w = constant::<T>(-0.4375) * s * s;
+            }
+            // MATLAB's ad hoc shift.
+            if iterations == 30 {
+                s = (y - x) / two;
+                s = s * s + w;
+                if s > T::zero() {
+                    s = s.sqrt();
+                    if y < x {
+                        s = -s;
+                    }
+                    s = x - w / ((y - x) / two + s);
+                    for i in 0..=n {
+                        h[at(i)][at(i)] = h[at(i)][at(i)] - s;
+                    }
+                    exshift = exshift + s;
+                    x = constant(0.964);
+                    y = x;
+                    w = x;
+                }
+            }
+            iterations += 1;
+            total += 1;
+            if total > iteration_cap {
+                return Err(AlgaeError::NoConvergence {
+                    iterations:iteration_cap,
+                    last_estimate:h[at(n)][at(n)].to_f64().unwrap_or(f64::NAN),
+                });
+            }
+
+            // Looks for two consecutive small subdiagonal elements.
+            let mut m = n - 2;
+            while m >= l {
+                z = h[at(m)][at(m)];
+                r = x - z;
+                s = y - z;
+                p = (r * s - w) / h[at(m + 1)][at(m)] + h[at(m)][at(m + 1)];
+                q = h[at(m + 1)][at(m + 1)] - z - r - s;
+                r = h[at(m + 2)][at(m + 1)];
+                s = p.abs() + q.abs() + r.abs();
+                p = p / s;
+                q = q / s;
+                r = r / s;
+                if m == l {
+                    break;
+                }
+                if h[at(m)][at(m - 1)].abs() * (q.abs() + r.abs())
+                    < eps * (p.abs() * (h[at(m - 1)][at(m - 1)].abs() + z.abs() + h[at(m + 1)][at(m + 1)].abs())) {
+                    break;
+                }
+                m -= 1;
+            }
+            for i in (m + 2)..=n {
+                h[at(i)][at(i - 2)] = T::zero();
+                if i > m + 2 {
+                    h[at(i)][at(i - 3)] = T::zero();
+                }
+            }
+
+            // Double QR step on rows l..=n and columns m..=n.
+            for k in m..n {
+                let not_last = k != n - 1;
+                if k != m {
+                    p = h[at(k)][at(k - 1)];
+                    q = h[at(k + 1)][at(k - 1)];
+                    r = if not_last { h[at(k + 2)][at(k - 1)] } else { T::zero() };
+                    x = p.abs() + q.abs() + r.abs();
+                    if x == T::zero() {
+                        continue;
+                    }
+                    p = p / x;
+                    q = q / x;
+                    r = r / x;
+                }
+                s = (p * p + q * q + r * r).sqrt();
+                if p < T::zero() {
+                    s = -s;
+                }
+                if s != T::zero() {
+                    if k != m {
+                        h[at(k)][at(k - 1)] = -s * x;
+                    } else if l != m {
+                        h[at(k)][at(k - 1)] = -h[at(k)][at(k - 1)];
+                    }
+                    p = p + s;
+                    x = p / s;
+                    y = q / s;
+                    z = r / s;
+                    q = q / p;
+                    r = r / p;
+                    for j in k..nn {
+                        p = h[at(k)][at(j)] + q * h[at(k + 1)][at(j)];
+                        if not_last {
+                            p = p + r * h[at(k + 2)][at(j)];
+                            h[at(k + 2)][at(j)] = h[at(k + 2)][at(j)] - p * z;
+                        }
+                        h[at(k)][at(j)] = h[at(k)][at(j)] - p * x;
+                        h[at(k + 1)][at(j)] = h[at(k + 1)][at(j)] - p * y;
+                    }
+                    for i in 0..=isize::min(n, k + 3) {
+                        p = x * h[at(i)][at(k)] + y * h[at(i)][at(k + 1)];
+                        if not_last {
+                            p = p + z * h[at(i)][at(k + 2)];
+                            h[at(i)][at(k + 2)] = h[at(i)][at(k + 2)] - p * r;
+                        }
+                        h[at(i)][at(k)] = h[at(i)][at(k)] - p;
+                        h[at(i)][at(k + 1)] = h[at(i)][at(k + 1)] - p * q;
+                    }
+                    for row in v.iter_mut() {
+                        p = x * row[at(k)] + y * row[at(k + 1)];
+                        if not_last {
+                            p = p + z * row[at(k + 2)];
+                            row[at(k + 2)] = row[at(k + 2)] - p * r;
+                        }
+                        row[at(k)] = row[at(k)] - p;
+                        row[at(k + 1)] = row[at(k + 1)] - p * q;
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(norm)
+}
+
+// The back substitution part of hqr2, turns the Schur vectors into eigenvectors.
+fn schur_eigenvectors<T: Float>(h:&mut [Vec<T>], v:&mut [Vec<T>], d:&[T], e:&[T], norm:T) {
+    let nn = h.len() as isize;
+    let at = |i:isize| i as usize;
+    let eps = T::epsilon();
+    let two = constant::<T>(2.0);
+    let (mut r, mut s, mut z) = (T::zero(), T::zero(), T::zero());
+    let (mut p, mut q, mut t, mut w, mut x, mut y):(T, T, T, T, T, T);
+
+    // Back substitution for the eigenvectors of the upper triangular form.
+    if norm == T::zero() {
+        return;
+    }
+    for n in (0..nn).rev() {
+        p = d[at(n)];
+        q = e[at(n)];
+        if q == T::zero() {
+            // A real vector.
+            let mut l = n;
+            h[at(n)][at(n)] = T::one();
+            for i in (0..n).rev() {
+                w = h[at(i)][at(i)] - p;
+                r = T::zero();
+                for j in l..=n {
+                    r = r + h[at(i)][at(j)] * h[at(j)][at(n)];
+                }
+                if e[at(i)] < T::zero() {
+                    z = w;
+                    s = r;
+                } else {
+                    l = i;
+                    if e[at(i)] == T::zero() {
+                        h[at(i)][at(n)] = if w != T::zero() { -r / w } else { -r / (eps * norm) };
+                    } else {
+                        x = h[at(i)][at(i + 1)];
+                        y = h[at(i + 1)][at(i)];
+                        q = (d[at(i)] - p) * (d[at(i)] - p) + e[at(i)] * e[at(i)];
+                        t = (x * s - z * r) / q;
+                        h[at(i)][at(n)] = t;
+                        h[at(i + 1)][at(n)] = if x.abs() > z.abs() { (-r - w * t) / x } else { (-s - y * t) / z };
+                    }
+                    t = h[at(i)][at(n)].abs();
+                    if (eps * t) * t > T::one() {
+                        for j in i..=n {
+                            h[at(j)][at(n)] = h[at(j)][at(n)] / t;
+                        }
+                    }
+                }
+            }
+        } else if q < T::zero() {
+            // A complex vector, the last component is chosen imaginary.
+            let mut l = n - 1;
+            if h[at(n)][at(n - 1)].abs() > h[at(n - 1)][at(n)].abs() {
+                h[at(n - 1)][at(n - 1)] = q / h[at(n)][at(n - 1)];
+                h[at(n - 1)][at(n)] = -(h[at(n)][at(n)] - p) / h[at(n)][at(n - 1)];
+            } else {
+                let (re, im) = cdiv(T::zero(), -h[at(n - 1)][at(n)], h[at(n - 1)][at(n - 1)] - p, q);
+                h[at(n - 1)][at(n - 1)] = re;
+                h[at(n - 1)][at(n)] = im;
+            }
+            h[at(n)][at(n - 1)] = T::zero();
+            h[at(n)][at(n)] = T::one();
+            for i in (0..(n - 1)).rev() {
+                let mut ra = T::zero();
+                let mut sa = T::zero();
+                for j in l..=n {
+                    ra = ra + h[at(i)][at(j)] * h[at(j)][at(n - 1)];
+                    sa = sa + h[at(i)][at(j)] * h[at(j)][at(n)];
+                }
+                w = h[at(i)][at(i)] - p;
+                if e[at(i)] < T::zero() {
+                    z = w;
+                    r = ra;
+                    s = sa;
+                } else {
+                    l = i;
+                    if e[at(i)] == T::zero() {
+                        let (re, im) = cdiv(-ra, -sa, w, q);
+                        h[at(i)][at(n - 1)] = re;
+                        h[at(i)][at(n)] = im;
+                    } else {
+                        x = h[at(i)][at(i + 1)];
+                        y = h[at(i + 1)][at(i)];
+                        let mut vr = (d[at(i)] - p) * (d[at(i)] - p) + e[at(i)] * e[at(i)] - q * q;
+                        let vi = (d[at(i)] - p) * two * q;
+                        if vr == T::zero() && vi == T::zero() {
+                            vr = eps * norm * (w.abs() + q.abs() + x.abs() + y.abs() + z.abs());
+                        }
+                        let (re, im) = cdiv(x * r - z * ra + q * sa, x * s - z * sa - q * ra, vr, vi);
+                        h[at(i)][at(n - 1)] = re;
+                        h[at(i)][at(n)] = im;
+                        if x.abs() > z.abs() + q.abs() {
+                            h[at(i + 1)][at(n - 1)] = (-ra - w * h[at(i)][at(n - 1)] + q * h[at(i)][at(n)]) / x;
+                            h[at(i + 1)][at(n)] = (-sa - w * h[at(i)][at(n)] - q * h[at(i)][at(n - 1)]) / x;
+                        } else {
+                            let (re, im) = cdiv(-r - y * h[at(i)][at(n - 1)], -s - y * h[at(i)][at(n)], z, q);
+                            h[at(i + 1)][at(n - 1)] = re;
+                            h[at(i + 1)][at(n)] = im;
+                        }
+                    }
+                    t = h[at(i)][at(n - 1)].abs().max(h[at(i)][at(n)].abs());
+                    if (eps * t) * t > T::one() {
+                        for j in i..=n {
+                            h[at(j)][at(n - 1)] = h[at(j)][at(n - 1)] / t;
+                            h[at(j)][at(n)] = h[at(j)][at(n)] / t;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    // Back transformation to the eigenvectors of the original matrix.
+    for j in (0..nn).rev() {
+        for row in v.iter_mut() {
+            z = T::zero();
+            for k in 0..=j {
+                z = z + row[at(k)] * h[at(k)][at(j)];
+            }
+            row[at(j)] = z;
+        }
+    }
+}
+
+// Real Schur form A = Q * T * Q^T with orthogonal Q and quasi upper triangular T. The diagonal
+// blocks are listed as (first index, size), a block of size two holds a complex conjugate pair.
+pub(in crate::array) struct RealSchur<T> {
+    pub t:Array<T>,
+    pub q:Array<T>,
+    pub blocks:Vec<(usize, usize)>,
+}
+
+pub(in crate::array) fn real_schur<T: Float>(a:&Array<T>) -> Result<RealSchur<T>, AlgaeError> {
+    if a.size.0 != a.size.1 {
+        return Err(AlgaeError::NotSquare {
+            size:a.size,
+        });
+    }
+    let n = a.size.0;
+    let mut h = to_rows(a);
+    let mut v = to_rows(&Array::identity(n));
+    let mut d = vec![T::zero(); n];
+    let mut e = vec![T::zero(); n];
+    if n > 0 {
+        hessenberg(&mut h, &mut v);
+        schur_iteration(&mut h, &mut v, &mut d, &mut e, 30 * n * n)?;
+    }
+    let mut blocks = Vec::<(usize, usize)>::with_capacity(n);
+    let mut i = 0;
+    while i < n {
+        let size = if e[i] > T::zero() { 2 } else { 1 };
+        blocks.push((i, size));
+        i += size;
+    }
+    let mut t = Array::new_filled((n, n), T::zero());
+    let mut q = Array::new_filled((n, n), T::zero());
+    for row in 0..n {
+        for col in 0..n {
+            q[(row, col)] = v[row][col];
+        }
+    }
+    for &(start, size) in blocks.iter() {
+        for row in start..(start + size) {
+            for col in start..n {
+                t[(row, col)] = h[row][col];
+            }
+        }
+    }
+    Ok(RealSchur {
+        t,
+        q,
+        blocks,
+    })
+}
+
+impl<T: Float> Array<T> {
+    // Hessenberg reduction followed by the shifted double QR algorithm.
+    pub fn eig(&self) -> Result<EigenDecomposition<T>, AlgaeError> {
+        if self.size.0 != self.size.1 {
+            return Err(AlgaeError::NotSquare {
+                size:self.size,
+            });
+        }
+        let n = self.size.0;
+        let mut h = to_rows(self);
+        let mut v = to_rows(&Array::identity(n));
+        let mut d = vec![T::zero(); n];
+        let mut e = vec![T::zero(); n];
+        if n > 0 {
+            hessenberg(&mut h, &mut v);
+            hessenberg_qr(&mut h, &mut v, &mut d, &mut e, 30 * n.max(1) * n.max(1))?;
+        }
+
+        let mut values = Vec::<Complex<T>>::with_capacity(n);
+        let mut vectors = Array::new_filled((n, n), Complex::new(T::zero(), T::zero()));
+        let mut col = 0;
+        while col < n {
+            if e[col] == T::zero() {
+                values.push(Complex::new(d[col], T::zero()));
+                for row in 0..n {
+                    vectors[(row, col)] = Complex::new(v[row][col], T::zero());
+                }
+                col += 1;
+            } else {
+                values.push(Complex::new(d[col], e[col]));
+                values.push(Complex::new(d[col], -e[col]));
+                for row in 0..n {
+                    let value = Complex::new(v[row][col], v[row][col + 1]);
+                    vectors[(row, col)] = value;
+                    vectors[(row, col + 1)] = value.conj();
+                }
+                col += 2;
+            }
+        }
+        for col in 0..n {
+            let norm = (0..n).fold(T::zero(), |acc, row| acc + vectors[(row, col)].norm_sqr()).sqrt();
+            if norm > T::zero() {
+                for row in 0..n {
+                    vectors[(row, col)] = vectors[(row, col)] / norm;
+                }
+            }
+        }
+        Ok(EigenDecomposition {
+            values,
+            vectors,
+        })
+    }
+
+    // Tridiagonalisation followed by the implicit QL algorithm. The input is symmetrised as
+    // (A + A^T) / 2, so only the symmetric part of A is taken into account.
+    pub fn eigh(&self) -> Result<SymmetricEigenDecomposition<T>, AlgaeError> {
+        if self.size.0 != self.size.1 {
+            return Err(AlgaeError::NotSquare {
+                size:self.size,
+            });
+        }
+        let n = self.size.0;
+        let half = constant::<T>(0.5);
+        let mut v = (0..n)
+            .map(|row| (0..n).map(|col| (self[(row, col)] + self[(col, row)]) * half).collect::<Vec<T>>())
+            .collect::<Vec<_>>();
+        let mut d = vec![T::zero(); n];
+        let mut e = vec![T::zero(); n];
+        if n > 0 {
+            tridiagonalize(&mut v, &mut d, &mut e);
+            tridiagonal_ql(&mut v, &mut d, &mut e, 30 * n.max(1))?;
+        }
+
+        let mut order = (0..n).collect::<Vec<usize>>();
+        order.sort_by(|a, b| d[*a].partial_cmp(&d[*b]).unwrap_or(std::cmp::Ordering::Equal));
+        let mut vectors = Array::new_filled((n, n), T::zero());
+        for (col, index) in order.iter().enumerate() {
+            for row in 0..n {
+                vectors[(row, col)] = v[row][*index];
+            }
+        }
+        Ok(SymmetricEigenDecomposition {
+            values:order.iter().map(|i| d[*i]).collect(),
+            vectors,
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::array::eigenvalues::Array;
-    use crate::array::float_eq::FloatEq;
+    use crate::array::float_eq::{FloatEq, Tolerance};
+    use crate::error::error::AlgaeError;
+    use num::Complex;
+
+    #[test]
+    fn power_method_with_tolerance() {
+        let array = Array::new_mat(vec![
+            vec![2000.0, 1.0],
+            vec![1.0, 1000.0],
+        ]);
+        let x_zero = Array::new_mat(vec![vec![1.0], vec![1.0]]);
+        let (loose, _) = Array::power_method_with(&array, x_zero.clone(), 10000, Some(Tolerance::relative(1e-6)))
+            .unwrap_or_else(|e| panic!("Error: {}", e.0));
+        let (tight, _) = Array::power_method(&array, x_zero, 10000)
+            .unwrap_or_else(|e| panic!("Error: {}", e.0));
+        let expected = 1500.0 + f64::sqrt(250001.0);
+        assert!(f64::abs(loose - expected) < 1e-2);
+        assert!(tight.approx_eq_with(&expected, Tolerance::relative(1e-12)));
+        let capped = Array::power_method_with(&array, Array::new_mat(vec![vec![1.0], vec![1.0]]), 3, Some(Tolerance::exact()));
+        assert!(matches!(capped, Err((AlgaeError::NoConvergence { iterations:3, .. }, _, _))));
+    }
 
     #[test]
     fn power_method() {
-        let array = Array {
-            content:vec![
-                vec![1.0, 2.0],
-                vec![5.0, 4.0],
-            ],
-            size:(2, 2),
-        };
-        let e_0 = Array {
-            content:vec![
-                vec![1.0],
-                vec![0.0],
-            ],
-            size:(1, 2),
-        };
+        let array = Array::new_mat(vec![
+            vec![1.0, 2.0],
+            vec![5.0, 4.0],
+        ]);
+        let e_0 = Array::new_mat(vec![
+            vec![1.0],
+            vec![0.0],
+        ]);
         let iteration_cap = 10000;
         match Array::power_method(&array, e_0, iteration_cap) {
             Ok((lambda, x)) => {
@@ -111,20 +939,14 @@ mod tests {
 
     #[test]
     fn inverse_power_method() {
-        let array = Array {
-            content:vec![
-                vec![1.0, 2.0],
-                vec![0.0, 4.0],
-            ],
-            size:(2, 2),
-        };
-        let e_0 = Array {
-            content:vec![
-                vec![1.0],
-                vec![0.0],
-            ],
-            size:(1, 2),
-        };
+        let array = Array::new_mat(vec![
+            vec![1.0, 2.0],
+            vec![0.0, 4.0],
+        ]);
+        let e_0 = Array::new_mat(vec![
+            vec![1.0],
+            vec![0.0],
+        ]);
         let alpha = 0.0;
         let iteration_cap = 10000;
         match Array::inverse_power_method(&array, e_0, alpha, iteration_cap) {
@@ -136,4 +958,128 @@ mod tests {
             },
         }
     }
+
+    #[test]
+    fn inverse_power_method_shifted_estimate() {
+        // The eigenvalues are 2 and 5. With mu the dominant entry of (A - alpha * I)^-1 * x, the
+        // estimate is alpha + 1 / mu, which only agrees with (alpha + 1) / mu for alpha == 0.
+        let array = Array::new_mat(vec![
+            vec![4.0, 1.0],
+            vec![2.0, 3.0],
+        ]);
+        let e_0 = Array::new_vec(vec![1.0, 0.0]);
+        match Array::inverse_power_method(&array, e_0, 4.5, 10000) {
+            Ok((lambda, x)) => {
+                assert!(lambda.float_eq(&5.0), "found {}", lambda);
+                assert!((array * x.clone()).float_eq(&(x * lambda)))
+            },
+            Err((e, _, _)) => {
+                panic!("{}", e)
+            },
+        }
+    }
+
+    #[test]
+    fn inverse_power_method_zero_pivot_shift() {
+        // Without pivoting the shifted matrix has a zero in its first pivot position.
+        let array = Array::new_mat(vec![
+            vec![1.0, 1.0],
+            vec![1.0, -2.0],
+        ]);
+        let e_0 = Array::new_vec(vec![1.0, 0.5]);
+        match Array::inverse_power_method(&array, e_0, 1.0, 10000) {
+            Ok((lambda, x)) => {
+                assert!((array * x.clone()).float_eq(&(x * lambda)))
+            },
+            Err((e, _, _)) => {
+                panic!("{}", e)
+            },
+        }
+    }
+
+    #[test]
+    fn power_method_no_convergence() {
+        let array = Array::new_mat(vec![
+            vec![0.0, 1.0],
+            vec![1.0, 0.0],
+        ]);
+        let e_0 = Array::new_vec(vec![1.0, 0.0]);
+        match Array::power_method(&array, e_0, 10) {
+            Ok((lambda, x)) => panic!("Wrong result: {}, {}", lambda, x),
+            Err((e, _, _)) => assert!(matches!(e, AlgaeError::NoConvergence { iterations:10, .. })),
+        }
+    }
+
+    #[test]
+    fn power_method_negative_dominant_eigenvalue() {
+        let array = Array::new_mat(vec![
+            vec![-5.0, 0.0],
+            vec![0.0, 1.0],
+        ]);
+        let e_0 = Array::new_vec(vec![1.0, 1.0]);
+        match Array::power_method(&array, e_0, 10000) {
+            Ok((lambda, _)) => assert!(f64::abs(lambda + 5.0) < 1e-9),
+            Err((e, _, _)) => panic!("{}", e),
+        }
+    }
+
+    #[test]
+    fn eigh() {
+        let array = Array::new_mat(vec![
+            vec![2.0, 1.0, 0.0],
+            vec![1.0, 2.0, 1.0],
+            vec![0.0, 1.0, 2.0],
+        ]);
+        let root = f64::sqrt(2.0);
+        let expected = [2.0 - root, 2.0, 2.0 + root];
+        let actual = array.eigh().unwrap();
+        for (e, a) in expected.iter().zip(actual.values.iter()) {
+            assert!(f64::abs(e - a) < 1e-12);
+        }
+        let v = &actual.vectors;
+        assert!((v.transpose() * v.clone() - Array::identity(3)).norm_max() < 1e-12);
+        let lambda = Array::new_mat(vec![
+            vec![actual.values[0], 0.0, 0.0],
+            vec![0.0, actual.values[1], 0.0],
+            vec![0.0, 0.0, actual.values[2]],
+        ]);
+        assert!((array * v.clone() - v.clone() * lambda).norm_max() < 1e-12);
+    }
+
+    #[test]
+    fn eig_complex_pair() {
+        let array = Array::new_mat(vec![
+            vec![0.0, -1.0],
+            vec![1.0, 0.0],
+        ]);
+        let actual = array.eig().unwrap();
+        assert!((actual.values[0] - Complex::new(0.0, 1.0)).norm() < 1e-12);
+        assert!((actual.values[1] - Complex::new(0.0, -1.0)).norm() < 1e-12);
+    }
+
+    #[test]
+    fn eig() {
+        let array = Array::new_mat(vec![
+            vec![4.0, -2.0, 1.0, 3.0],
+            vec![1.0, 1.0, 5.0, -1.0],
+            vec![2.0, 0.0, -3.0, 2.0],
+            vec![-1.0, 4.0, 2.0, 1.0],
+        ]);
+        let actual = array.eig().unwrap();
+        let complex = array.map(|a| Complex::new(a, 0.0));
+        for (i, lambda) in actual.values.iter().enumerate() {
+            let v = actual.vectors.col(i).to_array();
+            let residual = (complex.clone() * v.clone()) - v.map(|a| a * lambda);
+            assert!(residual.iter().all(|r| r.norm() < 1e-10));
+        }
+        let trace = actual.values.iter().fold(Complex::new(0.0, 0.0), |acc, a| acc + a);
+        assert!((trace - Complex::new(3.0, 0.0)).norm() < 1e-10);
+    }
+
+    #[test]
+    fn eig_not_square() {
+        let array = Array::new_filled((2, 3), 1.0);
+        assert!(matches!(array.eig(), Err(AlgaeError::NotSquare { .. })));
+        assert!(matches!(array.eigh(), Err(AlgaeError::NotSquare { .. })));
+    }
 }
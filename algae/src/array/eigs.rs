@@ -0,0 +1,476 @@
+use crate::array::array::Array;
+use crate::array::eigenvalues::{EigenDecomposition, EigenResult, SymmetricEigenDecomposition, constant};
+use crate::array::factorizations::PluFactorization;
+use crate::array::float_eq::FloatEq;
+use crate::array::orthogonal::{dot, orthogonalize};
+use crate::error::error::AlgaeError;
+use num::traits::Float;
+use num::Complex;
+
+const MAX_RESTARTS:usize = 300;
+
+// Which end of the spectrum `eigs` looks for. Largest and Smallest compare the modulus.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Which<T> {
+    Largest,
+    Smallest,
+    NearShift(T),
+}
+
+impl<T: Float> Which<T> {
+    // Smaller keys are wanted first.
+    fn key(&self, value:Complex<T>) -> T {
+        match self {
+            Which::Largest => -value.norm(),
+            Which::Smallest => value.norm(),
+            Which::NearShift(sigma) => (value - Complex::new(*sigma, T::zero())).norm(),
+        }
+    }
+}
+
+type RitzPairs<T> = (Vec<Complex<T>>, Array<Complex<T>>);
+
+fn tolerance<T: Float>() -> T {
+    T::epsilon().powf(constant(0.75))
+}
+
+fn norm<T: Float>(a:&[T]) -> T {
+    dot(a, a).sqrt()
+}
+
+// A deterministic start vector that is unlikely to be orthogonal to any eigenvector.
+fn start_vector<T: Float>(size:usize, seed:usize) -> Vec<T> {
+    (0..size).map(|i| constant::<T>(((i + 1) * (seed + 1)) as f64).sin() + constant(0.1)).collect()
+}
+
+fn to_vec<T: Copy + Clone>(x:&Array<T>) -> Vec<T> {
+    x.iter().copied().collect()
+}
+
+// Ritz pairs of the projected matrix, the vectors are the columns of `vectors`.
+fn ritz_pairs<T: Float>(h:&Array<T>, symmetric:bool) -> Result<RitzPairs<T>, AlgaeError> {
+    if symmetric {
+        let decomposition = h.eigh()?;
+        Ok((
+            decomposition.values.iter().map(|v| Complex::new(*v, T::zero())).collect(),
+            decomposition.vectors.map(|v| Complex::new(v, T::zero())),
+        ))
+    } else {
+        let decomposition = h.eig()?;
+        Ok((decomposition.values, decomposition.vectors))
+    }
+}
+
+// Restarted Arnoldi iteration with full reorthogonalisation. After every cycle the wanted Ritz
+// vectors span an invariant subspace of the projected matrix, which is kept as the start of the
+// next cycle (a Krylov-Schur style thick restart). For symmetric operators this is Lanczos.
+fn restarted_arnoldi<T: Float, F: FnMut(&Array<T>) -> Array<T>>
+(size:usize, k:usize, which:Which<T>, symmetric:bool, mut matvec:F)
+-> Result<RitzPairs<T>, AlgaeError> {
+    if k > size {
+        return Err(AlgaeError::IndexOutOfBounds {
+            index:(k, 0),
+            size:(size, size),
+        });
+    }
+    if k == 0 {
+        return Ok((Vec::new(), Array::new_filled((0, size), Complex::new(T::zero(), T::zero()))));
+    }
+    let m = usize::min(size, usize::max(2 * k + 1, k + 20));
+    let eps = T::epsilon();
+
+    let mut v0 = start_vector::<T>(size, 0);
+    let n0 = norm(&v0);
+    v0.iter_mut().for_each(|a| *a = *a / n0);
+    let mut basis = vec![v0];
+    let mut h = vec![vec![T::zero(); m]; m + 1];
+    let mut locked = 0;
+    let mut last_estimate = T::nan();
+
+    for _ in 0..MAX_RESTARTS {
+        // Expands the factorisation A V = V H + f e_m^T to m columns.
+        for j in locked..m {
+            let mut w = to_vec(&matvec(&Array::new_vec(basis[j].clone())));
+            if w.len() != size {
+                return Err(AlgaeError::ShapeMismatch {
+                    expected:(1, size),
+                    found:(1, w.len()),
+                });
+            }
+            let scale = norm(&w);
+            let coefficients = orthogonalize(&mut w, &basis);
+            for (i, c) in coefficients.iter().enumerate() {
+                h[i][j] = *c;
+            }
+            let mut beta = norm(&w);
+            if beta <= eps * scale.max(T::min_positive_value()) {
+                // An invariant subspace has been found, continues with a fresh orthogonal direction.
+                beta = T::zero();
+                if j + 1 < m {
+                    let mut seed = 1;
+                    loop {
+                        w = start_vector(size, seed);
+                        orthogonalize(&mut w, &basis);
+                        if norm(&w) > constant(0.01) || seed > size {
+                            break;
+                        }
+                        seed += 1;
+                    }
+                }
+            }
+            h[j + 1][j] = beta;
+            let w_norm = norm(&w);
+            if w_norm > T::zero() {
+                w.iter_mut().for_each(|a| *a = *a / w_norm);
+            }
+            basis.push(w);
+        }
+        let beta = h[m][m - 1];
+
+        let projected = Array::new_mat(h[0..m].to_vec());
+        let (values, vectors) = ritz_pairs(&projected, symmetric)?;
+        let mut order = (0..m).collect::<Vec<usize>>();
+        order.sort_by(|a, b| {
+            which.key(values[*a]).partial_cmp(&which.key(values[*b])).unwrap_or(std::cmp::Ordering::Equal)
+        });
+
+        let scale = values.iter().fold(T::zero(), |acc, v| acc.max(v.norm()));
+        let converged = order[0..k].iter()
+            .all(|i| beta * vectors[(m - 1, *i)].norm() <= tolerance::<T>() * scale);
+        last_estimate = values[order[0]].re;
+        if converged || m == size {
+            let mut eigenvectors = Array::new_filled((k, size), Complex::new(T::zero(), T::zero()));
+            for (col, index) in order[0..k].iter().enumerate() {
+                for (r, v) in basis[0..m].iter().enumerate() {
+                    let y = vectors[(r, *index)];
+                    for (row, a) in v.iter().enumerate() {
+                        eigenvectors[(row, col)] = eigenvectors[(row, col)] + y * *a;
+                    }
+                }
+                let n = (0..size).fold(T::zero(), |acc, row| acc + eigenvectors[(row, col)].norm_sqr()).sqrt();
+                for row in 0..size {
+                    eigenvectors[(row, col)] = eigenvectors[(row, col)] / n;
+                }
+            }
+            return Ok((order[0..k].iter().map(|i| values[*i]).collect(), eigenvectors));
+        }
+
+        // Keeps more than the k wanted vectors to speed up convergence, without splitting a
+        // conjugate pair.
+        let mut keep = usize::max(k, usize::min(m - 1, k + (m - k) / 2));
+        if values[order[keep - 1]].im != T::zero() && keep < m
+            && values[order[keep]] == values[order[keep - 1]].conj() {
+            keep = if keep + 1 < m { keep + 1 } else { keep - 1 };
+        }
+
+        // Real orthonormal basis W of the kept Ritz vectors.
+        let mut w = Vec::<Vec<T>>::with_capacity(keep);
+        for index in order[0..keep].iter() {
+            let mut candidates = vec![(0..m).map(|r| vectors[(r, *index)].re).collect::<Vec<T>>()];
+            if values[*index].im > T::zero() {
+                candidates.push((0..m).map(|r| vectors[(r, *index)].im).collect());
+            }
+            for mut c in candidates {
+                let before = norm(&c);
+                orthogonalize(&mut c, &w);
+                let after = norm(&c);
+                if after > constant::<T>(1e-8) * before {
+                    c.iter_mut().for_each(|a| *a = *a / after);
+                    w.push(c);
+                }
+            }
+        }
+        let p = w.len();
+
+        // H' = W^T H W, the residual direction couples through the last row of W.
+        let mut restarted = vec![vec![T::zero(); m]; m + 1];
+        for (i, wi) in w.iter().enumerate() {
+            for (j, wj) in w.iter().enumerate() {
+                let mut value = T::zero();
+                for r in 0..m {
+                    for s in 0..m {
+                        value = value + wi[r] * h[r][s] * wj[s];
+                    }
+                }
+                restarted[i][j] = value;
+            }
+            restarted[p][i] = beta * wi[m - 1];
+        }
+        let mut new_basis = w.iter()
+            .map(|wi| {
+                let mut v = vec![T::zero(); size];
+                for (r, b) in basis[0..m].iter().enumerate() {
+                    for (a, x) in v.iter_mut().zip(b.iter()) {
+                        *a = *a + wi[r] * *x;
+                    }
+                }
+                v
+            })
+            .collect::<Vec<Vec<T>>>();
+        new_basis.push(basis[m].clone());
+        basis = new_basis;
+        h = restarted;
+        locked = p;
+    }
+    Err(AlgaeError::NoConvergence {
+        iterations:MAX_RESTARTS,
+        last_estimate:last_estimate.to_f64().unwrap_or(f64::NAN),
+    })
+}
+
+// The k wanted eigenpairs of a general operator of the given size, which is only accessed
+// through `matvec`. The eigenvalues are ordered from the most wanted one.
+// NearShift converges slowly on the plain operator, passing (A - σI)^-1 as `matvec` and
+// looking for the Largest values θ, which belong to λ = σ + 1 / θ, is much faster.
+pub fn arnoldi<T: Float, F: FnMut(&Array<T>) -> Array<T>>(size:usize, k:usize, which:Which<T>, matvec:F)
+-> Result<EigenDecomposition<T>, AlgaeError> {
+    let (values, vectors) = restarted_arnoldi(size, k, which, false, matvec)?;
+    Ok(EigenDecomposition {
+        values,
+        vectors,
+    })
+}
+
+// Like `arnoldi`, but `matvec` has to be symmetric, which gives real eigenvalues and orthonormal
+// eigenvectors.
+pub fn lanczos<T: Float, F: FnMut(&Array<T>) -> Array<T>>(size:usize, k:usize, which:Which<T>, matvec:F)
+-> Result<SymmetricEigenDecomposition<T>, AlgaeError> {
+    let (values, vectors) = restarted_arnoldi(size, k, which, true, matvec)?;
+    Ok(SymmetricEigenDecomposition {
+        values:values.iter().map(|v| v.re).collect(),
+        vectors:vectors.map(|v| v.re),
+    })
+}
+
+impl<T: Float + FloatEq> Array<T> {
+    // Smallest and NearShift use the shift-invert transformation with an LU factorisation of
+    // A - σI, Smallest with σ = 0. A singular shifted matrix falls back to the plain operator.
+    fn shift_invert<R, S: FnOnce(usize, Which<T>, &mut dyn FnMut(&Array<T>) -> Array<T>) -> Result<R, AlgaeError>>
+    (&self, which:Which<T>, solver:S) -> Result<(R, Option<T>), AlgaeError> {
+        if self.size.0 != self.size.1 {
+            return Err(AlgaeError::NotSquare {
+                size:self.size,
+            });
+        }
+        let n = self.size.0;
+        let sigma = match which {
+            Which::Largest => None,
+            Which::Smallest => Some(T::zero()),
+            Which::NearShift(sigma) => Some(sigma),
+        };
+        if let Some(sigma) = sigma {
+            let plu = PluFactorization::new(self - &(Array::identity(n) * sigma))?;
+            if !plu.is_singular() {
+                let result = solver(n, Which::Largest, &mut |x| plu.solve(x.clone()))?;
+                return Ok((result, Some(sigma)));
+            }
+        }
+        let result = solver(n, which, &mut |x| self * x)?;
+        Ok((result, None))
+    }
+
+    // The k wanted eigenpairs of the matrix, computed with Lanczos if the matrix is symmetric
+    // and with Arnoldi otherwise.
+    pub fn eigs(&self, k:usize, which:Which<T>) -> Result<EigenDecomposition<T>, AlgaeError> {
+        if self.size.0 == self.size.1 && *self == self.transpose() {
+            let decomposition = self.eigsh(k, which)?;
+            return Ok(EigenDecomposition {
+                values:decomposition.values.iter().map(|v| Complex::new(*v, T::zero())).collect(),
+                vectors:decomposition.vectors.map(|v| Complex::new(v, T::zero())),
+            });
+        }
+        let (mut decomposition, sigma) = self.shift_invert(which, |n, which, matvec| arnoldi(n, k, which, matvec))?;
+        if let Some(sigma) = sigma {
+            decomposition.values.iter_mut()
+                .for_each(|theta| *theta = Complex::new(sigma, T::zero()) + theta.inv());
+        }
+        Ok(decomposition)
+    }
+
+    // The k wanted eigenpairs of a symmetric matrix. Lanczos relies on the symmetry, so any
+    // other matrix is rejected instead of producing meaningless values.
+    pub fn eigsh(&self, k:usize, which:Which<T>) -> Result<SymmetricEigenDecomposition<T>, AlgaeError> {
+        if self.size.0 != self.size.1 {
+            return Err(AlgaeError::NotSquare {
+                size:self.size,
+            });
+        }
+        for row in 0..self.size.1 {
+            for col in (row + 1)..self.size.0 {
+                if self[(row, col)] != self[(col, row)] {
+                    return Err(AlgaeError::NotSymmetric {
+                        index:(row, col),
+                    });
+                }
+            }
+        }
+        let (mut decomposition, sigma) = self.shift_invert(which, |n, which, matvec| lanczos(n, k, which, matvec))?;
+        if let Some(sigma) = sigma {
+            decomposition.values.iter_mut().for_each(|theta| *theta = sigma + theta.recip());
+        }
+        Ok(decomposition)
+    }
+
+    // Refines an approximate eigenvector with Rayleigh quotient iteration, which converges
+    // cubically for symmetric matrices. The error case carries the last estimates like
+    // `power_method`.
+    pub fn rayleigh_quotient_iteration(array:&Array<T>, x_zero:Array<T>, iteration_cap:usize)
+    -> EigenResult<T> {
+        if array.size.0 != array.size.1 {
+            return Err((AlgaeError::NotSquare { size:array.size }, T::nan(), x_zero));
+        }
+        let n = array.size.0;
+        let tolerance = tolerance::<T>() * array.norm();
+        let x_norm = x_zero.norm();
+        let mut x_k = x_zero / x_norm;
+        let mut mu_k = (x_k.transpose() * (array * &x_k))[(0, 0)];
+        for _ in 0..iteration_cap {
+            let residual = (array * &x_k - x_k.clone() * mu_k).norm();
+            if residual <= tolerance {
+                return Ok((mu_k, x_k));
+            }
+            let shifted = match PluFactorization::new(array - &(Array::identity(n) * mu_k)) {
+                Ok(shifted) => shifted,
+                Err(e) => return Err((e, mu_k, x_k)),
+            };
+            if shifted.is_singular() {
+                // The shift is an eigenvalue up to rounding.
+                return Ok((mu_k, x_k));
+            }
+            let y_k = shifted.solve(x_k);
+            let y_norm = y_k.norm();
+            x_k = y_k / y_norm;
+            mu_k = (x_k.transpose() * (array * &x_k))[(0, 0)];
+        }
+        Err((
+            AlgaeError::NoConvergence {
+                iterations:iteration_cap,
+                last_estimate:mu_k.to_f64().unwrap_or(f64::NAN),
+            },
+            mu_k,
+            x_k,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::array::eigs::{Array, Which, lanczos};
+    use crate::error::error::AlgaeError;
+    use num::Complex;
+    use std::f64::consts::PI;
+
+    // The 1D Laplacian with eigenvalues 2 - 2 cos(j π / (n + 1)).
+    fn laplacian(n:usize) -> Array<f64> {
+        let mut a = Array::new_filled((n, n), 0.0);
+        for i in 0..n {
+            a[(i, i)] = 2.0;
+            if i + 1 < n {
+                a[(i, i + 1)] = -1.0;
+                a[(i + 1, i)] = -1.0;
+            }
+        }
+        a
+    }
+
+    fn laplacian_eigenvalue(n:usize, j:usize) -> f64 {
+        2.0 - 2.0 * f64::cos(j as f64 * PI / (n as f64 + 1.0))
+    }
+
+    #[test]
+    fn eigsh_largest_and_smallest() {
+        let n = 60;
+        let a = laplacian(n);
+        let largest = a.eigsh(3, Which::Largest).unwrap();
+        let smallest = a.eigsh(2, Which::Smallest).unwrap();
+        for (i, lambda) in largest.values.iter().enumerate() {
+            assert!(f64::abs(lambda - laplacian_eigenvalue(n, n - i)) < 1e-10);
+        }
+        for (i, lambda) in smallest.values.iter().enumerate() {
+            assert!(f64::abs(lambda - laplacian_eigenvalue(n, i + 1)) < 1e-10);
+        }
+        for (i, lambda) in largest.values.iter().enumerate() {
+            let v = largest.vectors.col(i).to_array();
+            assert!((&a * &v - v.clone() * *lambda).norm_max() < 1e-8);
+        }
+    }
+
+    #[test]
+    fn eigsh_rejects_non_symmetric() {
+        let mut a = laplacian(10);
+        a[(2, 3)] = 0.5;
+        assert_eq!(Err(AlgaeError::NotSymmetric { index:(2, 3) }), a.eigsh(2, Which::Largest).map(|d| d.values));
+        let a = Array::new_mat(vec![vec![1.0, 2.0, 3.0]]);
+        assert_eq!(Err(AlgaeError::NotSquare { size:(3, 1) }), a.eigsh(1, Which::Largest).map(|d| d.values));
+    }
+
+    #[test]
+    fn lanczos_matrix_free() {
+        let n = 100;
+        let matvec = |x:&Array<f64>| {
+            let mut y = x.clone() * 2.0;
+            for i in 0..n {
+                if i > 0 {
+                    y[(i, 0)] -= x[(i - 1, 0)];
+                }
+                if i + 1 < n {
+                    y[(i, 0)] -= x[(i + 1, 0)];
+                }
+            }
+            y
+        };
+        let actual = lanczos(n, 2, Which::Largest, matvec).unwrap();
+        assert!(f64::abs(actual.values[0] - laplacian_eigenvalue(n, n)) < 1e-10);
+        assert!(f64::abs(actual.values[1] - laplacian_eigenvalue(n, n - 1)) < 1e-10);
+    }
+
+    #[test]
+    fn eigs_general() {
+        // Upper triangular, so the eigenvalues are the diagonal 1, ..., n.
+        let n = 40;
+        let mut a = Array::new_filled((n, n), 0.0);
+        for row in 0..n {
+            for col in row..n {
+                a[(row, col)] = if row == col { (row + 1) as f64 } else { f64::sin((row * n + col) as f64) };
+            }
+        }
+        let largest = a.eigs(2, Which::Largest).unwrap();
+        assert!((largest.values[0] - Complex::new(40.0, 0.0)).norm() < 1e-8);
+        assert!((largest.values[1] - Complex::new(39.0, 0.0)).norm() < 1e-8);
+        let near = a.eigs(1, Which::NearShift(10.3)).unwrap();
+        assert!((near.values[0] - Complex::new(10.0, 0.0)).norm() < 1e-10);
+        let complex = a.map(|x| Complex::new(x, 0.0));
+        let v = near.vectors.col(0).to_array();
+        let residual = complex * v.clone() - v.map(|x| x * near.values[0]);
+        assert!(residual.iter().all(|r| r.norm() < 1e-8));
+    }
+
+    #[test]
+    fn eigs_complex_pair() {
+        // A rotation block with eigenvalues 3 ± 4i dominates the diagonal.
+        let mut a = Array::new_filled((30, 30), 0.0);
+        for i in 0..30 {
+            a[(i, i)] = 1.0 + i as f64 * 0.1;
+        }
+        a[(0, 0)] = 3.0;
+        a[(1, 1)] = 3.0;
+        a[(0, 1)] = -4.0;
+        a[(1, 0)] = 4.0;
+        let actual = a.eigs(2, Which::Largest).unwrap();
+        assert!((actual.values[0] - Complex::new(3.0, 4.0)).norm() < 1e-8);
+        assert!((actual.values[1] - Complex::new(3.0, -4.0)).norm() < 1e-8);
+    }
+
+    #[test]
+    fn rayleigh_quotient_iteration() {
+        let a = laplacian(10);
+        let x_0 = Array::new_vec((0..10).map(|i| f64::sin((i + 1) as f64 * PI / 11.0) + 0.05).collect());
+        match Array::rayleigh_quotient_iteration(&a, x_0, 20) {
+            Ok((lambda, x)) => {
+                assert!(f64::abs(lambda - laplacian_eigenvalue(10, 1)) < 1e-12);
+                assert!((&a * &x - x.clone() * lambda).norm() < 1e-10);
+            },
+            Err((e, _, _)) => panic!("{}", e),
+        }
+    }
+}
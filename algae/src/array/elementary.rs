@@ -0,0 +1,387 @@
+use crate::array::array::Array;
+use crate::array::view::ArrayViewMut;
+use crate::error::error::AlgaeError;
+use std::ops::{Add, Sub, Neg, Mul, Div};
+use num::traits::{One, Zero};
+
+// Permutation of 0..len that acts on arrays without building the permutation matrix.
+// Row i of P * A is row map[i] of A, the same convention as PivotRecord::row_permutation.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Permutation {
+    map:Vec<usize>,
+}
+
+// Elementary row operation, applied in place. Every step of echelon_form is one of these.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RowOperation<T> {
+    // Exchanges the two rows.
+    Swap(usize, usize),
+    // row = factor * row
+    Scale {
+        row:usize,
+        factor:T,
+    },
+    // to = to + factor * from
+    AddMultiple {
+        from:usize,
+        to:usize,
+        factor:T,
+    },
+}
+
+impl Permutation {
+    pub fn identity(len:usize) -> Self {
+        Permutation {
+            map:(0..len).collect(),
+        }
+    }
+
+    pub fn new(map:Vec<usize>) -> Self {
+        match Self::try_new(map) {
+            Ok(permutation) => permutation,
+            Err(e) => panic!("Every index below the length has to appear exactly once. {}", e),
+        }
+    }
+
+    pub fn try_new(map:Vec<usize>) -> Result<Self, AlgaeError> {
+        let mut seen = vec![false; map.len()];
+        for (index, &target) in map.iter().enumerate() {
+            if target >= map.len() || seen[target] {
+                return Err(AlgaeError::InvalidPermutation {
+                    index,
+                });
+            }
+            seen[target] = true;
+        }
+        Ok(Permutation {
+            map,
+        })
+    }
+
+    pub(in crate::array) fn from_vec_unchecked(map:Vec<usize>) -> Self {
+        Permutation {
+            map,
+        }
+    }
+
+    // Exchanges a and b and leaves everything else in place.
+    pub fn transposition(len:usize, a:usize, b:usize) -> Self {
+        let mut permutation = Permutation::identity(len);
+        permutation.swap(a, b);
+        permutation
+    }
+
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    pub fn as_slice(&self) -> &[usize] {
+        &self.map
+    }
+
+    // Applies the transposition of a and b after self, i.e. swaps the rows a and b of P * A.
+    pub fn swap(&mut self, a:usize, b:usize) {
+        self.map.swap(a, b);
+    }
+
+    // self * other, so applying the result equals applying other first and then self.
+    pub fn compose(&self, other:&Permutation) -> Permutation {
+        match self.try_compose(other) {
+            Ok(permutation) => permutation,
+            Err(e) => panic!("Only permutations of the same length can be composed. {}", e),
+        }
+    }
+
+    pub fn try_compose(&self, other:&Permutation) -> Result<Permutation, AlgaeError> {
+        if self.len() != other.len() {
+            return Err(AlgaeError::ShapeMismatch {
+                expected:(self.len(), self.len()),
+                found:(other.len(), other.len()),
+            });
+        }
+        Ok(Permutation {
+            map:self.map.iter().map(|&i| other.map[i]).collect(),
+        })
+    }
+
+    pub fn inverse(&self) -> Permutation {
+        let mut map = vec![0; self.len()];
+        for (i, &target) in self.map.iter().enumerate() {
+            map[target] = i;
+        }
+        Permutation {
+            map,
+        }
+    }
+
+    // 1 for even and -1 for odd permutations, a cycle of length l takes l - 1 transpositions.
+    pub fn sign(&self) -> i32 {
+        let mut visited = vec![false; self.len()];
+        let mut transpositions = 0;
+        for start in 0..self.len() {
+            let mut i = start;
+            let mut length = 0;
+            while !visited[i] {
+                visited[i] = true;
+                i = self.map[i];
+                length += 1;
+            }
+            transpositions += usize::saturating_sub(length, 1);
+        }
+        if transpositions % 2 == 0 { 1 } else { -1 }
+    }
+
+    // P * A
+    pub fn permute_rows<T: Clone>(&self, a:&Array<T>) -> Array<T> {
+        match self.try_permute_rows(a) {
+            Ok(array) => array,
+            Err(e) => panic!("To permute the rows of an array its height has to match the length of the permutation. {}", e),
+        }
+    }
+
+    pub fn try_permute_rows<T: Clone>(&self, a:&Array<T>) -> Result<Array<T>, AlgaeError> {
+        if a.size.1 != self.len() {
+            return Err(AlgaeError::ShapeMismatch {
+                expected:(a.size.0, self.len()),
+                found:a.size,
+            });
+        }
+        let mut content = Vec::<T>::with_capacity(a.size.0 * a.size.1);
+        for &row in self.map.iter() {
+            for col in 0..a.size.0 {
+                content.push(a[(row, col)].clone());
+            }
+        }
+        Ok(Array::from_content(content, a.size))
+    }
+
+    // A * P^T, column j of the result is column map[j] of A.
+    pub fn permute_cols<T: Clone>(&self, a:&Array<T>) -> Array<T> {
+        match self.try_permute_cols(a) {
+            Ok(array) => array,
+            Err(e) => panic!("To permute the columns of an array its width has to match the length of the permutation. {}", e),
+        }
+    }
+
+    pub fn try_permute_cols<T: Clone>(&self, a:&Array<T>) -> Result<Array<T>, AlgaeError> {
+        if a.size.0 != self.len() {
+            return Err(AlgaeError::ShapeMismatch {
+                expected:(self.len(), a.size.1),
+                found:a.size,
+            });
+        }
+        let mut content = Vec::<T>::with_capacity(a.size.0 * a.size.1);
+        for row in 0..a.size.1 {
+            for &col in self.map.iter() {
+                content.push(a[(row, col)].clone());
+            }
+        }
+        Ok(Array::from_content(content, a.size))
+    }
+
+    pub fn to_array<T: Clone + Zero + One>(&self) -> Array<T> {
+        let mut array = Array::new_filled((self.len(), self.len()), T::zero());
+        for (row, &col) in self.map.iter().enumerate() {
+            array[(row, col)] = T::one();
+        }
+        array
+    }
+}
+
+impl<'b> Mul<&'b Permutation> for &Permutation {
+    type Output = Permutation;
+    fn mul(self, other:&'b Permutation) -> Permutation {
+        self.compose(other)
+    }
+}
+
+impl<'b, T: Clone> Mul<&'b Array<T>> for &Permutation {
+    type Output = Array<T>;
+    fn mul(self, other:&'b Array<T>) -> Array<T> {
+        self.permute_rows(other)
+    }
+}
+
+impl<T: Clone + Add<Output = T> + Mul<Output = T>> RowOperation<T> {
+    // Largest row index the operation touches.
+    fn last_row(&self) -> usize {
+        match *self {
+            RowOperation::Swap(a, b) => usize::max(a, b),
+            RowOperation::Scale { row, .. } => row,
+            RowOperation::AddMultiple { from, to, .. } => usize::max(from, to),
+        }
+    }
+
+    pub fn apply(&self, a:&mut Array<T>) {
+        if let Err(e) = self.try_apply(a) {
+            panic!("The row operation has to stay within the array. {}", e);
+        }
+    }
+
+    pub fn try_apply(&self, a:&mut Array<T>) -> Result<(), AlgaeError> {
+        self.try_apply_view(&mut a.view_mut())
+    }
+
+    pub fn try_apply_view(&self, a:&mut ArrayViewMut<T>) -> Result<(), AlgaeError> {
+        if self.last_row() >= a.size.1 {
+            return Err(AlgaeError::IndexOutOfBounds {
+                index:(self.last_row(), 0),
+                size:a.size,
+            });
+        }
+        match *self {
+            RowOperation::Swap(first, second) => a.swap_rows(first, second),
+            RowOperation::Scale { row, ref factor } => {
+                for col in 0..a.size.0 {
+                    a[(row, col)] = a[(row, col)].clone() * factor.clone();
+                }
+            },
+            RowOperation::AddMultiple { from, to, ref factor } => {
+                for col in 0..a.size.0 {
+                    a[(to, col)] = a[(to, col)].clone() + a[(from, col)].clone() * factor.clone();
+                }
+            },
+        }
+        Ok(())
+    }
+
+    // Replays the operations in order, e.g. the ones recorded by echelon_form_recorded.
+    pub fn apply_all(operations:&[RowOperation<T>], a:&mut Array<T>) {
+        if let Err(e) = Self::try_apply_all(operations, a) {
+            panic!("The row operations have to stay within the array. {}", e);
+        }
+    }
+
+    pub fn try_apply_all(operations:&[RowOperation<T>], a:&mut Array<T>) -> Result<(), AlgaeError> {
+        let mut view = a.view_mut();
+        if let Some(row) = operations.iter().map(|operation| operation.last_row()).max() {
+            if row >= view.size.1 {
+                return Err(AlgaeError::IndexOutOfBounds {
+                    index:(row, 0),
+                    size:view.size,
+                });
+            }
+        }
+        for operation in operations.iter() {
+            operation.try_apply_view(&mut view)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: Clone + Zero + One + PartialEq
+ + Add<Output = T> + Sub<Output = T> + Neg<Output = T> + Mul<Output = T> + Div<Output = T>>
+RowOperation<T> {
+    // The operation undoing self, None for a scaling by zero.
+    pub fn inverse(&self) -> Option<RowOperation<T>> {
+        match *self {
+            RowOperation::Swap(a, b) => Some(RowOperation::Swap(a, b)),
+            RowOperation::Scale { row, ref factor } => {
+                if factor.is_zero() {
+                    None
+                } else {
+                    Some(RowOperation::Scale {
+                        row,
+                        factor:T::one() / factor.clone(),
+                    })
+                }
+            },
+            RowOperation::AddMultiple { from, to, ref factor } => Some(RowOperation::AddMultiple {
+                from,
+                to,
+                factor:-factor.clone(),
+            }),
+        }
+    }
+
+    // The elementary matrix E with E * A equal to applying the operation to A.
+    pub fn to_array(&self, size:usize) -> Array<T> {
+        match *self {
+            RowOperation::Swap(a, b) => Array::elementary_swap(size, (a, b)),
+            RowOperation::Scale { row, ref factor } => Array::elementary_multiply(size, row, factor.clone()),
+            RowOperation::AddMultiple { from, to, ref factor } => Array::elementary_add_into(size, from, to, factor.clone()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::array::elementary::{Permutation, RowOperation};
+    use crate::array::elementary::Array;
+    use crate::array::field_methods::{PivotingOptions, PivotingStrategy};
+    use crate::error::error::AlgaeError;
+    use num::rational::Ratio;
+
+    #[test]
+    fn permutation_matches_matrix() {
+        let p = Permutation::new(vec![2, 0, 1]);
+        let q = Permutation::transposition(3, 0, 1);
+        let a = Array::new_mat(vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 9]]);
+        assert_eq!(p.to_array::<i32>() * a.clone(), &p * &a);
+        assert_eq!(a.clone() * p.to_array::<i32>().transpose(), p.permute_cols(&a));
+        assert_eq!(p.to_array::<i32>() * q.to_array::<i32>(), (&p * &q).to_array());
+        assert_eq!(&p * &(&q * &a), &(&p * &q) * &a);
+        assert_eq!(Permutation::identity(3), p.compose(&p.inverse()));
+        assert_eq!(a, p.inverse().permute_rows(&p.permute_rows(&a)));
+    }
+
+    #[test]
+    fn permutation_sign() {
+        assert_eq!(1, Permutation::identity(4).sign());
+        assert_eq!(-1, Permutation::transposition(4, 1, 3).sign());
+        assert_eq!(1, Permutation::new(vec![1, 2, 0]).sign());
+        assert_eq!(-1, Permutation::new(vec![1, 2, 3, 0]).sign());
+        let p = Permutation::new(vec![3, 0, 2, 1, 4]);
+        assert_eq!(p.sign() as f64, p.to_array::<f64>().determinant());
+    }
+
+    #[test]
+    fn invalid_permutations() {
+        assert_eq!(Err(AlgaeError::InvalidPermutation { index:2 }), Permutation::try_new(vec![0, 2, 2]));
+        assert_eq!(Err(AlgaeError::InvalidPermutation { index:1 }), Permutation::try_new(vec![0, 3, 1]));
+        assert!(Permutation::identity(2).try_permute_rows(&Array::new_vec(vec![1, 2, 3])).is_err());
+        assert!(Permutation::identity(2).try_compose(&Permutation::identity(3)).is_err());
+    }
+
+    #[test]
+    fn row_operations_match_elementary_matrices() {
+        let a = Array::new_mat(vec![vec![1.0, 2.0], vec![3.0, 4.0], vec![5.0, 6.0]]);
+        let operations = [
+            RowOperation::Swap(0, 2),
+            RowOperation::Scale { row:1, factor:3.0 },
+            RowOperation::AddMultiple { from:0, to:2, factor:-2.0 },
+        ];
+        for operation in operations.iter() {
+            let mut actual = a.clone();
+            operation.apply(&mut actual);
+            assert_eq!(operation.to_array(3) * a.clone(), actual);
+            operation.inverse().unwrap().apply(&mut actual);
+            assert_eq!(a, actual);
+        }
+        assert_eq!(None, RowOperation::Scale { row:0, factor:0.0 }.inverse());
+        let mut b = a.clone();
+        assert!(RowOperation::AddMultiple { from:3, to:0, factor:1.0 }.try_apply(&mut b).is_err());
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn replay_echelon_form() {
+        let r = |n:i64| Ratio::from_integer(n);
+        let a = Array::new_mat(vec![
+            vec![r(0), r(2), r(-1), r(3)],
+            vec![r(1), r(1), r(4), r(0)],
+            vec![r(2), r(6), r(7), r(6)],
+        ]);
+        for strategy in [PivotingStrategy::None, PivotingStrategy::Partial, PivotingStrategy::Complete] {
+            let mut echelon = a.clone();
+            let (record, operations) = echelon.echelon_form_recorded(PivotingOptions::new(strategy, None));
+            let mut replayed = a.clone();
+            RowOperation::apply_all(&operations, &mut replayed);
+            assert_eq!(echelon, record.permute_cols(&replayed));
+        }
+    }
+}
@@ -1,11 +1,13 @@
 use crate::array::array::Array;
+use crate::array::view::ArrayView;
 use crate::array::methods::multiply_row;
 use crate::array::methods::multiply_add_row;
-use crate::array::field_methods::LinearSystemResult;
-use crate::array::float_eq::FloatEq;
+use crate::array::field_methods::{default_tolerance, LinearSystemResult, PivotingOptions, PivotingStrategy};
+use crate::array::float_eq::{FloatEq, PivotMagnitude, Tolerance};
+use crate::error::error::AlgaeError;
 use std::fmt;
 use std::ops::{Add, Sub, Neg, Mul, Div};
-use num::traits::{One, Zero};
+use num::traits::{One, Zero, Float, ToPrimitive};
 
 pub enum LuResult<T> {
     Single(Array<T>),
@@ -16,6 +18,10 @@ pub struct LuFactorization<T> {
     l:Array<T>,
     u:Array<T>,
     pub size:(usize, usize),
+    // The one it was factorized with, solves use it to tell zero entries apart.
+    tolerance:Tolerance,
+    // Largest magnitude of A, the tolerance is relative to it.
+    scale:f64,
 }
 
 impl<T: PartialEq> PartialEq for LuFactorization<T> {
@@ -25,8 +31,12 @@ impl<T: PartialEq> PartialEq for LuFactorization<T> {
 }
 
 impl<T: Copy + Clone + PartialEq + FloatEq> FloatEq for LuFactorization<T> {    
-    fn float_eq(&self, other:&Self) -> bool  {
-        self.size == other.size && self.l.float_eq(&other.l) && self.u.float_eq(&other.u)
+    fn approx_eq_with(&self, other:&Self, tolerance:Tolerance) -> bool  {
+        self.size == other.size && self.l.approx_eq_with(&other.l, tolerance) && self.u.approx_eq_with(&other.u, tolerance)
+    }
+
+    fn standard_tolerance() -> Tolerance {
+        T::standard_tolerance()
     }
 }
 
@@ -37,15 +47,25 @@ impl<T: fmt::Display> fmt::Display for LuFactorization<T> {
 }
 
 impl<T: Copy + Clone + Zero + One + PartialEq
-+ Add<Output = T> + Sub<Output = T> + Neg<Output = T> + Mul<Output = T> + Div<Output = T> + FloatEq>
++ Add<Output = T> + Sub<Output = T> + Neg<Output = T> + Mul<Output = T> + Div<Output = T> + FloatEq + PivotMagnitude>
 LuFactorization<T> {
-    pub fn new(mut a:Array<T>) -> Result<Self, String> {
+    pub fn new(a:Array<T>) -> Result<Self, AlgaeError> {
+        Self::new_with(a, None)
+    }
+
+    // Pivots that are negligible next to the largest magnitude of A are reported as ZeroPivot.
+    // None is relative, PivotMagnitude::default_tolerance scaled by the larger dimension.
+    pub fn new_with(mut a:Array<T>, tolerance:Option<Tolerance>) -> Result<Self, AlgaeError> {
+        let tolerance = tolerance.unwrap_or(default_tolerance::<T>(a.size));
+        let scale = a.iter().fold(0.0, |acc, x| f64::max(acc, x.magnitude()));
         let mut pivot = (0, 0);
         let mut l = Array::new_filled((a.size.1, a.size.1), T::zero());
         
         while pivot.0 < a.size.1 && pivot.1 < a.size.0 {
-            if a[pivot].float_eq(&T::zero()) {
-                return Err("There exists no LU factorization of this matrix".to_string())
+            if a[pivot] == T::zero() || tolerance.negligible(a[pivot].magnitude(), scale) {
+                return Err(AlgaeError::ZeroPivot {
+                    index:pivot.0,
+                })
             }
             let factor = T::one()/a[pivot];
             l[pivot] = a[pivot] * factor;
@@ -53,7 +73,7 @@ LuFactorization<T> {
                 if !T::is_zero(&a[(row, pivot.1)]) {
                     let u_factor = factor * -a[(row, pivot.1)];
                     l[(row, pivot.1)] = a[(row, pivot.1)] * factor;
-                    multiply_add_row(&mut a, pivot.0, row, u_factor, pivot.1);
+                    multiply_add_row(&mut a.view_mut(), pivot.0, row, u_factor, pivot.1);
                 }
             }
             pivot.0 += 1;
@@ -66,14 +86,24 @@ LuFactorization<T> {
                 l,
                 u:a,
                 size,
+                tolerance,
+                scale,
             }
         )
     }
 
-    fn solve_l(&self, y:Array<T>) -> Array<T> {
-        let mut temp = Array::concat_0_axis(self.u.clone(), y);        
+    // Builds [a | b] with a single allocation.
+    fn augment(a:ArrayView<T>, b:ArrayView<T>) -> Array<T> {
+        let mut temp = Array::new_filled((a.size.0 + b.size.0, a.size.1), T::zero());
+        temp.slice_mut(.., ..a.size.0).assign(a);
+        temp.slice_mut(.., a.size.0..).assign(b);
+        temp
+    }
+
+    fn solve_l(&self, y:ArrayView<T>) -> Array<T> {
+        let mut temp = Self::augment(self.u.view(), y);
         let mut pivot = (temp.size.1 - 1, 0);
-        while temp[pivot].float_eq(&T::zero()) {
+        while temp[pivot] == T::zero() || self.tolerance.negligible(temp[pivot].magnitude(), self.scale) {
             pivot.1 += 1;
             if pivot.1 >= temp.size.0 {
                 if pivot.0 == 0 {
@@ -88,10 +118,10 @@ LuFactorization<T> {
                 pivot.1 -= 1;
             }
             let factor = T::one() / temp[pivot];
-            multiply_row(&mut temp, pivot.0, factor, pivot.1);
+            multiply_row(&mut temp.view_mut(), pivot.0, factor, pivot.1);
             for row in 0..pivot.0 {
                 let factor = -temp[(row, pivot.1)];
-                multiply_add_row(&mut temp, pivot.0, row, factor, pivot.1);
+                multiply_add_row(&mut temp.view_mut(), pivot.0, row, factor, pivot.1);
             }
             if pivot.0 == 0 {
                 break;
@@ -102,59 +132,941 @@ LuFactorization<T> {
     }
 
     pub fn solve(&self, b:Array<T>) -> LuResult<T> {
+        self.solve_view(b.view())
+    }
+
+    // Solves for a right hand side that may be a sub-block of a larger array.
+    pub fn solve_view(&self, b:ArrayView<T>) -> LuResult<T> {
+        match self.try_solve_view(b) {
+            Ok(res) => res,
+            Err(e) => panic!("The height of the A matrix and the b vector must be equal. {}", e),
+        }
+    }
+
+    pub fn try_solve(&self, b:Array<T>) -> Result<LuResult<T>, AlgaeError> {
+        self.try_solve_view(b.view())
+    }
+
+    pub fn try_solve_view(&self, b:ArrayView<T>) -> Result<LuResult<T>, AlgaeError> {
         if b.size.1 != self.u.size.1 {
-            panic!("The height of the A matrix and the b vector must be equal.");
+            return Err(AlgaeError::ShapeMismatch {
+                expected:(b.size.0, self.u.size.1),
+                found:b.size,
+            });
         }
-        let y = {
-            let mut temp = Array::concat_0_axis(self.l.clone(), b);
-            temp.echelon_form();
-            Array::split_0_axis(temp, self.l.size.0).1
-        };
-        let res = self.solve_l(y);
-        match Array::extract_solution_from_matrix(res, self.u.clone()) {
-            LinearSystemResult::Single(res) => LuResult::Single(res),
-            LinearSystemResult::Infinite(res) => LuResult::Infinite(res),
+        let mut temp = Self::augment(self.l.view(), b);
+        // L has unit pivots on its diagonal already, row swaps would only scramble the forward substitution.
+        temp.echelon_form_with(PivotingOptions::new(PivotingStrategy::None, Some(self.tolerance)));
+        let res = self.solve_l(temp.slice(.., self.l.size.0..));
+        match Array::extract_solution_from_matrix(res, self.u.clone(), self.tolerance) {
+            LinearSystemResult::Single(res) => Ok(LuResult::Single(res)),
+            LinearSystemResult::Infinite(res) => Ok(LuResult::Infinite(res)),
             LinearSystemResult::Inconsistent => panic!("Faulty implementation: Inconsistent system of equations."),
         }
     }
 }
 
+// Gaussian elimination with partial pivoting: P * A = L * U, where row i of P * A is row
+// permutation[i] of A. The factorization always exists, singular matrices only fail when solving.
+pub struct PluFactorization<T> {
+    l:Array<T>,
+    u:Array<T>,
+    permutation:Vec<usize>,
+    sign:T,
+    pub size:(usize, usize),
+    // Largest magnitude of A, pivots are compared to it when deciding singularity.
+    scale:f64,
+    // The one it was factorized with, is_singular and the solves use it.
+    tolerance:Tolerance,
+}
+
+impl<T: PartialEq> PartialEq for PluFactorization<T> {
+    fn eq(&self, other:&Self) -> bool {
+        self.size == other.size && self.permutation == other.permutation && self.l == other.l && self.u == other.u
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for PluFactorization<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}\n{}\n{}", self.permutation, self.l, self.u)
+    }
+}
+
+fn abs<T: Copy + Zero + PartialOrd + Neg<Output = T>>(a:T) -> T {
+    if a < T::zero() {
+        -a
+    } else {
+        a
+    }
+}
+
+fn magnitude<T: Copy + Zero + PartialOrd + Neg<Output = T> + ToPrimitive>(a:T) -> f64 {
+    abs(a).to_f64().unwrap_or(f64::INFINITY)
+}
+
+impl<T: Copy + Clone + Zero + One + PartialEq + PartialOrd
++ Add<Output = T> + Sub<Output = T> + Neg<Output = T> + Mul<Output = T> + Div<Output = T> + FloatEq + ToPrimitive>
+PluFactorization<T> {
+    pub fn new(a:Array<T>) -> Result<Self, AlgaeError> {
+        Self::new_with(a, None)
+    }
+
+    // The tolerance decides which pivots of U make the matrix singular, compared to the largest
+    // magnitude of A. None is relative, the standard tolerance of T scaled by the dimension.
+    pub fn new_with(mut a:Array<T>, tolerance:Option<Tolerance>) -> Result<Self, AlgaeError> {
+        if a.size.0 != a.size.1 {
+            return Err(AlgaeError::NotSquare {
+                size:a.size,
+            });
+        }
+        let n = a.size.0;
+        let tolerance = tolerance.unwrap_or(Self::default_tolerance(n));
+        let scale = a.iter().fold(0.0, |acc, x| f64::max(acc, magnitude(*x)));
+        let mut permutation = (0..n).collect::<Vec<usize>>();
+        let mut sign = T::one();
+        for k in 0..n {
+            let mut pivot_row = k;
+            for row in (k + 1)..n {
+                if abs(a[(row, k)]) > abs(a[(pivot_row, k)]) {
+                    pivot_row = row;
+                }
+            }
+            if pivot_row != k {
+                a.swap_rows(k, pivot_row);
+                permutation.swap(k, pivot_row);
+                sign = -sign;
+            }
+            // The whole column is zero, there's nothing left to eliminate. Small pivots are still
+            // eliminated, whether they make the matrix singular is decided by is_singular.
+            if a[(k, k)] == T::zero() {
+                continue;
+            }
+            for row in (k + 1)..n {
+                let factor = a[(row, k)] / a[(k, k)];
+                a[(row, k)] = factor;
+                for col in (k + 1)..n {
+                    a[(row, col)] = a[(row, col)] - factor * a[(k, col)];
+                }
+            }
+        }
+
+        let mut l = Array::identity(n);
+        for row in 1..n {
+            for col in 0..row {
+                l[(row, col)] = a[(row, col)];
+                a[(row, col)] = T::zero();
+            }
+        }
+        Ok(
+            PluFactorization {
+                l,
+                u:a,
+                permutation,
+                sign,
+                size:(n, n),
+                scale,
+                tolerance,
+            }
+        )
+    }
+
+    fn default_tolerance(n:usize) -> Tolerance {
+        Tolerance::relative(T::standard_tolerance().relative * n.max(1) as f64)
+    }
+
+    pub fn l(&self) -> &Array<T> {
+        &self.l
+    }
+
+    pub fn u(&self) -> &Array<T> {
+        &self.u
+    }
+
+    pub fn permutation(&self) -> &[usize] {
+        &self.permutation
+    }
+
+    pub fn p(&self) -> Array<T> {
+        let mut p = Array::new_filled(self.size, T::zero());
+        for (row, col) in self.permutation.iter().enumerate() {
+            p[(row, *col)] = T::one();
+        }
+        p
+    }
+
+    pub fn is_singular(&self) -> bool {
+        self.is_singular_with(None)
+    }
+
+    // A pivot is zero if it's negligible next to the largest magnitude of A,
+    // None uses the tolerance of the factorization.
+    pub fn is_singular_with(&self, tolerance:Option<Tolerance>) -> bool {
+        let tolerance = tolerance.unwrap_or(self.tolerance);
+        (0..self.size.0).any(|i| {
+            let pivot = self.u[(i, i)];
+            pivot == T::zero() || tolerance.negligible(magnitude(pivot), self.scale)
+        })
+    }
+
+    pub fn determinant(&self) -> T {
+        let mut det = self.sign;
+        for i in 0..self.size.0 {
+            det = det * self.u[(i, i)];
+        }
+        det
+    }
+
+    // Every column of b is treated as its own right hand side.
+    pub fn solve(&self, b:Array<T>) -> Array<T> {
+        self.solve_view(b.view())
+    }
+
+    pub fn solve_view(&self, b:ArrayView<T>) -> Array<T> {
+        match self.try_solve_view(b) {
+            Ok(res) => res,
+            Err(e) => panic!("Couldn't solve the system of equations. {}", e),
+        }
+    }
+
+    pub fn try_solve(&self, b:Array<T>) -> Result<Array<T>, AlgaeError> {
+        self.try_solve_view(b.view())
+    }
+
+    pub fn try_solve_view(&self, b:ArrayView<T>) -> Result<Array<T>, AlgaeError> {
+        if b.size.1 != self.size.1 {
+            return Err(AlgaeError::ShapeMismatch {
+                expected:(b.size.0, self.size.1),
+                found:b.size,
+            });
+        }
+        if self.is_singular() {
+            return Err(AlgaeError::Singular);
+        }
+        let n = self.size.1;
+        let mut x = Array::new_filled(b.size, T::zero());
+        for row in 0..n {
+            for col in 0..b.size.0 {
+                x[(row, col)] = b[(self.permutation[row], col)];
+            }
+        }
+        for col in 0..b.size.0 {
+            for row in 0..n {
+                let mut sum = x[(row, col)];
+                for k in 0..row {
+                    sum = sum - self.l[(row, k)] * x[(k, col)];
+                }
+                x[(row, col)] = sum;
+            }
+            for row in (0..n).rev() {
+                let mut sum = x[(row, col)];
+                for k in (row + 1)..n {
+                    sum = sum - self.u[(row, k)] * x[(k, col)];
+                }
+                x[(row, col)] = sum / self.u[(row, row)];
+            }
+        }
+        Ok(x)
+    }
+
+    pub fn solve_many(&self, bs:&[Array<T>]) -> Vec<Array<T>> {
+        match self.try_solve_many(bs) {
+            Ok(res) => res,
+            Err(e) => panic!("Couldn't solve the systems of equations. {}", e),
+        }
+    }
+
+    pub fn try_solve_many(&self, bs:&[Array<T>]) -> Result<Vec<Array<T>>, AlgaeError> {
+        bs.iter().map(|b| self.try_solve_view(b.view())).collect()
+    }
+
+    pub fn inverse(&self) -> Result<Array<T>, AlgaeError> {
+        self.try_solve(Array::identity(self.size.0))
+    }
+}
+
+impl<T: Float + FloatEq> PluFactorization<T> {
+    // Returns (sign, ln|det|), which doesn't overflow for large matrices.
+    // A zero pivot gives (0, -inf).
+    pub fn log_det(&self) -> (T, T) {
+        let mut sign = self.sign;
+        let mut log = T::zero();
+        for i in 0..self.size.0 {
+            let pivot = self.u[(i, i)];
+            if pivot == T::zero() {
+                return (T::zero(), T::neg_infinity());
+            }
+            sign = sign * pivot.signum();
+            log = log + pivot.abs().ln();
+        }
+        (sign, log)
+    }
+}
+
+// Householder QR: A * P = Q * R, where column i of A * P is column permutation[i] of A.
+// Q is never formed explicitly, the reflectors I - 2 * v * v^T are kept instead.
+pub struct QrFactorization<T> {
+    r:Array<T>,
+    reflectors:Vec<Array<T>>,
+    permutation:Vec<usize>,
+    pivoted:bool,
+    pub size:(usize, usize),
+}
+
+impl<T: fmt::Display> fmt::Display for QrFactorization<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}\n{}", self.permutation, self.r)
+    }
+}
+
+impl<T: Float> QrFactorization<T> {
+    pub fn new(a:Array<T>) -> Self {
+        Self::factorize(a, false)
+    }
+
+    // Column pivoting moves the column with the largest remaining norm to the front in every step,
+    // so the diagonal of R is non-increasing in magnitude and reveals the rank.
+    pub fn new_pivoted(a:Array<T>) -> Self {
+        Self::factorize(a, true)
+    }
+
+    fn factorize(mut r:Array<T>, pivoted:bool) -> Self {
+        let (n, m) = r.size;
+        let mut permutation = (0..n).collect::<Vec<usize>>();
+        let mut reflectors = Vec::<Array<T>>::with_capacity(usize::min(m, n));
+        for k in 0..usize::min(m, n) {
+            if pivoted {
+                let norm = |col:usize| (k..m).fold(T::zero(), |acc, row| acc + r[(row, col)] * r[(row, col)]);
+                let mut pivot_col = k;
+                for col in (k + 1)..n {
+                    if norm(col) > norm(pivot_col) {
+                        pivot_col = col;
+                    }
+                }
+                if pivot_col != k {
+                    for row in 0..m {
+                        let temp = r[(row, k)];
+                        r[(row, k)] = r[(row, pivot_col)];
+                        r[(row, pivot_col)] = temp;
+                    }
+                    permutation.swap(k, pivot_col);
+                }
+            }
+            let mut v = r.slice(k.., k..(k + 1)).to_array();
+            let x_norm = v.norm();
+            let alpha = if v[(0, 0)] < T::zero() { x_norm } else { -x_norm };
+            v[(0, 0)] = v[(0, 0)] - alpha;
+            let v_norm = v.norm();
+            if v_norm > T::zero() {
+                v /= v_norm;
+                reflect(&v, &mut r, k, k);
+            }
+            reflectors.push(v);
+        }
+        QrFactorization {
+            r,
+            reflectors,
+            permutation,
+            pivoted,
+            size:(n, m),
+        }
+    }
+
+    // The full m x n R.
+    pub fn r(&self) -> Array<T> {
+        self.r.clone()
+    }
+
+    // The k x n R with k = min(m, n).
+    pub fn thin_r(&self) -> Array<T> {
+        let k = usize::min(self.size.0, self.size.1);
+        self.r.slice(..k, ..).to_array()
+    }
+
+    // The full m x m Q.
+    pub fn q(&self) -> Array<T> {
+        let mut q = Array::identity(self.size.1);
+        for (k, v) in self.reflectors.iter().enumerate().rev() {
+            reflect(v, &mut q, k, 0);
+        }
+        q
+    }
+
+    // The m x k Q with orthonormal columns, k = min(m, n).
+    pub fn thin_q(&self) -> Array<T> {
+        let k = usize::min(self.size.0, self.size.1);
+        self.q().slice(.., ..k).to_array()
+    }
+
+    pub fn permutation(&self) -> &[usize] {
+        &self.permutation
+    }
+
+    pub fn p(&self) -> Array<T> {
+        let mut p = Array::new_filled((self.size.0, self.size.0), T::zero());
+        for (col, row) in self.permutation.iter().enumerate() {
+            p[(*row, col)] = T::one();
+        }
+        p
+    }
+
+    // The number of diagonal entries of R above max(m, n) * epsilon * |r_00|.
+    // Only meaningful for pivoted factorizations.
+    pub fn rank(&self) -> usize {
+        let k = usize::min(self.size.0, self.size.1);
+        if k == 0 {
+            return 0;
+        }
+        let max_dim = match T::from(usize::max(self.size.0, self.size.1)) {
+            Some(max_dim) => max_dim,
+            None => T::one(),
+        };
+        let tolerance = max_dim * T::epsilon() * self.r[(0, 0)].abs();
+        (0..k).take_while(|i| self.r[(*i, *i)].abs() > tolerance).count()
+    }
+
+    // Computes Q^T * b without forming Q.
+    pub fn apply_qt(&self, b:&Array<T>) -> Array<T> {
+        let mut b = b.clone();
+        for (k, v) in self.reflectors.iter().enumerate() {
+            reflect(v, &mut b, k, 0);
+        }
+        b
+    }
+
+    // Minimizes ||A * x - b|| and returns x together with the residual norm
+    // (the Frobenius norm if b has several columns). For rank deficient matrices the basic
+    // solution with zeros in the trailing pivoted components is returned, which requires
+    // a pivoted factorization.
+    pub fn least_squares(&self, b:&Array<T>) -> Result<(Array<T>, T), AlgaeError> {
+        let (n, m) = self.size;
+        if b.size.1 != m {
+            return Err(AlgaeError::ShapeMismatch {
+                expected:(b.size.0, m),
+                found:b.size,
+            });
+        }
+        let rank = self.rank();
+        if !self.pivoted && rank < usize::min(m, n) {
+            return Err(AlgaeError::Singular);
+        }
+        let qt_b = self.apply_qt(b);
+        let residual = qt_b.slice(rank.., ..).to_array().norm();
+        let mut x = Array::new_filled((b.size.0, n), T::zero());
+        for col in 0..b.size.0 {
+            for row in (0..rank).rev() {
+                let mut sum = qt_b[(row, col)];
+                for k in (row + 1)..rank {
+                    sum = sum - self.r[(row, k)] * x[(self.permutation[k], col)];
+                }
+                x[(self.permutation[row], col)] = sum / self.r[(row, row)];
+            }
+        }
+        Ok((x, residual))
+    }
+}
+
+// Applies I - 2 * v * v^T to the rows from `first_row` on and the columns from `first_col` on.
+fn reflect<T: Float>(v:&Array<T>, a:&mut Array<T>, first_row:usize, first_col:usize) {
+    let two = T::one() + T::one();
+    for col in first_col..a.size.0 {
+        let mut dot = T::zero();
+        for i in 0..v.size.1 {
+            dot = dot + v[(i, 0)] * a[(first_row + i, col)];
+        }
+        let factor = two * dot;
+        for i in 0..v.size.1 {
+            a[(first_row + i, col)] = a[(first_row + i, col)] - factor * v[(i, 0)];
+        }
+    }
+}
+
+impl<T: Float> Array<T> {
+    // Least squares solution of a * x = b, also for tall and rank deficient systems.
+    pub fn least_squares(a:Array<T>, b:&Array<T>) -> Result<(Array<T>, T), AlgaeError> {
+        QrFactorization::new_pivoted(a).least_squares(b)
+    }
+}
+
+// A = L * L^T for symmetric positive definite matrices. Only the lower triangle of A is read.
+pub struct CholeskyFactorization<T> {
+    l:Array<T>,
+    pub size:(usize, usize),
+}
+
+impl<T: fmt::Display> fmt::Display for CholeskyFactorization<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.l)
+    }
+}
+
+impl<T: Float> CholeskyFactorization<T> {
+    pub fn new(a:Array<T>) -> Result<Self, AlgaeError> {
+        if a.size.0 != a.size.1 {
+            return Err(AlgaeError::NotSquare {
+                size:a.size,
+            });
+        }
+        let n = a.size.0;
+        let mut l = Array::new_filled((n, n), T::zero());
+        for col in 0..n {
+            let mut diagonal = a[(col, col)];
+            for k in 0..col {
+                diagonal = diagonal - l[(col, k)] * l[(col, k)];
+            }
+            if diagonal <= T::zero() || diagonal.is_nan() {
+                return Err(AlgaeError::NotPositiveDefinite {
+                    index:col,
+                });
+            }
+            let diagonal = diagonal.sqrt();
+            l[(col, col)] = diagonal;
+            for row in (col + 1)..n {
+                let mut sum = a[(row, col)];
+                for k in 0..col {
+                    sum = sum - l[(row, k)] * l[(col, k)];
+                }
+                l[(row, col)] = sum / diagonal;
+            }
+        }
+        Ok(CholeskyFactorization {
+            l,
+            size:(n, n),
+        })
+    }
+
+    pub fn l(&self) -> &Array<T> {
+        &self.l
+    }
+
+    pub fn solve(&self, b:Array<T>) -> Array<T> {
+        match self.try_solve(b) {
+            Ok(res) => res,
+            Err(e) => panic!("The height of the A matrix and the b vector must be equal. {}", e),
+        }
+    }
+
+    // Every column of b is treated as its own right hand side.
+    pub fn try_solve(&self, mut b:Array<T>) -> Result<Array<T>, AlgaeError> {
+        let n = self.size.1;
+        if b.size.1 != n {
+            return Err(AlgaeError::ShapeMismatch {
+                expected:(b.size.0, n),
+                found:b.size,
+            });
+        }
+        for col in 0..b.size.0 {
+            for row in 0..n {
+                let mut sum = b[(row, col)];
+                for k in 0..row {
+                    sum = sum - self.l[(row, k)] * b[(k, col)];
+                }
+                b[(row, col)] = sum / self.l[(row, row)];
+            }
+            for row in (0..n).rev() {
+                let mut sum = b[(row, col)];
+                for k in (row + 1)..n {
+                    sum = sum - self.l[(k, row)] * b[(k, col)];
+                }
+                b[(row, col)] = sum / self.l[(row, row)];
+            }
+        }
+        Ok(b)
+    }
+
+    pub fn determinant(&self) -> T {
+        let mut det = T::one();
+        for i in 0..self.size.0 {
+            det = det * self.l[(i, i)] * self.l[(i, i)];
+        }
+        det
+    }
+
+    // The determinant of a positive definite matrix is positive, so no sign is returned.
+    pub fn log_det(&self) -> T {
+        let two = T::one() + T::one();
+        (0..self.size.0).fold(T::zero(), |acc, i| acc + two * self.l[(i, i)].ln())
+    }
+
+    pub fn inverse(&self) -> Array<T> {
+        self.solve(Array::identity(self.size.0))
+    }
+
+    // Turns the factorization of A into the one of A + x * x^T in O(n^2).
+    pub fn update(&mut self, x:&Array<T>) {
+        if let Err(e) = self.try_update(x) {
+            panic!("The update has to be a vector as high as the matrix. {}", e);
+        }
+    }
+
+    pub fn try_update(&mut self, x:&Array<T>) -> Result<(), AlgaeError> {
+        if x.size != (1, self.size.1) {
+            return Err(AlgaeError::ShapeMismatch {
+                expected:(1, self.size.1),
+                found:x.size,
+            });
+        }
+        let mut x = x.clone();
+        for k in 0..self.size.0 {
+            let l_kk = self.l[(k, k)];
+            let r = l_kk.hypot(x[(k, 0)]);
+            let (c, s) = (r / l_kk, x[(k, 0)] / l_kk);
+            self.l[(k, k)] = r;
+            for i in (k + 1)..self.size.0 {
+                self.l[(i, k)] = (self.l[(i, k)] + s * x[(i, 0)]) / c;
+                x[(i, 0)] = c * x[(i, 0)] - s * self.l[(i, k)];
+            }
+        }
+        Ok(())
+    }
+
+    // Turns the factorization of A into the one of A - x * x^T. Fails without changing the
+    // factorization if the result isn't positive definite.
+    pub fn downdate(&mut self, x:&Array<T>) -> Result<(), AlgaeError> {
+        if x.size != (1, self.size.1) {
+            return Err(AlgaeError::ShapeMismatch {
+                expected:(1, self.size.1),
+                found:x.size,
+            });
+        }
+        let mut x = x.clone();
+        let mut l = self.l.clone();
+        for k in 0..self.size.0 {
+            let l_kk = l[(k, k)];
+            let r_squared = (l_kk - x[(k, 0)]) * (l_kk + x[(k, 0)]);
+            if r_squared <= T::zero() || r_squared.is_nan() {
+                return Err(AlgaeError::NotPositiveDefinite {
+                    index:k,
+                });
+            }
+            let r = r_squared.sqrt();
+            let (c, s) = (r / l_kk, x[(k, 0)] / l_kk);
+            l[(k, k)] = r;
+            for i in (k + 1)..self.size.0 {
+                l[(i, k)] = (l[(i, k)] - s * x[(i, 0)]) / c;
+                x[(i, 0)] = c * x[(i, 0)] - s * l[(i, k)];
+            }
+        }
+        self.l = l;
+        Ok(())
+    }
+}
+
+// P * A * P^T = L * D * L^T for symmetric indefinite matrices, with unit lower triangular L and
+// D made of 1x1 and 2x2 blocks chosen by Bunch-Kaufman pivoting. Row i of P * A * P^T is row
+// permutation[i] of A. Both factors share the packed lower triangle of A: the diagonal and the
+// subdiagonal entry of every 2x2 block hold D, the other entries below the diagonal hold L.
+pub struct LdltFactorization<T> {
+    factors:Vec<T>,
+    // Whether a 2x2 block of D starts at the index.
+    pairs:Vec<bool>,
+    permutation:Vec<usize>,
+    pub size:(usize, usize),
+}
+
+impl<T: Float + fmt::Display> fmt::Display for LdltFactorization<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}\n{}\n{}", self.permutation, self.l(), self.d())
+    }
+}
+
+// Position of (row, col) with row >= col in a packed lower triangle.
+fn packed_at(row:usize, col:usize) -> usize {
+    row * (row + 1) / 2 + col
+}
+
+// Exchanges row and column i with row and column j of a symmetric matrix in packed lower storage.
+fn swap_packed<T>(packed:&mut [T], n:usize, i:usize, j:usize) {
+    let (p, q) = (usize::min(i, j), usize::max(i, j));
+    if p == q {
+        return;
+    }
+    for k in 0..p {
+        packed.swap(packed_at(p, k), packed_at(q, k));
+    }
+    packed.swap(packed_at(p, p), packed_at(q, q));
+    for k in (p + 1)..q {
+        packed.swap(packed_at(k, p), packed_at(q, k));
+    }
+    for k in (q + 1)..n {
+        packed.swap(packed_at(k, p), packed_at(k, q));
+    }
+}
+
+impl<T: Float> LdltFactorization<T> {
+    // Only the lower triangle of a is read.
+    pub fn new(a:Array<T>) -> Result<Self, AlgaeError> {
+        if a.size.0 != a.size.1 {
+            return Err(AlgaeError::NotSquare {
+                size:a.size,
+            });
+        }
+        let n = a.size.0;
+        let packed = (0..n).flat_map(|row| (0..=row).map(move |col| (row, col))).map(|index| a[index]).collect();
+        Ok(LdltFactorization::from_packed(packed, n))
+    }
+
+    // Factors the symmetric n x n matrix whose lower triangle is packed row after row, in place.
+    pub(in crate::array) fn from_packed(mut a:Vec<T>, n:usize) -> Self {
+        let alpha = match T::from((1.0 + f64::sqrt(17.0)) / 8.0) {
+            Some(alpha) => alpha,
+            None => panic!("The Bunch-Kaufman constant isn't representable by the element type"),
+        };
+        let mut pairs = vec![false; n];
+        let mut permutation = (0..n).collect::<Vec<usize>>();
+        let mut k = 0;
+        while k < n {
+            let abs_akk = a[packed_at(k, k)].abs();
+            let (mut col_max, mut i_max) = (T::zero(), k);
+            for i in (k + 1)..n {
+                if a[packed_at(i, k)].abs() > col_max {
+                    col_max = a[packed_at(i, k)].abs();
+                    i_max = i;
+                }
+            }
+            let (pivot, step) = if abs_akk.max(col_max) == T::zero() || abs_akk >= alpha * col_max {
+                (k, 1)
+            } else {
+                let mut row_max = T::zero();
+                for j in k..n {
+                    let value = a[packed_at(usize::max(i_max, j), usize::min(i_max, j))].abs();
+                    if j != i_max && value > row_max {
+                        row_max = value;
+                    }
+                }
+                if abs_akk * row_max >= alpha * col_max * col_max {
+                    (k, 1)
+                } else if a[packed_at(i_max, i_max)].abs() >= alpha * row_max {
+                    (i_max, 1)
+                } else {
+                    (i_max, 2)
+                }
+            };
+            let kk = k + step - 1;
+            if pivot != kk {
+                // Also exchanges the rows of the columns of L computed so far.
+                swap_packed(&mut a, n, kk, pivot);
+                permutation.swap(kk, pivot);
+            }
+            if step == 1 {
+                let d_kk = a[packed_at(k, k)];
+                if d_kk != T::zero() {
+                    let l = ((k + 1)..n).map(|i| a[packed_at(i, k)] / d_kk).collect::<Vec<T>>();
+                    for i in (k + 1)..n {
+                        for j in (k + 1)..=i {
+                            a[packed_at(i, j)] = a[packed_at(i, j)] - l[i - k - 1] * a[packed_at(j, k)];
+                        }
+                    }
+                    for i in (k + 1)..n {
+                        a[packed_at(i, k)] = l[i - k - 1];
+                    }
+                }
+            } else {
+                let (d11, d21, d22) = (a[packed_at(k, k)], a[packed_at(k + 1, k)], a[packed_at(k + 1, k + 1)]);
+                let det = d11 * d22 - d21 * d21;
+                let l = ((k + 2)..n).map(|i| {
+                    let (a1, a2) = (a[packed_at(i, k)], a[packed_at(i, k + 1)]);
+                    ((a1 * d22 - a2 * d21) / det, (a2 * d11 - a1 * d21) / det)
+                }).collect::<Vec<(T, T)>>();
+                for i in (k + 2)..n {
+                    for j in (k + 2)..=i {
+                        let (l1, l2) = l[i - k - 2];
+                        a[packed_at(i, j)] = a[packed_at(i, j)] - l1 * a[packed_at(j, k)] - l2 * a[packed_at(j, k + 1)];
+                    }
+                }
+                for i in (k + 2)..n {
+                    a[packed_at(i, k)] = l[i - k - 2].0;
+                    a[packed_at(i, k + 1)] = l[i - k - 2].1;
+                }
+                pairs[k] = true;
+            }
+            k += step;
+        }
+        LdltFactorization {
+            factors:a,
+            pairs,
+            permutation,
+            size:(n, n),
+        }
+    }
+
+    // Entry (row, col) of L below the diagonal, the subdiagonal of a 2x2 block belongs to D.
+    fn l_at(&self, row:usize, col:usize) -> T {
+        if self.pairs[col] && row == col + 1 { T::zero() } else { self.factors[packed_at(row, col)] }
+    }
+
+    // The blocks of D as (start, d11, d21, d22), with d21 and d22 zero for a 1x1 block.
+    fn blocks(&self) -> Vec<(usize, T, T, T)> {
+        let mut blocks = Vec::new();
+        let mut k = 0;
+        while k < self.size.0 {
+            if self.pairs[k] {
+                blocks.push((k, self.factors[packed_at(k, k)], self.factors[packed_at(k + 1, k)], self.factors[packed_at(k + 1, k + 1)]));
+                k += 2;
+            } else {
+                blocks.push((k, self.factors[packed_at(k, k)], T::zero(), T::zero()));
+                k += 1;
+            }
+        }
+        blocks
+    }
+
+    pub fn l(&self) -> Array<T> {
+        let mut l = Array::identity(self.size.0);
+        for row in 0..self.size.0 {
+            for col in 0..row {
+                l[(row, col)] = self.l_at(row, col);
+            }
+        }
+        l
+    }
+
+    pub fn d(&self) -> Array<T> {
+        let mut d = Array::new_filled(self.size, T::zero());
+        for (k, d11, d21, d22) in self.blocks() {
+            d[(k, k)] = d11;
+            if self.pairs[k] {
+                d[(k + 1, k)] = d21;
+                d[(k, k + 1)] = d21;
+                d[(k + 1, k + 1)] = d22;
+            }
+        }
+        d
+    }
+
+    pub fn permutation(&self) -> &[usize] {
+        &self.permutation
+    }
+
+    pub fn p(&self) -> Array<T> {
+        let mut p = Array::new_filled(self.size, T::zero());
+        for (row, col) in self.permutation.iter().enumerate() {
+            p[(row, *col)] = T::one();
+        }
+        p
+    }
+
+    pub fn determinant(&self) -> T {
+        let mut det = T::one();
+        for (k, d11, d21, d22) in self.blocks() {
+            det = det * if self.pairs[k] { d11 * d22 - d21 * d21 } else { d11 };
+        }
+        det
+    }
+
+    // The number of positive, negative and zero eigenvalues, which D shares with A.
+    // Bunch-Kaufman only chooses 2x2 blocks with a negative determinant.
+    pub fn inertia(&self) -> (usize, usize, usize) {
+        let (mut positive, mut negative, mut zero) = (0, 0, 0);
+        for (k, d11, _, _) in self.blocks() {
+            if self.pairs[k] {
+                positive += 1;
+                negative += 1;
+            } else if d11 > T::zero() {
+                positive += 1;
+            } else if d11 < T::zero() {
+                negative += 1;
+            } else {
+                zero += 1;
+            }
+        }
+        (positive, negative, zero)
+    }
+
+    pub fn solve(&self, b:Array<T>) -> Array<T> {
+        match self.try_solve(b) {
+            Ok(res) => res,
+            Err(e) => panic!("Couldn't solve the system of equations. {}", e),
+        }
+    }
+
+    pub fn try_solve(&self, b:Array<T>) -> Result<Array<T>, AlgaeError> {
+        let n = self.size.1;
+        if b.size.1 != n {
+            return Err(AlgaeError::ShapeMismatch {
+                expected:(b.size.0, n),
+                found:b.size,
+            });
+        }
+        let blocks = self.blocks();
+        let mut y = Array::new_filled(b.size, T::zero());
+        for row in 0..n {
+            for col in 0..b.size.0 {
+                y[(row, col)] = b[(self.permutation[row], col)];
+            }
+        }
+        for col in 0..b.size.0 {
+            for row in 0..n {
+                let mut sum = y[(row, col)];
+                for k in 0..row {
+                    sum = sum - self.l_at(row, k) * y[(k, col)];
+                }
+                y[(row, col)] = sum;
+            }
+            for &(k, d11, d21, d22) in blocks.iter() {
+                if self.pairs[k] {
+                    let det = d11 * d22 - d21 * d21;
+                    let (y1, y2) = (y[(k, col)], y[(k + 1, col)]);
+                    y[(k, col)] = (y1 * d22 - y2 * d21) / det;
+                    y[(k + 1, col)] = (y2 * d11 - y1 * d21) / det;
+                } else if d11 == T::zero() {
+                    return Err(AlgaeError::Singular);
+                } else {
+                    y[(k, col)] = y[(k, col)] / d11;
+                }
+            }
+            for row in (0..n).rev() {
+                let mut sum = y[(row, col)];
+                for k in (row + 1)..n {
+                    sum = sum - self.l_at(k, row) * y[(k, col)];
+                }
+                y[(row, col)] = sum;
+            }
+        }
+        let mut x = Array::new_filled(b.size, T::zero());
+        for row in 0..n {
+            for col in 0..b.size.0 {
+                x[(self.permutation[row], col)] = y[(row, col)];
+            }
+        }
+        Ok(x)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::array::factorizations::Array;
     use crate::array::float_eq::FloatEq;
     use crate::array::factorizations::LuFactorization;
     use crate::array::factorizations::LuResult;
+    use crate::array::factorizations::PluFactorization;
+    use crate::array::factorizations::QrFactorization;
+    use crate::array::factorizations::CholeskyFactorization;
+    use crate::array::factorizations::LdltFactorization;
+    use crate::array::checks::assert_close;
+    use crate::error::error::AlgaeError;
 
     #[test]
     fn lu_factorization_possible() {
         let expected = LuFactorization{
-            l:Array {
-                content:vec![
-                    vec![1.0, 0.0, 0.0],
-                    vec![3.0, 1.0, 0.0],
-                    vec![0.5, 0.16666666666666666, 1.0],
-                ],
-                size:(3, 3),
-            },
-            u:Array {
-                content:vec![
-                    vec![1.0, 3.0, 3.0],
-                    vec![0.0, -3.0, 0.0],
-                    vec![0.0, 0.0, 0.5],
-                ],
-                size:(3, 3),
-            },
-            size:(3, 3),
-        };
-        let actual = LuFactorization::new(Array {
-            content:vec![
+            l:Array::new_mat(vec![
+                vec![1.0, 0.0, 0.0],
+                vec![3.0, 1.0, 0.0],
+                vec![0.5, 0.16666666666666666, 1.0],
+            ]),
+            u:Array::new_mat(vec![
                 vec![1.0, 3.0, 3.0],
-                vec![3.0, 6.0, 9.0],
-                vec![0.5, 1.0, 2.0],
-            ],
+                vec![0.0, -3.0, 0.0],
+                vec![0.0, 0.0, 0.5],
+            ]),
             size:(3, 3),
-        });
+            tolerance:f64::standard_tolerance(),
+            scale:9.0,
+        };
+        let actual = LuFactorization::new(Array::new_mat(vec![
+            vec![1.0, 3.0, 3.0],
+            vec![3.0, 6.0, 9.0],
+            vec![0.5, 1.0, 2.0],
+        ]));
         match actual {
             Ok(arr) => assert!(expected.float_eq(&arr)),
             Err(e) => panic!("Error: {}", e),
@@ -163,51 +1075,38 @@ mod tests {
 
     #[test]
     fn lu_factorization_impossible() {
-        let actual = LuFactorization::new(Array {
-            content:vec![
-                vec![1.0, 3.0, 3.0],
-                vec![3.0, 9.0, 9.0],
-                vec![0.5, 1.0, 2.0],
-            ],
-            size:(3, 3),
-        });
+        let actual = LuFactorization::new(Array::new_mat(vec![
+            vec![1.0, 3.0, 3.0],
+            vec![3.0, 9.0, 9.0],
+            vec![0.5, 1.0, 2.0],
+        ]));
         match actual {
             Ok(arr) => panic!("Wrong result: {}", arr),
-            Err(_) => {},
+            Err(e) => assert_eq!(AlgaeError::ZeroPivot { index:1 }, e),
         }
     }
 
     #[test]
     fn solve() {
-        let expected = Array {
-            content:vec![vec![-16.0], vec![0.3333333333333333], vec![5.333333333333333]],
-            size:(1, 3),
-        };
+        let expected = Array::new_mat(vec![vec![-16.0], vec![0.3333333333333333], vec![5.333333333333333]]);
         let actual = {
             let temp = LuFactorization{
-                l:Array {
-                    content:vec![
-                        vec![1.0, 0.0, 0.0],
-                        vec![3.0, 1.0, 0.0],
-                        vec![0.5, 0.16666666666666666, 1.0],
-                    ],
-                    size:(3, 3),
-                },
-                u:Array {
-                    content:vec![
-                        vec![1.0, 3.0, 3.0],
-                        vec![0.0, -3.0, 0.0],
-                        vec![0.0, 0.0, 0.5],
-                    ],
-                    size:(3, 3),
-                },
+                l:Array::new_mat(vec![
+                    vec![1.0, 0.0, 0.0],
+                    vec![3.0, 1.0, 0.0],
+                    vec![0.5, 0.16666666666666666, 1.0],
+                ]),
+                u:Array::new_mat(vec![
+                    vec![1.0, 3.0, 3.0],
+                    vec![0.0, -3.0, 0.0],
+                    vec![0.0, 0.0, 0.5],
+                ]),
                 size:(3, 3),
+                tolerance:f64::standard_tolerance(),
+                scale:9.0,
             };
             temp.solve(
-                Array {
-                    content:vec![vec![1.0], vec![2.0], vec![3.0]],
-                    size:(1, 3),
-                }
+                Array::new_mat(vec![vec![1.0], vec![2.0], vec![3.0]])
             )
         };
         match actual {
@@ -218,4 +1117,210 @@ mod tests {
             LuResult::Infinite(s) => panic!("Wrong result: {}; {}", s.0, s.1),
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn solve_view() {
+        let expected = Array::new_mat(vec![vec![-16.0], vec![0.3333333333333333], vec![5.333333333333333]]);
+        let lu = LuFactorization::new(Array::new_mat(vec![
+            vec![1.0, 3.0, 3.0],
+            vec![3.0, 6.0, 9.0],
+            vec![0.5, 1.0, 2.0],
+        ])).unwrap();
+        let rhs = Array::new_mat(vec![
+            vec![0.0, 1.0, 0.0],
+            vec![0.0, 2.0, 0.0],
+            vec![0.0, 3.0, 0.0],
+        ]);
+        match lu.solve_view(rhs.col(1)) {
+            LuResult::Single(s) => assert!(expected.float_eq(&s)),
+            LuResult::Infinite(s) => panic!("Wrong result: {}; {}", s.0, s.1),
+        }
+    }
+
+    #[test]
+    fn try_solve_shape_mismatch() {
+        let lu = LuFactorization::new(Array::<f64>::identity(2)).unwrap();
+        match lu.try_solve(Array::new_vec(vec![1.0, 2.0, 3.0])) {
+            Err(e) => assert_eq!(AlgaeError::ShapeMismatch { expected:(1, 2), found:(1, 3) }, e),
+            Ok(_) => panic!("Wrong result: expected a shape mismatch"),
+        }
+    }
+
+    #[test]
+    fn plu_factorization_needs_pivoting() {
+        // The plain LU factorization fails on this matrix.
+        let a = Array::new_mat(vec![
+            vec![1.0, 3.0, 3.0],
+            vec![3.0, 9.0, 9.0],
+            vec![0.5, 1.0, 2.0],
+        ]);
+        let plu = PluFactorization::new(a.clone()).unwrap();
+        assert!((plu.p() * a).float_eq(&(plu.l() * plu.u())));
+        assert!(plu.is_singular());
+        assert!(plu.determinant().float_eq(&0.0));
+        assert_eq!(Err(AlgaeError::Singular), plu.try_solve(Array::new_vec(vec![1.0, 2.0, 3.0])));
+    }
+
+    // Singularity is relative to the scale of the matrix, a well-conditioned matrix with tiny
+    // entries is factorized and inverted like any other.
+    #[test]
+    fn plu_small_scale() {
+        let a = Array::new_mat(vec![vec![1e-16, 2e-16], vec![3e-16, 4e-16]]);
+        let plu = PluFactorization::new(a.clone()).unwrap();
+        assert_close(&((plu.p() * a.clone()) * 1e16), &((plu.l() * plu.u()) * 1e16), 1e-12);
+        assert!(!plu.is_singular());
+        assert!(f64::abs(plu.determinant() + 2e-32) < 1e-46);
+        let inverse = plu.inverse().unwrap();
+        assert_close(&Array::identity(2), &(&a * &inverse), 1e-12);
+        let singular = Array::new_mat(vec![vec![1e-16, 2e-16], vec![2e-16, 4e-16 * (1.0 + f64::EPSILON)]]);
+        assert!(PluFactorization::new(singular).unwrap().is_singular());
+    }
+
+    #[test]
+    fn plu_solve() {
+        let a = Array::new_mat(vec![
+            vec![0.0, 2.0, 1.0],
+            vec![1.0, 1.0, 0.0],
+            vec![2.0, 0.0, 3.0],
+        ]);
+        let plu = PluFactorization::new(a.clone()).unwrap();
+        let expected = Array::new_vec(vec![1.0, 2.0, -1.0]);
+        let b = &a * &expected;
+        assert!(expected.float_eq(&plu.solve(b.clone())));
+        let solutions = plu.solve_many(&[b.clone(), b * 2.0]);
+        assert!((expected.clone() * 2.0).float_eq(&solutions[1]));
+        assert!(plu.determinant().float_eq(&-8.0));
+        let (sign, log) = plu.log_det();
+        assert!(sign.float_eq(&-1.0) && log.float_eq(&f64::ln(8.0)));
+        assert!((&a * &plu.inverse().unwrap()).float_eq(&Array::identity(3)));
+    }
+
+    #[test]
+    fn plu_not_square() {
+        match PluFactorization::new(Array::new_filled((3, 2), 1.0)) {
+            Ok(plu) => panic!("Wrong result: {}", plu),
+            Err(e) => assert_eq!(AlgaeError::NotSquare { size:(3, 2) }, e),
+        }
+    }
+
+    #[test]
+    fn qr_factorization() {
+        let a = Array::new_mat(vec![
+            vec![12.0, -51.0, 4.0],
+            vec![6.0, 167.0, -68.0],
+            vec![-4.0, 24.0, -41.0],
+            vec![1.0, 1.0, 1.0],
+        ]);
+        for qr in [QrFactorization::new(a.clone()), QrFactorization::new_pivoted(a.clone())] {
+            let q = qr.q();
+            assert_close(&(q.transpose() * q.clone()), &Array::identity(4), 1e-12);
+            assert_close(&(q * qr.r()), &(&a * &qr.p()), 1e-12);
+            assert_close(&(qr.thin_q() * qr.thin_r()), &(&a * &qr.p()), 1e-12);
+            assert_eq!((3, 4), qr.thin_q().size);
+            assert_eq!(3, qr.rank());
+        }
+    }
+
+    #[test]
+    fn least_squares() {
+        // Fits a line through (0, 1), (1, 2) and (2, 2).
+        let a = Array::new_mat(vec![vec![1.0, 0.0], vec![1.0, 1.0], vec![1.0, 2.0]]);
+        let b = Array::new_vec(vec![1.0, 2.0, 2.0]);
+        let (x, residual) = QrFactorization::new(a).least_squares(&b).unwrap();
+        assert_close(&Array::new_vec(vec![7.0 / 6.0, 0.5]), &x, 1e-12);
+        assert!(f64::abs(residual - f64::sqrt(6.0) / 6.0) < 1e-12);
+    }
+
+    #[test]
+    fn least_squares_rank_deficient() {
+        // The third column is twice the second one.
+        let a = Array::new_mat(vec![vec![1.0, 0.0, 0.0], vec![1.0, 1.0, 2.0], vec![1.0, 2.0, 4.0]]);
+        let b = Array::new_vec(vec![1.0, 2.0, 2.0]);
+        assert_eq!(Err(AlgaeError::Singular), QrFactorization::new(a.clone()).least_squares(&b).map(|_| ()));
+        let qr = QrFactorization::new_pivoted(a.clone());
+        assert_eq!(2, qr.rank());
+        let (x, residual) = Array::least_squares(a.clone(), &b).unwrap();
+        assert_close(&Array::new_vec(vec![7.0 / 6.0, 5.0 / 3.0, 13.0 / 6.0]), &(a * x), 1e-12);
+        assert!(f64::abs(residual - f64::sqrt(6.0) / 6.0) < 1e-12);
+    }
+
+    #[test]
+    fn cholesky_factorization() {
+        let a = Array::new_mat(vec![
+            vec![4.0, 12.0, -16.0],
+            vec![12.0, 37.0, -43.0],
+            vec![-16.0, -43.0, 98.0],
+        ]);
+        let cholesky = CholeskyFactorization::new(a.clone()).unwrap();
+        let expected = Array::new_mat(vec![
+            vec![2.0, 0.0, 0.0],
+            vec![6.0, 1.0, 0.0],
+            vec![-8.0, 5.0, 3.0],
+        ]);
+        assert_close(&expected, cholesky.l(), 1e-12);
+        assert!(f64::abs(cholesky.log_det() - 2.0 * f64::ln(6.0)) < 1e-12);
+        let x = Array::new_vec(vec![1.0, -1.0, 2.0]);
+        assert_close(&x, &cholesky.solve(&a * &x), 1e-12);
+        assert_close(&Array::identity(3), &(&a * &cholesky.inverse()), 1e-12);
+    }
+
+    #[test]
+    fn cholesky_not_positive_definite() {
+        match CholeskyFactorization::new(Array::new_mat(vec![vec![1.0, 2.0], vec![2.0, 1.0]])) {
+            Ok(c) => panic!("Wrong result: {}", c),
+            Err(e) => assert_eq!(AlgaeError::NotPositiveDefinite { index:1 }, e),
+        }
+    }
+
+    #[test]
+    fn cholesky_update_downdate() {
+        let a = Array::new_mat(vec![
+            vec![4.0, 12.0, -16.0],
+            vec![12.0, 37.0, -43.0],
+            vec![-16.0, -43.0, 98.0],
+        ]);
+        let x = Array::new_vec(vec![1.0, 2.0, -1.0]);
+        let mut cholesky = CholeskyFactorization::new(a.clone()).unwrap();
+        cholesky.update(&x);
+        let updated = CholeskyFactorization::new(&a + &(&x * &x.transpose())).unwrap();
+        assert_close(updated.l(), cholesky.l(), 1e-12);
+        cholesky.downdate(&x).unwrap();
+        assert_close(CholeskyFactorization::new(a).unwrap().l(), cholesky.l(), 1e-12);
+        let before = cholesky.l().clone();
+        let too_large = Array::new_vec(vec![3.0, 0.0, 0.0]);
+        assert_eq!(Err(AlgaeError::NotPositiveDefinite { index:0 }), cholesky.downdate(&too_large));
+        assert_eq!(&before, cholesky.l());
+        let wrong_size = Array::new_vec(vec![1.0, 2.0]);
+        assert_eq!(Err(AlgaeError::ShapeMismatch { expected:(1, 3), found:(1, 2) }), cholesky.try_update(&wrong_size));
+        assert_eq!(Err(AlgaeError::ShapeMismatch { expected:(1, 3), found:(1, 2) }), cholesky.downdate(&wrong_size));
+        assert_eq!(&before, cholesky.l());
+    }
+
+    #[test]
+    fn ldlt_factorization() {
+        let a = Array::new_mat(vec![
+            vec![0.0, 1.0, 2.0, 1.0],
+            vec![1.0, 0.0, 3.0, -1.0],
+            vec![2.0, 3.0, 0.0, 4.0],
+            vec![1.0, -1.0, 4.0, 2.0],
+        ]);
+        let ldlt = LdltFactorization::new(a.clone()).unwrap();
+        let p = ldlt.p();
+        let reconstructed = ldlt.l() * ldlt.d() * ldlt.l().transpose();
+        assert_close(&(&(&p * &a) * &p.transpose()), &reconstructed, 1e-12);
+        let x = Array::new_vec(vec![1.0, 2.0, -1.0, 0.5]);
+        assert_close(&x, &ldlt.solve(&a * &x), 1e-12);
+        let plu = PluFactorization::new(a).unwrap();
+        assert!(f64::abs(plu.determinant() - ldlt.determinant()) < 1e-12);
+    }
+
+    #[test]
+    fn ldlt_two_by_two_pivot() {
+        let ldlt = LdltFactorization::new(Array::new_mat(vec![vec![0.0, 1.0], vec![1.0, 0.0]])).unwrap();
+        assert_close(&Array::new_mat(vec![vec![0.0, 1.0], vec![1.0, 0.0]]), &ldlt.d(), 1e-12);
+        assert_eq!((1, 1, 0), ldlt.inertia());
+        let singular = LdltFactorization::new(Array::new_filled((2, 2), 1.0)).unwrap();
+        assert_eq!((1, 0, 1), singular.inertia());
+        assert_eq!(Err(AlgaeError::Singular), singular.try_solve(Array::new_vec(vec![1.0, 1.0])));
+    }
+}
@@ -1,20 +1,27 @@
 use crate::array::array::Array;
+use crate::array::view::ArrayViewMut;
 use crate::array::methods::multiply_row;
 use crate::array::methods::multiply_add_row;
-use crate::array::float_eq::FloatEq;
-use std::ops::{Add, Sub, Neg, Mul, Div};
+use crate::array::float_eq::{FloatEq, PivotMagnitude, Tolerance};
+use crate::array::elementary::{Permutation, RowOperation};
+use crate::error::error::AlgaeError;
+use std::ops::{Add, Sub, Neg, Mul, Div, Range};
 use num::traits::{One, Zero};
 
-impl<T: Copy + Clone + FloatEq> FloatEq for Array<T> {
-    fn float_eq(&self, other:&Self) -> bool {
+impl<T: Clone + FloatEq> FloatEq for Array<T> {
+    fn approx_eq_with(&self, other:&Self, tolerance:Tolerance) -> bool {
         if self.size != other.size {return false;}
         for i in 0..self.size.0 {
             for j in 0..self.size.1 {
-                if !self[(j, i)].float_eq(&other[(j, i)]) {return false;}
+                if !self[(j, i)].approx_eq_with(&other[(j, i)], tolerance) {return false;}
             }
         }
         true
     }
+
+    fn standard_tolerance() -> Tolerance {
+        T::standard_tolerance()
+    }
 }
 
 pub enum LinearSystemResult<T> {
@@ -23,198 +30,386 @@ pub enum LinearSystemResult<T> {
     Inconsistent,
 }
 
-impl<T: Copy + Clone + Zero + One + PartialEq
- + Add<Output = T> + Sub<Output = T> + Neg<Output = T> + Mul<Output = T> + Div<Output = T> + FloatEq> 
-Array<T> {
-    pub fn echelon_form(&mut self) {
-        let mut pivot = (0, 0);
-        while pivot.0 < self.size.1 && pivot.1 < self.size.0 {
-            // Ensure pivot position != 0
-            loop {
-                let mut temp = Vec::<Vec<T>>::with_capacity(self.size.1);
-                while pivot.0 < self.content.len() && T::is_zero(&self[pivot]) {
-                    temp.push(self.content.swap_remove(pivot.0));
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PivotingStrategy {
+    // First entry of the column that is not negligible, meant for exact fields.
+    None,
+    // Entry of largest magnitude in the column.
+    Partial,
+    // Entry of largest magnitude in the remaining sub matrix. This also reorders the columns.
+    Complete,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PivotingOptions {
+    pub strategy:PivotingStrategy,
+    // Entries within the absolute tolerance, or the relative one times the largest magnitude
+    // of the matrix, count as zero. Right hand sides are measured against their own largest
+    // magnitude. None is relative, PivotMagnitude::default_tolerance scaled by the larger
+    // dimension.
+    pub tolerance:Option<Tolerance>,
+}
+
+// Relative to the largest magnitude involved, so the result doesn't depend on the scale of the matrix.
+pub(in crate::array) fn default_tolerance<T: PivotMagnitude>(size:(usize, usize)) -> Tolerance {
+    Tolerance::relative(T::default_tolerance() * usize::max(size.0, size.1) as f64)
+}
+
+impl PivotingOptions {
+    pub fn new(strategy:PivotingStrategy, tolerance:Option<Tolerance>) -> Self {
+        PivotingOptions {
+            strategy,
+            tolerance,
+        }
+    }
+}
+
+impl Default for PivotingOptions {
+    fn default() -> Self {
+        PivotingOptions::new(PivotingStrategy::Partial, None)
+    }
+}
+
+// Row i of an echelon form was computed from row row_permutation[i] of the input and column j
+// from column column_permutation[j], i.e. the result is an echelon form of P * A * Q.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PivotRecord {
+    pub row_permutation:Vec<usize>,
+    pub column_permutation:Vec<usize>,
+    // Positions of the pivots in the echelon form, their count is the rank.
+    pub pivots:Vec<(usize, usize)>,
+}
+
+impl PivotRecord {
+    pub fn rank(&self) -> usize {
+        self.pivots.len()
+    }
+
+    pub fn permutations(&self) -> (Permutation, Permutation) {
+        (Permutation::from_vec_unchecked(self.row_permutation.clone()),
+            Permutation::from_vec_unchecked(self.column_permutation.clone()))
+    }
+
+    // P * A
+    pub fn permute_rows<T: Clone>(&self, a:&Array<T>) -> Array<T> {
+        self.permutations().0.permute_rows(a)
+    }
+
+    // A * Q
+    pub fn permute_cols<T: Clone>(&self, a:&Array<T>) -> Array<T> {
+        self.permutations().1.permute_cols(a)
+    }
+}
+
+impl<'a, T: Clone + Zero + One + PartialEq
+ + Add<Output = T> + Sub<Output = T> + Neg<Output = T> + Mul<Output = T> + Div<Output = T> + FloatEq + PivotMagnitude> 
+ArrayViewMut<'a, T> {
+    pub fn echelon_form(&mut self) -> PivotRecord {
+        self.echelon_form_with(PivotingOptions::default())
+    }
+
+    pub fn echelon_form_with(&mut self, options:PivotingOptions) -> PivotRecord {
+        self.eliminate(options, self.size.0, None)
+    }
+
+    // Also returns every row operation in the order it was performed. Replaying them on the
+    // input and permuting the columns by the record reproduces the echelon form, except for
+    // the entries below the tolerance, which are set to zero directly.
+    pub fn echelon_form_recorded(&mut self, options:PivotingOptions) -> (PivotRecord, Vec<RowOperation<T>>) {
+        let mut operations = Vec::<RowOperation<T>>::new();
+        let record = self.eliminate(options, self.size.0, Some(&mut operations));
+        (record, operations)
+    }
+
+    // Complete pivoting only exchanges columns below pivot_width, so an augmented [A | b] keeps
+    // b in place. The columns from pivot_width on are eliminated with partial pivoting.
+    fn eliminate(&mut self, options:PivotingOptions, pivot_width:usize, mut operations:Option<&mut Vec<RowOperation<T>>>)
+    -> PivotRecord {
+        let (width, height) = self.size;
+        let mut record = PivotRecord {
+            row_permutation:(0..height).collect(),
+            column_permutation:(0..width).collect(),
+            pivots:Vec::<(usize, usize)>::with_capacity(usize::min(width, height)),
+        };
+        // The columns from pivot_width on, e.g. the right hand sides, are judged by their own scale.
+        let mut scales = (0.0, 0.0);
+        for row in 0..height {
+            for col in 0..width {
+                let magnitude = self[(row, col)].magnitude();
+                if col < pivot_width {
+                    scales.0 = f64::max(scales.0, magnitude);
+                } else {
+                    scales.1 = f64::max(scales.1, magnitude);
                 }
-                if pivot.0 >= self.content.len() {
+            }
+        }
+        let tolerance = options.tolerance.unwrap_or(default_tolerance::<T>(self.size));
+        let significant = |value:&T, col:usize| {
+            !tolerance.negligible(value.magnitude(), if col < pivot_width { scales.0 } else { scales.1 })
+        };
+        let mut pivot = (0, 0);
+        while pivot.0 < height && pivot.1 < width {
+            let candidate = match options.strategy {
+                PivotingStrategy::None => (pivot.0..height)
+                    .map(|row| (row, pivot.1))
+                    .find(|index| significant(&self[*index], index.1)),
+                PivotingStrategy::Complete if pivot.1 < pivot_width => self.largest_entry(pivot.0..height, pivot.1..pivot_width)
+                    .filter(|index| significant(&self[*index], index.1)),
+                PivotingStrategy::Partial | PivotingStrategy::Complete => self.largest_entry(pivot.0..height, pivot.1..(pivot.1 + 1))
+                    .filter(|index| significant(&self[*index], index.1)),
+            };
+            let (row, col) = match candidate {
+                Some(index) => index,
+                None if options.strategy == PivotingStrategy::Complete && pivot.1 < pivot_width => {
+                    // Everything that is left of pivot_width is negligible.
+                    for row in pivot.0..height {
+                        for col in pivot.1..pivot_width {
+                            self[(row, col)] = T::zero();
+                        }
+                    }
+                    pivot.1 = pivot_width;
+                    continue;
+                },
+                None => {
+                    for row in pivot.0..height {
+                        self[(row, pivot.1)] = T::zero();
+                    }
                     pivot.1 += 1;
-                } 
-                self.content.append(&mut temp);
-                // This way the elementary matrix only performs a single swap
-                // temp = temp.clone().into_iter().rev().collect();
-                // match temp.pop() {
-                //     Some(t) => {
-                //         temp.insert(0, t);
-                //         self.content.append(&mut temp);
-                //     },
-                //     None => {},
-                // }
-                if pivot.1 >= self.size.0 {
-                    return;
+                    continue;
+                },
+            };
+            if row != pivot.0 {
+                self.swap_rows(pivot.0, row);
+                record.row_permutation.swap(pivot.0, row);
+                if let Some(operations) = operations.as_mut() {
+                    operations.push(RowOperation::Swap(pivot.0, row));
                 }
-                if !T::is_zero(&self[pivot]) {
-                    break;
+            }
+            if col != pivot.1 {
+                for row in 0..height {
+                    let temp = self[(row, pivot.1)].clone();
+                    self[(row, pivot.1)] = self[(row, col)].clone();
+                    self[(row, col)] = temp;
                 }
+                record.column_permutation.swap(pivot.1, col);
             }
-            let factor = T::one()/self[pivot];
-            multiply_row(self, pivot.0, factor, pivot.1);
+            let factor = T::one()/self[pivot].clone();
+            multiply_row(self, pivot.0, factor.clone(), pivot.1);
             self[pivot] = T::one();
-            for row in (pivot.0 + 1)..self.size.1 {
+            if let Some(operations) = operations.as_mut() {
+                operations.push(RowOperation::Scale {
+                    row:pivot.0,
+                    factor,
+                });
+            }
+            for row in (pivot.0 + 1)..height {
                 if !T::is_zero(&self[(row, pivot.1)]) {
-                    let factor = -self[(row, pivot.1)];
-                    multiply_add_row(self, pivot.0, row, factor, pivot.1);
+                    let factor = -self[(row, pivot.1)].clone();
+                    multiply_add_row(self, pivot.0, row, factor.clone(), pivot.1);
+                    self[(row, pivot.1)] = T::zero();
+                    if let Some(operations) = operations.as_mut() {
+                        operations.push(RowOperation::AddMultiple {
+                            from:pivot.0,
+                            to:row,
+                            factor,
+                        });
+                    }
                 }
             }
+            record.pivots.push(pivot);
             pivot.0 += 1;
             pivot.1 += 1;
         }
+        record
     }
 
-    pub(in crate::array) fn echelon_form_to_reduced_echelon_form(&mut self) {
-        let mut pivot = (self.size.1 - 1, 0);
-        while self[pivot].float_eq(&T::zero()) {
-            pivot.1 += 1;
-            if pivot.1 >= self.size.0 {
-                if pivot.0 == 0 {
-                    break;
+    // First index of largest magnitude within the given block.
+    fn largest_entry(&self, rows:Range<usize>, cols:Range<usize>) -> Option<(usize, usize)> {
+        let mut largest:Option<((usize, usize), f64)> = None;
+        for row in rows {
+            for col in cols.clone() {
+                let magnitude = self[(row, col)].magnitude();
+                if largest.is_none_or(|(_, m)| magnitude > m) {
+                    largest = Some(((row, col), magnitude));
                 }
-                pivot.0 -= 1;
-                pivot.1 = 0;
-            } 
-        }
-        while pivot.0 > 0 {
-            while pivot.1 > 0 && self[(pivot.0, pivot.1 - 1)] != T::zero() {
-                pivot.1 -= 1;
             }
-            for row in 0..pivot.0 {
-                let factor = -self[(row, pivot.1)];
-                multiply_add_row(self, pivot.0, row, factor, pivot.1);
+        }
+        largest.map(|(index, _)| index)
+    }
+
+    // Expects the output of echelon_form, which stores exact zeros in front of every
+    // leading one, so the pivots are found without any tolerance.
+    pub(in crate::array) fn echelon_form_to_reduced_echelon_form(&mut self) {
+        for pivot_row in (0..self.size.1).rev() {
+            let pivot_col = match (0..self.size.0).find(|col| !T::is_zero(&self[(pivot_row, *col)])) {
+                Some(col) => col,
+                None => continue,
+            };
+            for row in 0..pivot_row {
+                if !T::is_zero(&self[(row, pivot_col)]) {
+                    let factor = -self[(row, pivot_col)].clone();
+                    multiply_add_row(self, pivot_row, row, factor, pivot_col);
+                    self[(row, pivot_col)] = T::zero();
+                }
             }
-            pivot.0 -= 1;
         }
     }
 
-    pub fn reduced_echelon_form(&mut self) {
-        self.echelon_form();
+    pub fn reduced_echelon_form(&mut self) -> PivotRecord {
+        self.reduced_echelon_form_with(PivotingOptions::default())
+    }
+
+    pub fn reduced_echelon_form_with(&mut self, options:PivotingOptions) -> PivotRecord {
+        let record = self.echelon_form_with(options);
+        self.echelon_form_to_reduced_echelon_form();
+        record
+    }
+
+    // Reduced echelon form of an augmented [A | b], where A has pivot_width columns.
+    pub(in crate::array) fn reduced_augmented_form_with(&mut self, options:PivotingOptions, pivot_width:usize)
+    -> PivotRecord {
+        let record = self.eliminate(options, pivot_width, None);
         self.echelon_form_to_reduced_echelon_form();
+        record
+    }
+}
+
+impl<T: Clone + Zero + One + PartialEq
+ + Add<Output = T> + Sub<Output = T> + Neg<Output = T> + Mul<Output = T> + Div<Output = T> + FloatEq + PivotMagnitude> 
+Array<T> {
+    pub fn echelon_form(&mut self) -> PivotRecord {
+        self.view_mut().echelon_form()
+    }
+
+    pub fn echelon_form_with(&mut self, options:PivotingOptions) -> PivotRecord {
+        self.view_mut().echelon_form_with(options)
+    }
+
+    pub fn echelon_form_recorded(&mut self, options:PivotingOptions) -> (PivotRecord, Vec<RowOperation<T>>) {
+        self.view_mut().echelon_form_recorded(options)
+    }
+
+    pub fn reduced_echelon_form(&mut self) -> PivotRecord {
+        self.view_mut().reduced_echelon_form()
+    }
+
+    pub fn reduced_echelon_form_with(&mut self, options:PivotingOptions) -> PivotRecord {
+        self.view_mut().reduced_echelon_form_with(options)
     }
     
-    pub(in crate::array) fn extract_solution_from_matrix(res:Array<T>, mat:Array<T>)
+    // Reads the solutions off the reduced echelon form res of [A | b], mat is A. The pivot of a
+    // row is its first entry that isn't negligible next to the largest magnitude of its part of
+    // res, a row without a pivot in A but with one in b makes the system inconsistent.
+    pub(in crate::array) fn extract_solution_from_matrix(res:Array<T>, mat:Array<T>, tolerance:Tolerance)
     -> LinearSystemResult<T> {
-        let (a, b) = Array::split_0_axis(res.clone(), mat.size.0);
-        // Might need a check if the outer split is even possible
-        let a = {
-            let mut temp = Array::split_1_axis(
-                a.clone(),
-                a.size.0,
-            ).0;
-            while temp.get_row(temp.size.1 - 1) == Array::new_filled((temp.size.0, 1), T::zero()) {
-                temp = Array::split_1_axis(
-                    temp.clone(),
-                    temp.size.1 - 1,
-                ).0;
-            }
-            temp
-        };
-        let mut b = match Array::split_1_axis(
-            b.clone(),
-            a.size.0,
-        ) {
-            (i, j) if j.float_eq(&Array::new_filled(j.size, T::zero())) => {
-                i
-            },
-            _ => return LinearSystemResult::Inconsistent,
+        let (width, height) = mat.size;
+        let rhs = res.size.0 - width;
+        // A and the right hand sides are judged by their own scales.
+        let scale = |cols:Range<usize>| cols.flat_map(|col| (0..height).map(move |row| (row, col)))
+            .fold(0.0, |acc, index| f64::max(acc, res[index].magnitude()));
+        let scales = (scale(0..width), scale(width..res.size.0));
+        let is_zero = |index:(usize, usize)| {
+            let scale = if index.1 < width { scales.0 } else { scales.1 };
+            res[index] == T::zero() || tolerance.negligible(res[index].magnitude(), scale)
         };
-        while b.size.1 > 0 && b.get_row(b.size.1 - 1) == Array::new_filled((b.size.0, 1), T::zero()) {
-            b = Array::split_1_axis(
-                b.clone(),
-                b.size.1 - 1,
-            ).0;
-        }
-        if a.float_eq(&Array::identity(a.size.0)) {
-            let mut r = b.clone();
-            r.extend_to((b.size.0, mat.size.1), T::zero());
-            LinearSystemResult::Single(r)
-        } else if b.size.1 > a.size.1 {
-            LinearSystemResult::Inconsistent
-        } else {
-            let mut pivot = (res.size.1 - 1, 0);
-            while res[pivot].float_eq(&T::zero()) {
-                pivot.1 += 1;
-                if pivot.1 >= res.size.0 {
-                    if pivot.0 == 0 {
-                        break;
-                    }
-                    pivot.0 -= 1;
-                    pivot.1 = 0;
-                } 
+        let mut pivots = Vec::<(usize, usize)>::with_capacity(usize::min(width, height));
+        for row in 0..height {
+            match (0..width).find(|col| !is_zero((row, *col))) {
+                Some(col) => pivots.push((row, col)),
+                None if (width..res.size.0).any(|col| !is_zero((row, col))) => {
+                    return LinearSystemResult::Inconsistent;
+                },
+                None => {},
             }
-            let mut free_variables:Option<Array<T>> = None;
-            let mut pivot_row = 0;
-            for col_index in 0..a.size.0 {
-                if pivot_row >= a.size.1 || a[(pivot_row, col_index)].float_eq(&T::zero()) {
-                    let mut col = -a.get_col(col_index);
-                    col.extend_to((1, mat.size.0), T::one());
-                    match free_variables {
-                        Some(arr) => {
-                            free_variables = Some(Array::concat_0_axis(arr, col));
-                        },
-                        None => free_variables = Some(col),
-                    }
-                } else {
-                    pivot_row += 1;
-                }
+        }
+        let mut particular = Array::new_filled((rhs, width), T::zero());
+        for &(row, col) in pivots.iter() {
+            for k in 0..rhs {
+                particular[(col, k)] = res[(row, width + k)].clone() / res[(row, col)].clone();
             }
-            b.extend_to((b.size.0, mat.size.1), T::zero());
-            match free_variables {
-                Some(arr) => {
-                    // here fixed + any linear combination of the column vectors in arr will yield a result.
-                    LinearSystemResult::Infinite((b, arr))
-                },
-                None => {
-                    panic!("Faulty implementation");
-                }
+        }
+        let free = (0..width)
+            .filter(|col| !pivots.iter().any(|pivot| pivot.1 == *col))
+            .collect::<Vec<usize>>();
+        if free.is_empty() {
+            return LinearSystemResult::Single(particular);
+        }
+        // Every free variable spans one direction: itself set to one, the pivot variables
+        // adjusted so that the homogeneous system still holds.
+        let mut directions = Array::new_filled((free.len(), width), T::zero());
+        for (i, &col) in free.iter().enumerate() {
+            directions[(col, i)] = T::one();
+            for &(row, pivot_col) in pivots.iter() {
+                directions[(pivot_col, i)] = -res[(row, col)].clone() / res[(row, pivot_col)].clone();
             }
         }
+        LinearSystemResult::Infinite((particular, directions))
     }
 
     pub fn solve(a:Array<T>, b:Array<T>) -> LinearSystemResult<T> {
+        match Self::try_solve(a, b) {
+            Ok(res) => res,
+            Err(e) => panic!("The height of the A matrix and the b vector must be equal. {}", e),
+        }
+    }
+
+    pub fn try_solve(a:Array<T>, b:Array<T>) -> Result<LinearSystemResult<T>, AlgaeError> {
+        Self::try_solve_with(a, b, PivotingOptions::default(), None)
+    }
+
+    // The tolerance decides which entries of the reduced system count as zero when telling the
+    // single, infinite and inconsistent cases apart. None is the default of PivotingOptions.
+    pub fn try_solve_with(a:Array<T>, b:Array<T>, options:PivotingOptions, tolerance:Option<Tolerance>)
+    -> Result<LinearSystemResult<T>, AlgaeError> {
         if a.size.1 != b.size.1 {
-            panic!("The height of the A matrix and the b vector must be equal.");
+            return Err(AlgaeError::ShapeMismatch {
+                expected:(b.size.0, a.size.1),
+                found:b.size,
+            });
         } 
         let mut m = Array::concat_0_axis(a.clone(), b);
-        m.reduced_echelon_form();
-        Self::extract_solution_from_matrix(m, a)
+        let record = m.view_mut().reduced_augmented_form_with(options, a.size.0);
+        // The unknowns were reordered along with the columns of A by complete pivoting.
+        let unknowns = Permutation::from_vec_unchecked(record.column_permutation[..a.size.0].to_vec()).inverse();
+        let tolerance = tolerance.unwrap_or(default_tolerance::<T>(a.size));
+        Ok(match Self::extract_solution_from_matrix(m, a, tolerance) {
+            LinearSystemResult::Single(x) => LinearSystemResult::Single(unknowns.permute_rows(&x)),
+            LinearSystemResult::Infinite((x, directions)) => LinearSystemResult::Infinite(
+                (unknowns.permute_rows(&x), unknowns.permute_rows(&directions))
+            ),
+            LinearSystemResult::Inconsistent => LinearSystemResult::Inconsistent,
+        })
+    }
+
+    pub fn inv(&self) -> Result<Array<T>, AlgaeError> {
+        self.inv_with(None)
     }
 
-    pub fn inv(&self) -> Result<Array<T>, String> {
-        match Self::solve(self.clone(), Self::identity(self.size.0)) {
+    // The tolerance decides which pivots count as zero, see PivotingOptions, and which entries
+    // of the reduced system count as zero, see try_solve_with.
+    pub fn inv_with(&self, tolerance:Option<Tolerance>) -> Result<Array<T>, AlgaeError> {
+        if self.size.0 != self.size.1 {
+            return Err(AlgaeError::NotSquare {
+                size:self.size,
+            });
+        }
+        let options = PivotingOptions::new(PivotingStrategy::Partial, tolerance);
+        match Self::try_solve_with(self.clone(), Self::identity(self.size.0), options, tolerance)? {
             LinearSystemResult::Single(r) => {
                 Ok(r)
             },
             _ => {
-                Err("Non-invertible matrix.".to_string())
+                Err(AlgaeError::Singular)
             },
         }
     }
 
     pub fn rank(&self) -> usize {
-        let mut a = self.clone();
-        a.echelon_form();
-        let mut pivot = (a.size.1 - 1, 0);
-        while a[pivot].float_eq(&T::zero()) {
-            pivot.1 += 1;
-            if pivot.1 >= a.size.0 {
-                if pivot.0 == 0 {
-                    break;
-                }
-                if pivot.0 == 0 {
-                    return 0;
-                }
-                pivot.0 -= 1;
-                pivot.1 = 0;
-            } 
-        }
-        pivot.0 + 1
+        self.rank_with(PivotingOptions::new(PivotingStrategy::Complete, None))
+    }
+
+    pub fn rank_with(&self, options:PivotingOptions) -> usize {
+        self.clone().echelon_form_with(options).rank()
     }
 
     pub fn leontief_input_output_model(consumption:Array<T>, demand:Array<T>) 
@@ -232,9 +427,25 @@ Array<T> {
         Array::solve(a, demand) 
     }
 
+    pub fn try_leontief_input_output_model(consumption:Array<T>, demand:Array<T>) 
+    -> Result<LinearSystemResult<T>, AlgaeError> {
+        if consumption.size.0 != consumption.size.1 {
+            return Err(AlgaeError::NotSquare {
+                size:consumption.size,
+            });
+        }
+        if demand.size != (1, consumption.size.1) {
+            return Err(AlgaeError::ShapeMismatch {
+                expected:(1, consumption.size.1),
+                found:demand.size,
+            });
+        }
+        Ok(Self::leontief_input_output_model(consumption, demand))
+    }
+
     fn replace_col(mut a:Array<T>, col_index:usize, col:Array<T>) -> Array<T> {
         for row_index in 0..a.size.1 {
-            a[(row_index, col_index)] = col[(row_index, 0)];
+            a[(row_index, col_index)] = col[(row_index, 0)].clone();
         }
         a
     }
@@ -246,24 +457,55 @@ Array<T> {
         if b.size.0 != 1 {
             panic!("b must be a vector");
         }
-        let mut content = Vec::<Vec<T>>::with_capacity(b.size.1);
+        match Self::try_cramers_rule(a, b) {
+            Ok(res) => res,
+            Err(e) => panic!("Cramers rule can't be applied to the system. {}", e),
+        }
+    }
+
+    pub fn try_cramers_rule(a:Array<T>, b:Array<T>) -> Result<Array<T>, AlgaeError> {
+        Self::try_cramers_rule_with(a, b, None)
+    }
+
+    pub fn try_cramers_rule_with(a:Array<T>, b:Array<T>, tolerance:Option<Tolerance>) -> Result<Array<T>, AlgaeError> {
+        if a.size.0 != a.size.1 {
+            return Err(AlgaeError::NotSquare {
+                size:a.size,
+            });
+        }
+        if b.size != (1, a.size.1) {
+            return Err(AlgaeError::ShapeMismatch {
+                expected:(1, a.size.1),
+                found:b.size,
+            });
+        }
+        let mut content = Vec::<T>::with_capacity(b.size.1);
         let det_a = a.determinant();
-        if det_a.float_eq(&T::zero()) {
-            panic!("Cramers rule doesn't apply, when det(a) == 0");
+        // Hadamard's inequality bounds |det A| by the product of the column norms.
+        let scale = (0..a.size.0).fold(1.0, |acc, col| {
+            acc * (0..a.size.1).fold(0.0, |sum, row| sum + a[(row, col)].magnitude().powi(2)).sqrt()
+        });
+        let tolerance = tolerance.unwrap_or(default_tolerance::<T>(a.size));
+        if det_a == T::zero() || tolerance.negligible(det_a.magnitude(), scale) {
+            return Err(AlgaeError::Singular);
         }
         for i in 0..b.size.1 {
-            content.push(vec![
-                Self::replace_col(a.clone(), i, b.clone()).determinant() / det_a
-            ]);
-        }
-        Array {
-            content,
-            size:b.size,
+            content.push(
+                Self::replace_col(a.clone(), i, b.clone()).determinant() / det_a.clone()
+            );
         }
+        Ok(Array::new_vec(content))
     }
 
     pub fn null_space(&self) -> Array<T> {
-        match Array::solve(self.clone(), Array::new_filled((1, self.size.1), T::zero())) {
+        self.null_space_with(None)
+    }
+
+    // Same tolerance as inv_with.
+    pub fn null_space_with(&self, tolerance:Option<Tolerance>) -> Array<T> {
+        let options = PivotingOptions::new(PivotingStrategy::Partial, tolerance);
+        let system = Self::try_solve_with(self.clone(), Array::new_filled((1, self.size.1), T::zero()), options, tolerance);
+        match system.unwrap_or_else(|e| panic!("Faulty implementation: the right hand side fits by construction. {}", e)) {
             LinearSystemResult::Single(res) => res,
             LinearSystemResult::Infinite(res) => res.1,
             LinearSystemResult::Inconsistent => panic!("Faulty implementation: Incosistent system of equations."),
@@ -271,24 +513,10 @@ Array<T> {
     }
 
     pub fn get_linear_independent(mut array:Self) -> Array<T> {
-        let echelon_form = {
-            let mut temp = array.clone();
-            temp.echelon_form();
-            temp
-        };
-        let mut pivot = (0, 0);
-        let mut indicies = Vec::<usize>::with_capacity(echelon_form.size.0);
-        while pivot.1 < echelon_form.size.0 {
-            while   (pivot.0 >= echelon_form.size.1 && pivot.1 < echelon_form.size.0) ||
-                    pivot.0 < echelon_form.size.1 &&
-                    pivot.1 < echelon_form.size.0 &&
-                    echelon_form[pivot].float_eq(&T::zero())
-            {
-                indicies.push(pivot.1);
-                pivot.1 += 1;
-            }
-            pivot = (pivot.0 + 1, pivot.1 + 1);
-        }
+        let pivots = array.clone().echelon_form().pivots;
+        let mut indicies = (0..array.size.0)
+            .filter(|col| !pivots.iter().any(|pivot| pivot.1 == *col))
+            .collect::<Vec<usize>>();
         while let Some(i) = indicies.pop() {
             if i + 1 < array.size.0 {
                 let a = Array::split_0_axis(array.clone(), i).0;
@@ -302,11 +530,21 @@ Array<T> {
     }
     
     pub fn change_basis(basis_a:&Array<T>, vec_a:&Array<T>, basis_b:&Array<T>) -> Array<T> {
-        let basis_b_inv = match basis_b.inv() {
-            Ok(i) => i,
+        match Self::try_change_basis(basis_a, vec_a, basis_b) {
+            Ok(res) => res,
             Err(e) => panic!("Error: basis_b not a basis: {}", e),
-        };
-        basis_b_inv * basis_a.clone() * vec_a.clone()
+        }
+    }
+
+    pub fn try_change_basis(basis_a:&Array<T>, vec_a:&Array<T>, basis_b:&Array<T>) -> Result<Array<T>, AlgaeError> {
+        if basis_a.size != basis_b.size {
+            return Err(AlgaeError::ShapeMismatch {
+                expected:basis_b.size,
+                found:basis_a.size,
+            });
+        }
+        let basis_b_inv = basis_b.inv()?;
+        basis_b_inv.try_matmul(&basis_a.try_matmul(vec_a)?)
     }
 }
 
@@ -314,46 +552,101 @@ Array<T> {
 #[cfg(test)]
 mod tests{
     use crate::array::field_methods::Array;
-    use crate::array::float_eq::FloatEq;
+    use crate::array::float_eq::{FloatEq, Tolerance};
     use crate::array::field_methods::LinearSystemResult;
+    use crate::array::field_methods::{PivotingOptions, PivotingStrategy};
+    use crate::array::finite_field::GfP;
+    use crate::array::factorizations::{LuFactorization, LuResult};
+    use crate::error::error::AlgaeError;
+    use num::Complex;
+    use num::rational::{BigRational, Rational64};
+    use num::BigInt;
 
     #[test]
     fn echelon_form() {
-        let expected = Array {
-            content:vec![vec![1.0, 2.0, 3.0], vec![0.0, 1.0, 6.0], vec![0.0, 0.0, 1.0]],
-            size:(3, 3),
-        };
+        let expected = Array::new_mat(vec![vec![1.0, 2.5, 5.5], vec![0.0, 1.0, 7.0], vec![0.0, 0.0, 1.0]]);
+        let mut actual = Array::new_mat(vec![
+            vec![1.0, 2.0, 3.0],
+            vec![1.0, 3.0, 9.0],
+            vec![2.0, 5.0, 11.0]
+        ]);
+        let record = actual.echelon_form();
+        assert_eq!(expected, actual);
+        assert_eq!(vec![2, 1, 0], record.row_permutation);
+        assert_eq!(vec![0, 1, 2], record.column_permutation);
+        assert_eq!(3, record.rank());
+    }
+
+    #[test]
+    fn echelon_form_without_pivoting() {
+        let expected = Array::new_mat(vec![vec![1.0, 2.0, 3.0], vec![0.0, 1.0, 6.0], vec![0.0, 0.0, 1.0]]);
         let actual = {
-            let mut temp = Array {
-                content:vec![
-                    vec![1.0, 2.0, 3.0],
-                    vec![1.0, 3.0, 9.0],
-                    vec![2.0, 5.0, 11.0]
-                ],
-                size:(3, 3)
-            };
-            temp.echelon_form();
+            let mut temp = Array::new_mat(vec![
+                vec![1.0, 2.0, 3.0],
+                vec![1.0, 3.0, 9.0],
+                vec![2.0, 5.0, 11.0]
+            ]);
+            temp.echelon_form_with(PivotingOptions::new(PivotingStrategy::None, None));
             temp
         };
         assert_eq!(expected, actual);
     }
 
+    #[test]
+    fn echelon_form_reconstructs_permuted_input() {
+        let a = Array::new_mat(vec![
+            vec![0.5, 1.0, 4.0],
+            vec![-3.0, 2.0, 1.0],
+            vec![2.0, 7.0, -1.0],
+        ]);
+        let mut expected = a.clone();
+        let record = expected.echelon_form();
+        let mut actual = record.permute_rows(&a);
+        actual.echelon_form_with(PivotingOptions::new(PivotingStrategy::None, None));
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn echelon_form_complete_pivoting() {
+        let expected = Array::new_mat(vec![vec![1.0, 0.375], vec![0.0, 1.0]]);
+        let mut actual = Array::new_mat(vec![vec![1.0, 2.0], vec![3.0, 8.0]]);
+        let record = actual.echelon_form_with(PivotingOptions::new(PivotingStrategy::Complete, None));
+        assert_eq!(expected, actual);
+        assert_eq!(vec![1, 0], record.row_permutation);
+        assert_eq!(vec![1, 0], record.column_permutation);
+    }
+
     #[test]
     fn echelon_form_to_reduced_echelon_form() {
-        let expected = Array {
-            content:vec![vec![1.0, 0.0, 0.0], vec![0.0, 1.0, 0.0], vec![0.0, 0.0, 1.0]],
-            size:(3, 3),
+        let expected = Array::new_mat(vec![vec![1.0, 0.0, 0.0], vec![0.0, 1.0, 0.0], vec![0.0, 0.0, 1.0]]);
+        let actual = {
+            let mut temp = Array::new_mat(vec![
+                vec![1.0, 2.0, 3.0],
+                vec![0.0, 1.0, 9.0],
+                vec![0.0, 0.0, 1.0]
+            ]);
+            temp.view_mut().echelon_form_to_reduced_echelon_form();
+            temp
         };
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn echelon_form_of_sub_block() {
+        let expected = Array::new_mat(vec![
+            vec![9.0, 9.0, 9.0, 9.0],
+            vec![9.0, 1.0, 2.5, 5.5],
+            vec![9.0, 0.0, 1.0, 7.0],
+            vec![9.0, 0.0, 0.0, 1.0],
+        ]);
         let actual = {
-            let mut temp = Array {
-                content:vec![
-                    vec![1.0, 2.0, 3.0],
-                    vec![0.0, 1.0, 9.0],
-                    vec![0.0, 0.0, 1.0]
-                ],
-                size:(3, 3)
-            };
-            temp.echelon_form_to_reduced_echelon_form();
+            let mut temp = Array::new_mat(vec![
+                vec![9.0, 9.0, 9.0, 9.0],
+                vec![9.0, 1.0, 2.0, 3.0],
+                vec![9.0, 1.0, 3.0, 9.0],
+                vec![9.0, 2.0, 5.0, 11.0],
+            ]);
+            temp.slice_mut(1.., 1..).echelon_form();
             temp
         };
         assert_eq!(expected, actual);
@@ -361,19 +654,13 @@ mod tests{
 
     #[test]
     fn reduced_echelon_form() {
-        let expected = Array {
-            content:vec![vec![1.0, 0.0, 3.0], vec![0.0, 1.0, 0.0], vec![0.0, 0.0, 0.0]],
-            size:(3, 3),
-        };
+        let expected = Array::new_mat(vec![vec![1.0, 0.0, 3.0], vec![0.0, 1.0, 0.0], vec![0.0, 0.0, 0.0]]);
         let actual = {
-            let mut temp = Array {
-                content:vec![
-                    vec![1.0, 2.0, 3.0],
-                    vec![1.0, 1.0, 3.0],
-                    vec![3.0, 3.0, 9.0]
-                ],
-                size:(3, 3)
-            };
+            let mut temp = Array::new_mat(vec![
+                vec![1.0, 2.0, 3.0],
+                vec![1.0, 1.0, 3.0],
+                vec![3.0, 3.0, 9.0]
+            ]);
             temp.reduced_echelon_form();
             temp
         };
@@ -382,23 +669,14 @@ mod tests{
 
     #[test]
     fn solve_single() {
-        let expected = Array {
-            content:vec![vec![-16.0], vec![0.3333333333333333], vec![5.333333333333333]],
-            size:(1, 3),
-        };
+        let expected = Array::new_mat(vec![vec![-16.0], vec![0.3333333333333333], vec![5.333333333333333]]);
         let actual = {
-            let a = Array {
-                content:vec![
-                    vec![1.0, 3.0, 3.0],
-                    vec![3.0, 6.0, 9.0],
-                    vec![0.5, 1.0, 2.0],
-                ],
-                size:(3, 3),
-            };
-            let b = Array {
-                content:vec![vec![1.0], vec![2.0], vec![3.0]],
-                size:(1, 3),
-            };
+            let a = Array::new_mat(vec![
+                vec![1.0, 3.0, 3.0],
+                vec![3.0, 6.0, 9.0],
+                vec![0.5, 1.0, 2.0],
+            ]);
+            let b = Array::new_mat(vec![vec![1.0], vec![2.0], vec![3.0]]);
             Array::solve(a, b)
         };
         match actual {
@@ -421,26 +699,14 @@ mod tests{
     //  2 3 5 | 5
     #[test]
     fn solve_infinite() {
-        let expected = (Array {
-            content:vec![vec![1.0], vec![1.0], vec![0.0]],
-            size:(1, 3),
-        }, Array {
-            content:vec![vec![-1.0], vec![-1.0], vec![1.0]],
-            size:(1, 3),
-        });
+        let expected = (Array::new_mat(vec![vec![1.0], vec![1.0], vec![0.0]]), Array::new_mat(vec![vec![-1.0], vec![-1.0], vec![1.0]]));
         let actual = {
-            let a = Array {
-                content:vec![
-                    vec![3.0, 0.0, 3.0],
-                    vec![-1.0, 1.0, 0.0],
-                    vec![2.0, 3.0, 5.0],
-                ],
-                size:(3, 3),
-            };
-            let b = Array {
-                content:vec![vec![3.0], vec![0.0], vec![5.0]],
-                size:(1, 3),
-            };
+            let a = Array::new_mat(vec![
+                vec![3.0, 0.0, 3.0],
+                vec![-1.0, 1.0, 0.0],
+                vec![2.0, 3.0, 5.0],
+            ]);
+            let b = Array::new_mat(vec![vec![3.0], vec![0.0], vec![5.0]]);
             Array::solve(a, b)
         };
         match actual {
@@ -468,18 +734,12 @@ mod tests{
     #[test]
     fn solve_unsolvable() {
         let actual = {
-            let a = Array {
-                content:vec![
-                    vec![1.0, 3.0, 1.0],
-                    vec![1.0, 2.0, 1.0],
-                    vec![4.0, 0.0, 4.0],
-                ],
-                size:(3, 3),
-            };
-            let b = Array {
-                content:vec![vec![4.0], vec![3.0], vec![5.0]],
-                size:(1, 3),
-            };
+            let a = Array::new_mat(vec![
+                vec![1.0, 3.0, 1.0],
+                vec![1.0, 2.0, 1.0],
+                vec![4.0, 0.0, 4.0],
+            ]);
+            let b = Array::new_mat(vec![vec![4.0], vec![3.0], vec![5.0]]);
             Array::solve(a, b)
         };
         match actual {
@@ -495,14 +755,11 @@ mod tests{
 
     #[test]
     fn inverse() {
-        let matrix = Array {
-            content:vec![
-                vec![1.0, 3.0, 3.0],
-                vec![3.0, 6.0, 9.0],
-                vec![0.5, 1.0, 2.0],
-            ],
-            size:(3, 3),
-        };
+        let matrix = Array::new_mat(vec![
+            vec![1.0, 3.0, 3.0],
+            vec![3.0, 6.0, 9.0],
+            vec![0.5, 1.0, 2.0],
+        ]);
         let inverse = match matrix.inv() {
             Ok(i) => i,
             Err(e) => panic!("Error: {}", e),
@@ -515,14 +772,11 @@ mod tests{
     fn rank() {
         let expected = 2;
         let actual = {
-            let temp = Array {
-                content:vec![
-                    vec![3.0, 0.0, 3.0],
-                    vec![-1.0, 1.0, 0.0],
-                    vec![2.0, 3.0, 5.0],
-                ],
-                size:(3, 3),
-            };
+            let temp = Array::new_mat(vec![
+                vec![3.0, 0.0, 3.0],
+                vec![-1.0, 1.0, 0.0],
+                vec![2.0, 3.0, 5.0],
+            ]);
             temp.rank()
         };
         assert_eq!(expected, actual);
@@ -530,25 +784,17 @@ mod tests{
 
     #[test]
     fn leontief_input_output_model_test() {
-        let expected = Array {
-            content:vec![vec![-16.0], vec![0.3333333333333333], vec![5.333333333333333]],
-            size:(1, 3),
-        };
-        match {
-            let consumption = Array {
-                content:vec![
-                    vec![0.0, -3.0, -3.0],
-                    vec![-3.0, -5.0, -9.0],
-                    vec![-0.5, -1.0, -1.0],
-                ],
-                size:(3, 3),
-            };
-            let demand = Array {
-                content:vec![vec![1.0], vec![2.0], vec![3.0]],
-                size:(1, 3),
-            };
+        let expected = Array::new_mat(vec![vec![-16.0], vec![0.3333333333333333], vec![5.333333333333333]]);
+        let actual = {
+            let consumption = Array::new_mat(vec![
+                vec![0.0, -3.0, -3.0],
+                vec![-3.0, -5.0, -9.0],
+                vec![-0.5, -1.0, -1.0],
+            ]);
+            let demand = Array::new_mat(vec![vec![1.0], vec![2.0], vec![3.0]]);
             Array::leontief_input_output_model(consumption, demand)
-        } {
+        };
+        match actual {
             LinearSystemResult::Single(actual) => assert!(expected.float_eq(&actual)),
             LinearSystemResult::Infinite(actual) => panic!("Wrong result: {}, {}", actual.0, actual.1),
             LinearSystemResult::Inconsistent => panic!("Error: Inconsistent system of equations."),
@@ -557,96 +803,289 @@ mod tests{
 
     #[test]
     fn cramers_rule_test() {
-        let expected = Array {
-            content:vec![vec![-16.0], vec![0.3333333333333333], vec![5.333333333333333]],
-            size:(1, 3),
-        };
+        let expected = Array::new_mat(vec![vec![-16.0], vec![0.3333333333333333], vec![5.333333333333333]]);
         let actual = {
-            let a = Array {
-                content:vec![
-                    vec![1.0, 3.0, 3.0],
-                    vec![3.0, 6.0, 9.0],
-                    vec![0.5, 1.0, 2.0],
-                ],
-                size:(3, 3),
-            };
-            let b = Array {
-                content:vec![vec![1.0], vec![2.0], vec![3.0]],
-                size:(1, 3),
-            };
+            let a = Array::new_mat(vec![
+                vec![1.0, 3.0, 3.0],
+                vec![3.0, 6.0, 9.0],
+                vec![0.5, 1.0, 2.0],
+            ]);
+            let b = Array::new_mat(vec![vec![1.0], vec![2.0], vec![3.0]]);
             Array::cramers_rule(a, b)
         };
         assert!(expected.float_eq(&actual));
     }
 
+    // Every zero test is relative to the scale of the system, so tiny but well-conditioned
+    // systems are solved like their rescaled versions.
+    #[test]
+    fn small_scale_systems() {
+        let a = Array::new_mat(vec![vec![1e-16, 2e-16], vec![3e-16, 4e-16]]);
+        let b = Array::new_vec(vec![1e-16, 1e-16]);
+        let expected = Array::new_vec(vec![-1.0, 1.0]);
+        assert!(expected.float_eq(&Array::cramers_rule(a.clone(), b.clone())));
+        match Array::solve(a.clone(), b.clone()) {
+            LinearSystemResult::Single(actual) => assert!(expected.float_eq(&actual)),
+            LinearSystemResult::Infinite(actual) => panic!("Wrong result: {}, {}", actual.0, actual.1),
+            LinearSystemResult::Inconsistent => panic!("Wrong result: Linear system inconsistent."),
+        }
+        match LuFactorization::new(a.clone()).unwrap().solve(b) {
+            LuResult::Single(actual) => assert!(expected.float_eq(&actual)),
+            LuResult::Infinite(actual) => panic!("Wrong result: {}, {}", actual.0, actual.1),
+        }
+        let inverse = Array::new_mat(vec![vec![-2e16, 1e16], vec![1.5e16, -0.5e16]]);
+        assert!(inverse.approx_eq_with(&a.inv().unwrap(), Tolerance::relative(1e-12)));
+    }
+
     #[test]
     fn null_space() {
-        let expected = Array{
-            content:vec![vec![-1.0], vec![-1.0], vec![1.0]],
-            size:(1, 3),
-        };
+        let expected = Array::new_mat(vec![vec![-1.0], vec![-1.0], vec![1.0]]);
         let actual = {
-            Array {
-                content:vec![
-                    vec![3.0, 0.0, 3.0],
-                    vec![-1.0, 1.0, 0.0],
-                    vec![2.0, 3.0, 5.0],
-                ],
-                size:(3, 3),
-            }.null_space()
+            Array::new_mat(vec![
+                vec![3.0, 0.0, 3.0],
+                vec![-1.0, 1.0, 0.0],
+                vec![2.0, 3.0, 5.0],
+            ]).null_space()
         };
         assert_eq!(expected, actual);
     }
 
+    #[test]
+    fn solve_small_leading_pivot() {
+        let expected = Array::new_mat(vec![vec![1.0], vec![1.0]]);
+        let a = Array::new_mat(vec![vec![1e-20, 1.0], vec![1.0, 1.0]]);
+        let b = Array::new_mat(vec![vec![1.0], vec![2.0]]);
+        match Array::solve(a, b) {
+            LinearSystemResult::Single(actual) => assert!(expected.float_eq(&actual)),
+            LinearSystemResult::Infinite(actual) => panic!("Wrong result: {}, {}", actual.0, actual.1),
+            LinearSystemResult::Inconsistent => panic!("Wrong result: Linear system inconsistent."),
+        }
+    }
+
+    #[test]
+    fn solve_complete_pivoting() {
+        let options = PivotingOptions::new(PivotingStrategy::Complete, None);
+        let a = Array::new_mat(vec![vec![1.0, 2.0], vec![3.0, 4.0]]);
+        let b = Array::new_mat(vec![vec![100.0], vec![1.0]]);
+        match Array::try_solve_with(a.clone(), b.clone(), options, None).unwrap() {
+            LinearSystemResult::Single(x) => assert!(b.float_eq(&(a * x))),
+            LinearSystemResult::Infinite(actual) => panic!("Wrong result: {}, {}", actual.0, actual.1),
+            LinearSystemResult::Inconsistent => panic!("Wrong result: Linear system inconsistent."),
+        }
+
+        // The largest entry sits in the last column, so the unknowns get reordered.
+        let a = Array::new_mat(vec![vec![1.0, 2.0, 9.0], vec![2.0, 4.0, 3.0]]);
+        let b = Array::new_mat(vec![vec![12.0], vec![9.0]]);
+        match Array::try_solve_with(a.clone(), b.clone(), options, None).unwrap() {
+            LinearSystemResult::Infinite((x, directions)) => {
+                assert!(b.float_eq(&(a.clone() * x)));
+                assert_eq!((1, 3), directions.size);
+                assert!(Array::new_filled((1, 2), 0.0).float_eq(&(a * directions)));
+            },
+            LinearSystemResult::Single(s) => panic!("Wrong result: {}", s),
+            LinearSystemResult::Inconsistent => panic!("Wrong result: Linear system inconsistent."),
+        }
+
+        let a = Array::new_mat(vec![vec![1.0, 2.0], vec![2.0, 4.0]]);
+        let b = Array::new_mat(vec![vec![1.0], vec![3.0]]);
+        assert!(matches!(Array::try_solve_with(a, b, options, None).unwrap(), LinearSystemResult::Inconsistent));
+    }
+
+    #[test]
+    fn solve_infinite_with_free_variable_between_pivots() {
+        let a = Array::new_mat(vec![vec![1.0, 2.0, 0.0, 1.0], vec![0.0, 0.0, 1.0, 3.0]]);
+        let b = Array::new_mat(vec![vec![4.0], vec![5.0]]);
+        match Array::solve(a.clone(), b.clone()) {
+            LinearSystemResult::Infinite((x, directions)) => {
+                assert!(b.float_eq(&(a.clone() * x)));
+                assert_eq!((2, 4), directions.size);
+                assert_eq!(2, directions.rank());
+                assert!(Array::new_filled((2, 2), 0.0).float_eq(&(a * directions)));
+            },
+            LinearSystemResult::Single(s) => panic!("Wrong result: {}", s),
+            LinearSystemResult::Inconsistent => panic!("Wrong result: Linear system inconsistent."),
+        }
+    }
+
+    #[test]
+    fn rank_of_nearly_dependent_rows() {
+        let a = Array::new_mat(vec![
+            vec![0.1, 0.2, 0.3],
+            vec![0.4, 0.5, 0.6],
+            vec![0.5, 0.7, 0.9],
+        ]);
+        assert_eq!(2, a.rank());
+        assert_eq!(2, a.rank_with(PivotingOptions::default()));
+        let b = Array::new_mat(vec![
+            vec![1.0, 0.0],
+            vec![0.0, 1e-9],
+        ]);
+        assert_eq!(2, b.rank());
+        assert_eq!(1, b.rank_with(PivotingOptions::new(PivotingStrategy::Complete, Some(Tolerance::relative(1e-6)))));
+        assert_eq!(1, b.rank_with(PivotingOptions::new(PivotingStrategy::Complete, Some(Tolerance::absolute(1e-6)))));
+        assert!(b.inv().is_ok());
+        assert_eq!(Err(AlgaeError::Singular), b.inv_with(Some(Tolerance::absolute(1e-6))));
+        assert!(Array::new_vec(vec![0.0, 0.0]).float_eq(&b.null_space()));
+        assert!(Array::new_vec(vec![0.0, 1.0]).float_eq(&b.null_space_with(Some(Tolerance::absolute(1e-6)))));
+    }
+
     #[test]
     fn get_linear_independent_test() {
-        let expected = Array {
-            content:vec![
-                vec![1.0, 3.0],
-                vec![2.0, 3.0],
-                vec![2.0, 1.0],
-            ],
-            size:(2, 3),
-        };
-        let actual = Array::get_linear_independent(Array {
-            content:vec![
-                vec![1.0, 2.0, 3.0, 6.0],
-                vec![2.0, 4.0, 3.0, 6.0],
-                vec![2.0, 4.0, 1.0, 2.0],
-            ],
-            size:(4, 3),
-        });
+        let expected = Array::new_mat(vec![
+            vec![1.0, 3.0],
+            vec![2.0, 3.0],
+            vec![2.0, 1.0],
+        ]);
+        let actual = Array::get_linear_independent(Array::new_mat(vec![
+            vec![1.0, 2.0, 3.0, 6.0],
+            vec![2.0, 4.0, 3.0, 6.0],
+            vec![2.0, 4.0, 1.0, 2.0],
+        ]));
         assert_eq!(expected, actual);
     }
 
     #[test]
     fn change_basis_test() {
-        let basis_a = Array {
-            content:vec![
-                vec![3.0, 4.0, 0.0],
-                vec![0.0, 4.0, 4.0],
-                vec![1.0, 0.0, 4.0],
-            ],
-            size:(3, 3),
-        };
-        let basis_b = Array {
-            content:vec![
-                vec![1.0, 1.0, 1.0],
-                vec![1.0, 2.0, 3.0],
-                vec![1.0, 4.0, 9.0],
-            ],
-            size:(3, 3),
-        };
-        let vec_a = Array {
-            content:vec![
-                vec![1.0],
-                vec![0.0],
-                vec![0.0],
-            ],
-            size:(1, 3),
-        };
+        let basis_a = Array::new_mat(vec![
+            vec![3.0, 4.0, 0.0],
+            vec![0.0, 4.0, 4.0],
+            vec![1.0, 0.0, 4.0],
+        ]);
+        let basis_b = Array::new_mat(vec![
+            vec![1.0, 1.0, 1.0],
+            vec![1.0, 2.0, 3.0],
+            vec![1.0, 4.0, 9.0],
+        ]);
+        let vec_a = Array::new_mat(vec![
+            vec![1.0],
+            vec![0.0],
+            vec![0.0],
+        ]);
         let vec_b = Array::change_basis(&basis_a, &vec_a, &basis_b);
         let vec_a_back = Array::change_basis(&basis_b, &vec_b, &basis_a);
         assert!(vec_a.float_eq(&vec_a_back));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn try_solve_shape_mismatch() {
+        let a = Array::new_mat(vec![vec![1.0, 0.0], vec![0.0, 1.0]]);
+        let b = Array::new_vec(vec![1.0, 2.0, 3.0]);
+        match Array::try_solve(a, b) {
+            Err(e) => assert_eq!(AlgaeError::ShapeMismatch { expected:(1, 2), found:(1, 3) }, e),
+            Ok(_) => panic!("Wrong result: expected a shape mismatch"),
+        }
+    }
+
+    #[test]
+    fn inverse_singular() {
+        let matrix = Array::new_mat(vec![
+            vec![1.0, 2.0],
+            vec![2.0, 4.0],
+        ]);
+        assert_eq!(Err(AlgaeError::Singular), matrix.inv());
+        assert_eq!(Err(AlgaeError::NotSquare { size:(1, 2) }), Array::new_vec(vec![1.0, 2.0]).inv());
+    }
+
+    #[test]
+    fn try_cramers_rule_singular() {
+        let a = Array::new_mat(vec![
+            vec![1.0, 2.0],
+            vec![2.0, 4.0],
+        ]);
+        let b = Array::new_vec(vec![1.0, 2.0]);
+        assert_eq!(Err(AlgaeError::Singular), Array::try_cramers_rule(a, b));
+    }
+
+    #[test]
+    fn try_change_basis_singular() {
+        let basis_a = Array::identity(2);
+        let basis_b = Array::new_mat(vec![
+            vec![1.0, 2.0],
+            vec![2.0, 4.0],
+        ]);
+        let vec_a = Array::new_vec(vec![1.0, 0.0]);
+        assert_eq!(Err(AlgaeError::Singular), Array::try_change_basis(&basis_a, &vec_a, &basis_b));
+    }
+
+    #[test]
+    fn inverse_rational_hilbert() {
+        let r = |n:i64, d:i64| Rational64::new(n, d);
+        let hilbert = Array::new_mat(vec![
+            vec![r(1, 1), r(1, 2), r(1, 3)],
+            vec![r(1, 2), r(1, 3), r(1, 4)],
+            vec![r(1, 3), r(1, 4), r(1, 5)],
+        ]);
+        let expected = Array::new_mat(vec![
+            vec![r(9, 1), r(-36, 1), r(30, 1)],
+            vec![r(-36, 1), r(192, 1), r(-180, 1)],
+            vec![r(30, 1), r(-180, 1), r(180, 1)],
+        ]);
+        assert_eq!(Ok(expected), hilbert.inv());
+        assert_eq!(r(1, 2160), hilbert.determinant());
+    }
+
+    // The determinant of the 10x10 Hilbert matrix is the reciprocal of an integer past i64::MAX,
+    // so only arbitrary precision rationals get through the elimination.
+    #[test]
+    fn inverse_big_rational_hilbert() {
+        let n = 10;
+        let hilbert = Array::new_mat((1..=n).map(|row| {
+            (1..=n).map(|col| BigRational::new(BigInt::from(1), BigInt::from(row + col - 1))).collect()
+        }).collect());
+        let inverse = hilbert.inv().unwrap();
+        assert_eq!(Array::identity(n as usize), &inverse * &hilbert);
+        assert_eq!(BigRational::from_integer(BigInt::from(n * n)), inverse[(0, 0)]);
+        let det = hilbert.determinant();
+        assert!(det.recip().is_integer());
+        assert!(det.recip() > BigRational::from_integer(BigInt::from(i64::MAX)));
+    }
+
+    #[test]
+    fn null_space_rational() {
+        let r = |n:i64| Rational64::from_integer(n);
+        let expected = Array::new_mat(vec![vec![r(-1)], vec![r(-1)], vec![r(1)]]);
+        let actual = Array::new_mat(vec![
+            vec![r(3), r(0), r(3)],
+            vec![r(-1), r(1), r(0)],
+            vec![r(2), r(3), r(5)],
+        ]).null_space();
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn solve_modular() {
+        type Gf7 = GfP<7>;
+        let g = |n:i64| Gf7::from_signed(n);
+        let a = Array::new_mat(vec![
+            vec![g(2), g(3), g(1)],
+            vec![g(4), g(1), g(5)],
+            vec![g(1), g(6), g(3)],
+        ]);
+        let x = Array::new_mat(vec![vec![g(1)], vec![g(2)], vec![g(3)]]);
+        let b = a.clone() * x.clone();
+        match Array::solve(a.clone(), b) {
+            LinearSystemResult::Single(actual) => assert_eq!(x, actual),
+            LinearSystemResult::Infinite(actual) => panic!("Wrong result: {}, {}", actual.0, actual.1),
+            LinearSystemResult::Inconsistent => panic!("Wrong result: Linear system inconsistent."),
+        }
+        let inverse = a.inv().unwrap();
+        assert_eq!(Array::identity(3), inverse * a.clone());
+        assert_eq!(g(2 * (3 - 30) - 3 * (12 - 5) + (24 - 1)), a.determinant());
+    }
+
+    #[test]
+    fn solve_complex() {
+        let c = |re:f64, im:f64| Complex::new(re, im);
+        let a = Array::new_mat(vec![
+            vec![c(1.0, 1.0), c(2.0, 0.0)],
+            vec![c(0.0, -1.0), c(3.0, 2.0)],
+        ]);
+        let x = Array::new_mat(vec![vec![c(1.0, -2.0)], vec![c(0.5, 0.5)]]);
+        let b = a.clone() * x.clone();
+        match Array::solve(a, b) {
+            LinearSystemResult::Single(actual) => assert!(x.float_eq(&actual)),
+            LinearSystemResult::Infinite(actual) => panic!("Wrong result: {}, {}", actual.0, actual.1),
+            LinearSystemResult::Inconsistent => panic!("Wrong result: Linear system inconsistent."),
+        }
+    }
+}
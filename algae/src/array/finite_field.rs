@@ -0,0 +1,180 @@
+use crate::array::float_eq::{FloatEq, PivotMagnitude, Tolerance};
+use std::fmt;
+use std::ops::{Add, Sub, Neg, Mul, Div};
+use num::traits::{One, Zero};
+
+// Integers modulo a prime P. P must be prime: GF(P) is only a field then, and inv() relies on
+// Fermat's little theorem. Primality isn't checked, but P < 2 fails to compile.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GfP<const P: u64>(u64);
+
+impl<const P: u64> GfP<P> {
+    // Evaluated wherever an element is created, so GfP<0> and GfP<1> are rejected at compile time.
+    const MODULUS_CHECK: () = assert!(P >= 2, "The modulus of GF(P) must be a prime, so at least 2");
+
+    pub fn new(value:u64) -> Self {
+        let () = Self::MODULUS_CHECK;
+        GfP(value % P)
+    }
+
+    pub fn from_signed(value:i64) -> Self {
+        let () = Self::MODULUS_CHECK;
+        GfP((value as i128).rem_euclid(P as i128) as u64)
+    }
+
+    pub fn value(&self) -> u64 {
+        self.0
+    }
+
+    pub fn pow(self, mut exponent:u64) -> Self {
+        let mut base = self;
+        let mut res = Self::one();
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                res = res * base;
+            }
+            base = base * base;
+            exponent >>= 1;
+        }
+        res
+    }
+
+    // Multiplicative inverse by Fermat's little theorem, None for zero.
+    pub fn inv(self) -> Option<Self> {
+        if self.0 == 0 {
+            None
+        } else {
+            Some(self.pow(P - 2))
+        }
+    }
+}
+
+impl<const P: u64> fmt::Display for GfP<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl<const P: u64> Add for GfP<P> {
+    type Output = Self;
+
+    fn add(self, other:Self) -> Self {
+        GfP(((self.0 as u128 + other.0 as u128) % P as u128) as u64)
+    }
+}
+
+impl<const P: u64> Sub for GfP<P> {
+    type Output = Self;
+
+    fn sub(self, other:Self) -> Self {
+        self + (-other)
+    }
+}
+
+impl<const P: u64> Neg for GfP<P> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        if self.0 == 0 {
+            self
+        } else {
+            GfP(P - self.0)
+        }
+    }
+}
+
+impl<const P: u64> Mul for GfP<P> {
+    type Output = Self;
+
+    fn mul(self, other:Self) -> Self {
+        GfP(((self.0 as u128 * other.0 as u128) % P as u128) as u64)
+    }
+}
+
+impl<const P: u64> Div for GfP<P> {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, other:Self) -> Self {
+        match other.inv() {
+            Some(inverse) => self * inverse,
+            None => panic!("Attempted to divide by zero in GF({})", P),
+        }
+    }
+}
+
+impl<const P: u64> Zero for GfP<P> {
+    fn zero() -> Self {
+        let () = Self::MODULUS_CHECK;
+        GfP(0)
+    }
+
+    fn is_zero(&self) -> bool {
+        self.0 == 0
+    }
+}
+
+impl<const P: u64> One for GfP<P> {
+    fn one() -> Self {
+        let () = Self::MODULUS_CHECK;
+        GfP(1)
+    }
+}
+
+impl<const P: u64> Default for GfP<P> {
+    fn default() -> Self {
+        Self::zero()
+    }
+}
+
+impl<const P: u64> FloatEq for GfP<P> {
+    fn approx_eq_with(&self, other:&Self, _tolerance:Tolerance) -> bool {
+        self == other
+    }
+}
+
+// Every nonzero element is an equally good pivot.
+impl<const P: u64> PivotMagnitude for GfP<P> {
+    fn magnitude(&self) -> f64 {
+        if self.0 == 0 {
+            0.0
+        } else {
+            1.0
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::array::finite_field::GfP;
+    use num::traits::{One, Zero};
+
+    type Gf7 = GfP<7>;
+
+    #[test]
+    fn arithmetic() {
+        assert_eq!(Gf7::new(1), Gf7::new(4) + Gf7::new(4));
+        assert_eq!(Gf7::new(5), Gf7::new(2) - Gf7::new(4));
+        assert_eq!(Gf7::new(6), Gf7::from_signed(-1));
+        assert_eq!(Gf7::new(6), Gf7::new(3) * Gf7::new(2));
+        assert_eq!(Gf7::new(3), Gf7::new(1) / Gf7::new(5));
+        assert_eq!(Gf7::zero(), -Gf7::zero());
+    }
+
+    #[test]
+    fn inverse() {
+        for value in 1..7 {
+            let a = Gf7::new(value);
+            assert_eq!(Gf7::one(), a * a.inv().unwrap());
+        }
+        assert_eq!(None, Gf7::zero().inv());
+    }
+
+    #[test]
+    fn large_modulus_does_not_overflow() {
+        type Gf = GfP<18446744073709551557>;
+        let a = Gf::new(18446744073709551556);
+        assert_eq!(Gf::one(), a * a);
+        assert_eq!(Gf::new(18446744073709551555), a + a);
+    }
+}
@@ -1,9 +1,18 @@
+// Approximate equality, with a tolerance model that the caller may override.
 pub trait FloatEq {
-    fn float_eq(&self, other:&Self) -> bool;
+    fn approx_eq_with(&self, other:&Self, tolerance:Tolerance) -> bool;
+
+    fn standard_tolerance() -> Tolerance {
+        Tolerance::default()
+    }
+
+    fn float_eq(&self, other:&Self) -> bool {
+        self.approx_eq_with(other, Self::standard_tolerance())
+    }
 }
 
-use std::f32::EPSILON as F32EPSILON;
-use std::f64::EPSILON as F64EPSILON;
+use num::{Complex, Integer, Signed, ToPrimitive};
+use num::rational::Ratio;
 
 // As epsilon is the smallest representable number 
 // I might be able to treat it "like" the natural one for floats.
@@ -22,17 +31,268 @@ use std::f64::EPSILON as F64EPSILON;
 // 10^-[ 1110 ] {14} * [ .0010 ] { 0.125 }  = 0.0000000000000012500
 
 // If a shift in the exponenent is equivalent as adding n epsilons this holds.
+// That is only true close to one though, so the default tolerance accepts a difference of
+// THRESHOLD_FACTOR epsilons either absolutely or relative to the larger magnitude.
 
 const THRESHOLD_FACTOR:usize = 5;
 
+// Two values are equal if any of the enabled criteria holds. A criterion is disabled by
+// setting it to zero, so Tolerance::exact() only accepts identical values.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tolerance {
+    // |a - b| <= absolute
+    pub absolute:f64,
+    // |a - b| <= relative * max(|a|, |b|)
+    pub relative:f64,
+    // Number of representable floats between a and b, in the precision of the compared type.
+    pub ulps:u64,
+}
+
+impl Tolerance {
+    pub fn new(absolute:f64, relative:f64, ulps:u64) -> Self {
+        Tolerance {
+            absolute,
+            relative,
+            ulps,
+        }
+    }
+
+    pub fn exact() -> Self {
+        Tolerance::new(0.0, 0.0, 0)
+    }
+
+    pub fn absolute(absolute:f64) -> Self {
+        Tolerance::new(absolute, 0.0, 0)
+    }
+
+    pub fn relative(relative:f64) -> Self {
+        Tolerance::new(0.0, relative, 0)
+    }
+
+    pub fn ulps(ulps:u64) -> Self {
+        Tolerance::new(0.0, 0.0, ulps)
+    }
+
+    pub fn with_absolute(self, absolute:f64) -> Self {
+        Tolerance { absolute, ..self }
+    }
+
+    pub fn with_relative(self, relative:f64) -> Self {
+        Tolerance { relative, ..self }
+    }
+
+    pub fn with_ulps(self, ulps:u64) -> Self {
+        Tolerance { ulps, ..self }
+    }
+
+    // The absolute and relative criteria for a distance between values of the given magnitudes.
+    fn accepts(&self, distance:f64, a:f64, b:f64) -> bool {
+        distance <= self.absolute || distance <= self.relative * f64::max(a.abs(), b.abs())
+    }
+
+    // Whether a value of the given magnitude counts as zero among values up to scale in magnitude.
+    pub fn negligible(&self, magnitude:f64, scale:f64) -> bool {
+        self.accepts(magnitude, magnitude, scale)
+    }
+}
+
+impl Default for Tolerance {
+    fn default() -> Self {
+        let threshold = f64::EPSILON * THRESHOLD_FACTOR as f64;
+        Tolerance::new(threshold, threshold, 0)
+    }
+}
+
+// Maps the bit patterns onto a line, so that neighbouring floats differ by one.
+fn ulp_distance_f32(a:f32, b:f32) -> u64 {
+    let key = |x:f32| {
+        let bits = x.to_bits() as i32;
+        if bits < 0 { i32::MIN as i64 - bits as i64 } else { bits as i64 }
+    };
+    (key(a) - key(b)).unsigned_abs()
+}
+
+fn ulp_distance_f64(a:f64, b:f64) -> u64 {
+    let key = |x:f64| {
+        let bits = x.to_bits() as i64;
+        if bits < 0 { i64::MIN as i128 - bits as i128 } else { bits as i128 }
+    };
+    u64::try_from((key(a) - key(b)).unsigned_abs()).unwrap_or(u64::MAX)
+}
+
 impl FloatEq for f32 {
-    fn float_eq(&self, other:&Self) -> bool {
-        f32::abs(self - other) < F32EPSILON * THRESHOLD_FACTOR as f32
+    fn approx_eq_with(&self, other:&Self, tolerance:Tolerance) -> bool {
+        if self == other {
+            return true;
+        }
+        if self.is_nan() || other.is_nan() {
+            return false;
+        }
+        tolerance.accepts(f32::abs(self - other) as f64, *self as f64, *other as f64)
+            || ulp_distance_f32(*self, *other) <= tolerance.ulps
+    }
+
+    fn standard_tolerance() -> Tolerance {
+        let threshold = (f32::EPSILON * THRESHOLD_FACTOR as f32) as f64;
+        Tolerance::new(threshold, threshold, 0)
     }
 }
 
 impl FloatEq for f64 {
-    fn float_eq(&self, other:&Self) -> bool {
-        f64::abs(self - other) < F64EPSILON * THRESHOLD_FACTOR as f64
+    fn approx_eq_with(&self, other:&Self, tolerance:Tolerance) -> bool {
+        if self == other {
+            return true;
+        }
+        if self.is_nan() || other.is_nan() {
+            return false;
+        }
+        tolerance.accepts(f64::abs(self - other), *self, *other)
+            || ulp_distance_f64(*self, *other) <= tolerance.ulps
+    }
+}
+
+// Rationals are exact, so approximate equality is equality whatever the tolerance.
+impl<T: Clone + Integer> FloatEq for Ratio<T> {
+    fn approx_eq_with(&self, other:&Self, _tolerance:Tolerance) -> bool {
+        self == other
+    }
+}
+
+// The absolute and relative criteria use the modulus, the ULP distance is taken per component.
+impl FloatEq for Complex<f32> {
+    fn approx_eq_with(&self, other:&Self, tolerance:Tolerance) -> bool {
+        if self == other {
+            return true;
+        }
+        if self.is_nan() || other.is_nan() {
+            return false;
+        }
+        tolerance.accepts((self - other).norm() as f64, self.norm() as f64, other.norm() as f64)
+            || (ulp_distance_f32(self.re, other.re) <= tolerance.ulps
+                && ulp_distance_f32(self.im, other.im) <= tolerance.ulps)
+    }
+
+    fn standard_tolerance() -> Tolerance {
+        f32::standard_tolerance()
+    }
+}
+
+impl FloatEq for Complex<f64> {
+    fn approx_eq_with(&self, other:&Self, tolerance:Tolerance) -> bool {
+        if self == other {
+            return true;
+        }
+        if self.is_nan() || other.is_nan() {
+            return false;
+        }
+        tolerance.accepts((self - other).norm(), self.norm(), other.norm())
+            || (ulp_distance_f64(self.re, other.re) <= tolerance.ulps
+                && ulp_distance_f64(self.im, other.im) <= tolerance.ulps)
+    }
+}
+
+// Size of a scalar as seen by pivot selection in echelon_form and determinant.
+// Exact fields keep the default tolerance of zero, so only true zeros are skipped.
+pub trait PivotMagnitude {
+    fn magnitude(&self) -> f64;
+
+    fn default_tolerance() -> f64 {
+        0.0
+    }
+}
+
+impl PivotMagnitude for f32 {
+    fn magnitude(&self) -> f64 {
+        f32::abs(*self) as f64
+    }
+
+    fn default_tolerance() -> f64 {
+        f32::EPSILON as f64
+    }
+}
+
+impl PivotMagnitude for f64 {
+    fn magnitude(&self) -> f64 {
+        f64::abs(*self)
+    }
+
+    fn default_tolerance() -> f64 {
+        f64::EPSILON
+    }
+}
+
+impl<T: Clone + Integer + Signed> PivotMagnitude for Ratio<T> where Ratio<T>: ToPrimitive {
+    fn magnitude(&self) -> f64 {
+        self.abs().to_f64().unwrap_or(f64::INFINITY)
+    }
+}
+
+impl PivotMagnitude for Complex<f32> {
+    fn magnitude(&self) -> f64 {
+        self.norm() as f64
+    }
+
+    fn default_tolerance() -> f64 {
+        f32::EPSILON as f64
+    }
+}
+
+impl PivotMagnitude for Complex<f64> {
+    fn magnitude(&self) -> f64 {
+        self.norm()
+    }
+
+    fn default_tolerance() -> f64 {
+        f64::EPSILON
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::array::array::Array;
+    use crate::array::float_eq::{FloatEq, Tolerance};
+    use num::Complex;
+
+    #[test]
+    fn default_tolerance_scales_with_magnitude() {
+        assert!(1e6.float_eq(&(1e6 + 1e-10)));
+        assert!(!1e6.float_eq(&(1e6 + 1e-6)));
+        assert!(0.0.float_eq(&1e-16));
+        assert!(!0.0.float_eq(&1e-14));
+        assert!(1e6f32.float_eq(&(1e6f32 + 0.25)));
+    }
+
+    #[test]
+    fn absolute_and_relative() {
+        assert!(1.0.approx_eq_with(&1.05, Tolerance::absolute(0.1)));
+        assert!(!100.0.approx_eq_with(&100.5, Tolerance::absolute(0.1)));
+        assert!(100.0.approx_eq_with(&100.5, Tolerance::relative(0.01)));
+        assert!(!0.0.approx_eq_with(&1e-300, Tolerance::relative(0.5)));
+        assert!(0.0.approx_eq_with(&1e-300, Tolerance::relative(0.5).with_absolute(1e-200)));
+        assert!(!1.0.approx_eq_with(&(1.0 + f64::EPSILON), Tolerance::exact()));
+    }
+
+    #[test]
+    fn ulps() {
+        let next = f64::from_bits(1.0f64.to_bits() + 3);
+        assert!(1.0.approx_eq_with(&next, Tolerance::ulps(3)));
+        assert!(!1.0.approx_eq_with(&next, Tolerance::ulps(2)));
+        let below_zero = -f64::from_bits(1);
+        let above_zero = f64::from_bits(1);
+        assert!(below_zero.approx_eq_with(&above_zero, Tolerance::ulps(2)));
+        assert!(!1.0f32.approx_eq_with(&-1.0f32, Tolerance::ulps(1000)));
+        assert!(!f64::NAN.approx_eq_with(&f64::NAN, Tolerance::ulps(u64::MAX)));
+    }
+
+    #[test]
+    fn complex_and_arrays() {
+        let a = Complex::new(3.0, 4.0);
+        assert!(a.approx_eq_with(&Complex::new(3.0, 4.04), Tolerance::relative(0.01)));
+        assert!(!a.approx_eq_with(&Complex::new(3.0, 4.1), Tolerance::relative(0.01)));
+        let x = Array::new_mat(vec![vec![1.0, 1000.0], vec![0.0, -2.0]]);
+        let y = Array::new_mat(vec![vec![1.0, 1000.001], vec![0.0, -2.0]]);
+        assert!(!x.float_eq(&y));
+        assert!(x.approx_eq_with(&y, Tolerance::relative(1e-5)));
+        assert!(!x.view().approx_eq_with(&y.view(), Tolerance::relative(1e-7)));
+    }
+}
@@ -0,0 +1,221 @@
+use crate::array::array::Array;
+use crate::array::view::{ArrayView, ArrayViewMut};
+use crate::error::error::AlgaeError;
+use std::ops::{Add, Mul};
+use num::traits::Zero;
+
+// Cache blocking: a KC x NC panel of b is streamed against MC x KC blocks of a,
+// and every block is computed in MR x NR register tiles.
+const MC:usize = 64;
+const KC:usize = 256;
+const NC:usize = 512;
+const MR:usize = 4;
+const NR:usize = 4;
+
+// c += alpha * a * b, where alpha = None stands for the multiplicative identity.
+// Nothing is allocated, so the kernel can run on sub-blocks of larger arrays.
+pub(in crate::array) fn gemm_kernel<T: Clone + Zero + Add<Output = T> + Mul<Output = T>>
+(alpha:Option<T>, a:ArrayView<T>, b:ArrayView<T>, c:&mut ArrayViewMut<T>) {
+    let (m, k, n) = (a.size.1, a.size.0, b.size.0);
+    for jc in (0..n).step_by(NC) {
+        let nc = usize::min(NC, n - jc);
+        for pc in (0..k).step_by(KC) {
+            let kc = usize::min(KC, k - pc);
+            for ic in (0..m).step_by(MC) {
+                let mc = usize::min(MC, m - ic);
+                for jr in (0..nc).step_by(NR) {
+                    let nr = usize::min(NR, nc - jr);
+                    for ir in (0..mc).step_by(MR) {
+                        let mr = usize::min(MR, mc - ir);
+                        micro_kernel(alpha.as_ref(), &a, &b, c, (ic + ir, pc, jc + jr), (mr, kc, nr));
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[inline(always)]
+fn micro_kernel<T: Clone + Zero + Add<Output = T> + Mul<Output = T>>
+(alpha:Option<&T>, a:&ArrayView<T>, b:&ArrayView<T>, c:&mut ArrayViewMut<T>,
+start:(usize, usize, usize), len:(usize, usize, usize)) {
+    let (row, depth, col) = start;
+    let (mr, kc, nr) = len;
+    let mut acc:[[T; NR]; MR] = std::array::from_fn(|_| std::array::from_fn(|_| T::zero()));
+    let mut a_reg:[T; MR] = std::array::from_fn(|_| T::zero());
+    let mut b_reg:[T; NR] = std::array::from_fn(|_| T::zero());
+    for p in depth..(depth + kc) {
+        for (i, value) in a_reg.iter_mut().enumerate().take(mr) {
+            *value = a.content[a.offset(row + i, p)].clone();
+        }
+        for (j, value) in b_reg.iter_mut().enumerate().take(nr) {
+            *value = b.content[b.offset(p, col + j)].clone();
+        }
+        for i in 0..mr {
+            for j in 0..nr {
+                acc[i][j] = acc[i][j].clone() + a_reg[i].clone() * b_reg[j].clone();
+            }
+        }
+    }
+    for (i, acc_row) in acc.iter().enumerate().take(mr) {
+        for (j, sum) in acc_row.iter().enumerate().take(nr) {
+            let offset = c.offset(row + i, col + j);
+            let value = match alpha {
+                Some(alpha) => alpha.clone() * sum.clone(),
+                None => sum.clone(),
+            };
+            c.content[offset] = c.content[offset].clone() + value;
+        }
+    }
+}
+
+fn check_sizes(a:(usize, usize), b:(usize, usize), c:(usize, usize)) -> Result<(), AlgaeError> {
+    if a.0 != b.1 {
+        return Err(AlgaeError::ShapeMismatch {
+            expected:(b.0, a.0),
+            found:b,
+        });
+    }
+    if c != (b.0, a.1) {
+        return Err(AlgaeError::ShapeMismatch {
+            expected:(b.0, a.1),
+            found:c,
+        });
+    }
+    Ok(())
+}
+
+impl<T: Clone + Zero + Add<Output = T> + Mul<Output = T>> Array<T> {
+    // c = a * b, reusing the buffer of c.
+    pub fn matmul_into(a:&Array<T>, b:&Array<T>, c:&mut Array<T>) {
+        if let Err(e) = check_sizes(a.size, b.size, c.size) {
+            panic!("To multiply two arrays the first width has to be equal to the seconds height. {}", e);
+        }
+        for value in c.content.iter_mut() {
+            *value = T::zero();
+        }
+        gemm_kernel(None, a.view(), b.view(), &mut c.view_mut());
+    }
+
+    // c = alpha * a * b + beta * c
+    pub fn gemm(alpha:T, a:&Array<T>, b:&Array<T>, beta:T, c:&mut Array<T>) {
+        if let Err(e) = Self::try_gemm(alpha, a, b, beta, c) {
+            panic!("To multiply two arrays the first width has to be equal to the seconds height. {}", e);
+        }
+    }
+
+    pub fn try_gemm(alpha:T, a:&Array<T>, b:&Array<T>, beta:T, c:&mut Array<T>) -> Result<(), AlgaeError> {
+        check_sizes(a.size, b.size, c.size)?;
+        for value in c.content.iter_mut() {
+            // As in BLAS, beta == 0 discards c entirely instead of scaling it.
+            *value = if beta.is_zero() { T::zero() } else { beta.clone() * value.clone() };
+        }
+        gemm_kernel(Some(alpha), a.view(), b.view(), &mut c.view_mut());
+        Ok(())
+    }
+}
+
+// The column by column product that was used before the blocked kernel, the tests and the
+// benchmarks compare against it. Cargo compiles benchmarks with cfg(test) as well.
+#[cfg(test)]
+pub(crate) fn matmul_by_columns<T: Copy + Clone + Add<Output = T> + Mul<Output = T>>
+(a:&Array<T>, b:&Array<T>) -> Array<T> {
+    fn multiply_col<T: Copy + Clone + Add<Output = T> + Mul<Output = T>>
+    (a:Array<T>, b:Array<T>) -> Array<T> {
+        (1..a.size.0).fold(a.get_col(0) * b[(0, 0)], |array, i| array + a.get_col(i) * b[(i, 0)])
+    }
+    let mut array = multiply_col(a.clone(), b.get_col(0));
+    for i in 1..b.size.0 {
+        array = Array::concat_0_axis(array.clone(), multiply_col(a.clone(), b.get_col(i)));
+    }
+    array
+}
+
+// Entries uniformly distributed in [-0.5, 0.5), from a linear congruential generator.
+#[cfg(test)]
+pub(crate) fn pseudo_random(size:(usize, usize), seed:u64) -> Array<f64> {
+    let mut state = seed;
+    let mut content = Vec::<Vec<f64>>::with_capacity(size.1);
+    for _ in 0..size.1 {
+        let mut row = Vec::<f64>::with_capacity(size.0);
+        for _ in 0..size.0 {
+            state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            row.push((state >> 33) as f64 / (1u64 << 31) as f64 - 0.5);
+        }
+        content.push(row);
+    }
+    Array::new_mat(content)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::array::gemm::{Array, matmul_by_columns, pseudo_random};
+    use crate::array::float_eq::FloatEq;
+
+    #[test]
+    fn matches_column_product() {
+        let a = Array::new_mat(vec![vec![1, 2, 0], vec![0, 1, 3]]);
+        let b = Array::new_mat(vec![vec![2, 3], vec![1, 0], vec![4, -1]]);
+        assert_eq!(matmul_by_columns(&a, &b), a.clone() * b.clone());
+    }
+
+    #[test]
+    fn matches_column_product_over_tile_edges() {
+        // None of the dimensions is a multiple of the tile sizes.
+        let a = pseudo_random((263, 70), 1);
+        let b = pseudo_random((5, 263), 2);
+        let expected = matmul_by_columns(&a, &b);
+        let actual = a * b;
+        let max_diff = expected.content.iter()
+            .zip(actual.content.iter())
+            .map(|(x, y)| f64::abs(x - y))
+            .fold(0.0, f64::max);
+        assert!(max_diff < 1e-12);
+    }
+
+    #[test]
+    fn matmul_into() {
+        let expected = Array::new_mat(vec![vec![4, 3], vec![1, 0]]);
+        let actual = {
+            let mut temp = Array::new_filled((2, 2), 7);
+            Array::matmul_into(
+                &Array::new_mat(vec![vec![1, 2], vec![0, 1]]),
+                &Array::new_mat(vec![vec![2, 3], vec![1, 0]]),
+                &mut temp,
+            );
+            temp
+        };
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn gemm() {
+        let a = Array::new_mat(vec![vec![1.0, 2.0], vec![0.0, 1.0]]);
+        let b = Array::new_mat(vec![vec![2.0, 3.0], vec![1.0, 0.0]]);
+        let expected = Array::new_mat(vec![vec![9.0, 7.0], vec![3.0, 1.0]]);
+        let actual = {
+            let mut temp = Array::new_filled((2, 2), 0.5);
+            Array::gemm(2.0, &a, &b, 2.0, &mut temp);
+            temp
+        };
+        assert!(expected.float_eq(&actual));
+    }
+
+    #[test]
+    fn gemm_beta_zero_discards_nan() {
+        let a = Array::<f64>::identity(2);
+        let actual = {
+            let mut temp = Array::new_filled((2, 2), f64::NAN);
+            Array::gemm(1.0, &a, &a, 0.0, &mut temp);
+            temp
+        };
+        assert!(a.float_eq(&actual));
+    }
+
+    #[test]
+    fn try_gemm_shape_mismatch() {
+        let a = Array::<f64>::identity(2);
+        let mut c = Array::new_filled((3, 2), 0.0);
+        assert!(Array::try_gemm(1.0, &a, &a, 0.0, &mut c).is_err());
+    }
+}
@@ -0,0 +1,214 @@
+use crate::array::array::Array;
+use crate::array::view::{ArrayView, broadcast_size};
+use crate::error::error::AlgaeError;
+use std::iter::FromIterator;
+
+impl<T> Array<T> {
+    // Owned arrays are always stored row-major, so the buffer can be walked directly.
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.content.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, T> {
+        self.content.iter_mut()
+    }
+
+    // Yields ((row, col), element) in row-major order.
+    pub fn indexed_iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> + '_ {
+        let width = self.size.0;
+        self.content.iter()
+            .enumerate()
+            .map(move |(i, value)| ((i / width, i % width), value))
+    }
+
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = ArrayView<'_, T>> + ExactSizeIterator {
+        (0..self.size.1).map(move |i| self.row(i))
+    }
+
+    pub fn cols(&self) -> impl DoubleEndedIterator<Item = ArrayView<'_, T>> + ExactSizeIterator {
+        (0..self.size.0).map(move |i| self.col(i))
+    }
+}
+
+impl<'a, T> ArrayView<'a, T> {
+    pub fn iter(&self) -> impl Iterator<Item = &'a T> + '_ {
+        self.indexed_iter().map(|(_, value)| value)
+    }
+
+    pub fn indexed_iter(&self) -> impl Iterator<Item = ((usize, usize), &'a T)> + '_ {
+        let (width, height) = self.size;
+        (0..height)
+            .flat_map(move |row| (0..width).map(move |col| (row, col)))
+            .map(move |(row, col)| ((row, col), &self.content[self.offset(row, col)]))
+    }
+}
+
+impl<T: Clone> Array<T> {
+    pub fn map<U, F: FnMut(T) -> U>(&self, mut f:F) -> Array<U> {
+        let content = self.content.iter().map(|a| f(a.clone())).collect();
+        Array::from_content(content, self.size)
+    }
+
+    pub fn map_inplace<F: FnMut(T) -> T>(&mut self, mut f:F) {
+        for a in self.content.iter_mut() {
+            *a = f(a.clone());
+        }
+    }
+
+    // Combines two arrays element by element, broadcasting axes of length one like the arithmetic operators.
+    pub fn zip_with<U: Clone, V, F: FnMut(T, U) -> V>(&self, other:&Array<U>, f:F) -> Array<V> {
+        match self.try_zip_with(other, f) {
+            Ok(array) => array,
+            Err(e) => panic!("To zip two arrays their sizes must be broadcastable. {}", e),
+        }
+    }
+
+    pub fn try_zip_with<U: Clone, V, F: FnMut(T, U) -> V>
+    (&self, other:&Array<U>, mut f:F) -> Result<Array<V>, AlgaeError> {
+        if self.size == other.size {
+            let content = self.content.iter()
+                .zip(other.content.iter())
+                .map(|(a, b)| f(a.clone(), b.clone()))
+                .collect();
+            return Ok(Array::from_content(content, self.size));
+        }
+        let size = broadcast_size(self.size, other.size)?;
+        let (a, b) = (self.view().broadcast_to(size)?, other.view().broadcast_to(size)?);
+        let content = a.iter()
+            .zip(b.iter())
+            .map(|(a, b)| f(a.clone(), b.clone()))
+            .collect();
+        Ok(Array::from_content(content, size))
+    }
+
+    pub fn fold<A, F: FnMut(A, T) -> A>(&self, init:A, mut f:F) -> A {
+        self.content.iter().fold(init, |acc, a| f(acc, a.clone()))
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Array<T> {
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.content.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut Array<T> {
+    type Item = &'a mut T;
+    type IntoIter = std::slice::IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.content.iter_mut()
+    }
+}
+
+// Every item is one row, all rows must have the same length.
+impl<T: Clone> FromIterator<Vec<T>> for Array<T> {
+    fn from_iter<I: IntoIterator<Item = Vec<T>>>(iter:I) -> Self {
+        Array::new_mat(iter.into_iter().collect())
+    }
+}
+
+// Stacks the views on top of each other, e.g. to collect a filtered `rows()` iterator.
+impl<'a, T: Clone> FromIterator<ArrayView<'a, T>> for Array<T> {
+    fn from_iter<I: IntoIterator<Item = ArrayView<'a, T>>>(iter:I) -> Self {
+        let mut content = Vec::<T>::new();
+        let mut size:Option<(usize, usize)> = None;
+        for view in iter {
+            size = match size {
+                None => Some(view.size),
+                Some((width, height)) => {
+                    if width != view.size.0 {
+                        panic!("Invalid array initialisation: rows of a matrix must all have equal lengths. {}",
+                            AlgaeError::ShapeMismatch {
+                                expected:(width, view.size.1),
+                                found:view.size,
+                            });
+                    }
+                    Some((width, height + view.size.1))
+                },
+            };
+            content.extend(view.iter().cloned());
+        }
+        Array::from_content(content, size.unwrap_or((0, 0)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::array::iter::Array;
+
+    #[test]
+    fn map() {
+        let expected = Array::new_mat(vec![vec![1.0, 4.0], vec![9.0, 16.0]]);
+        let actual = Array::new_mat(vec![vec![1, 2], vec![3, 4]]).map(|a| (a * a) as f64);
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn map_inplace() {
+        let expected = Array::new_mat(vec![vec![0, 1], vec![2, 3]]);
+        let mut actual = Array::new_mat(vec![vec![1, 2], vec![3, 4]]);
+        actual.map_inplace(|a| a - 1);
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn zip_with() {
+        let a = Array::new_mat(vec![vec![1, 2], vec![3, 4]]);
+        let expected = Array::new_mat(vec![vec![true, false], vec![true, false]]);
+        let actual = a.zip_with(&Array::new_mat(vec![vec![1, 3]]), |a, b| a % 2 == b % 2 && a <= b + 2);
+        assert_eq!(expected, actual);
+        assert!(a.try_zip_with(&Array::new_filled((3, 1), 0), |a, b| a + b).is_err());
+    }
+
+    #[test]
+    fn fold() {
+        let a = Array::new_mat(vec![vec![1, 2], vec![3, 4]]);
+        assert_eq!(24, a.fold(1, |acc, a| acc * a));
+        assert_eq!(10, a.iter().sum::<i32>());
+    }
+
+    #[test]
+    fn iter_mut() {
+        let expected = Array::new_mat(vec![vec![2, 4], vec![6, 8]]);
+        let mut actual = Array::new_mat(vec![vec![1, 2], vec![3, 4]]);
+        for a in actual.iter_mut() {
+            *a *= 2;
+        }
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn indexed_iter() {
+        let a = Array::new_mat(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+        for ((row, col), value) in a.indexed_iter() {
+            assert_eq!(a[(row, col)], *value);
+        }
+        let expected = vec![((0, 0), &1), ((1, 0), &2), ((2, 0), &3)];
+        let actual = a.t().indexed_iter().filter(|((_, col), _)| *col == 0).collect::<Vec<_>>();
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn rows_and_cols() {
+        let a = Array::new_mat(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+        let row_sums = a.rows().map(|row| row.iter().sum()).collect::<Vec<i32>>();
+        let col_sums = a.cols().map(|col| col.iter().sum()).collect::<Vec<i32>>();
+        assert_eq!(vec![6, 15], row_sums);
+        assert_eq!(vec![5, 7, 9], col_sums);
+        assert_eq!(a.transpose(), a.cols().map(|col| col.iter().copied().collect::<Vec<i32>>()).collect::<Array<i32>>());
+    }
+
+    #[test]
+    fn from_iter() {
+        let a = Array::new_mat(vec![vec![1, 2], vec![3, 4], vec![5, 6]]);
+        let expected = Array::new_mat(vec![vec![1, 2], vec![5, 6]]);
+        let actual = a.rows().filter(|row| row[(0, 0)] != 3).collect::<Array<i32>>();
+        assert_eq!(expected, actual);
+        let actual = (0..2).map(|i| vec![4 * i + 1, 4 * i + 2]).collect::<Array<i32>>();
+        assert_eq!(expected, actual);
+    }
+}
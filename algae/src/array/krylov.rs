@@ -0,0 +1,731 @@
+// Failed solves return the last iterate along with the error, see SolverResult.
+#![allow(clippy::result_large_err)]
+
+use crate::array::array::Array;
+use crate::array::checks::check_square;
+use crate::array::eigenvalues::constant;
+use crate::array::sparse::CsrMatrix;
+use crate::error::error::AlgaeError;
+use std::ops::{Add, Mul};
+use num::traits::{Zero, Float};
+
+// A square operator that is only accessed through products with column vectors,
+// e.g. a dense or sparse matrix or a matrix-free stencil.
+pub trait LinearOperator<T> {
+    // The operator maps vectors of this length onto vectors of the same length.
+    fn dimension(&self) -> usize;
+    fn apply(&self, x:&Array<T>) -> Array<T>;
+
+    // The (width, height) of the operator, the solvers reject it unless it's square.
+    // Operators that are square by construction can keep the default.
+    fn size(&self) -> (usize, usize) {
+        (self.dimension(), self.dimension())
+    }
+}
+
+impl<T: Copy + Clone + Zero + Add<Output = T> + Mul<Output = T>> LinearOperator<T> for Array<T> {
+    fn dimension(&self) -> usize {
+        self.size.1
+    }
+
+    fn size(&self) -> (usize, usize) {
+        self.size
+    }
+
+    fn apply(&self, x:&Array<T>) -> Array<T> {
+        self * x
+    }
+}
+
+// Approximates M^-1 * r for a matrix M close to A that is cheap to invert.
+pub trait Preconditioner<T> {
+    fn apply(&self, r:&Array<T>) -> Array<T>;
+}
+
+pub struct SolverOptions<T> {
+    // The iteration stops once ||b - A * x|| <= tolerance * ||b||.
+    pub tolerance:T,
+    pub iteration_cap:usize,
+}
+
+impl<T: Float> Default for SolverOptions<T> {
+    fn default() -> Self {
+        SolverOptions {
+            tolerance:constant(1e-10),
+            iteration_cap:1000,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConvergenceReport<T> {
+    pub iterations:usize,
+    // The true residual norm ||b - A * x|| of the returned iterate.
+    pub residual_norm:T,
+    // The residual norm estimated by the method after every iteration.
+    pub residual_history:Vec<T>,
+}
+
+impl<T: Float> ConvergenceReport<T> {
+    fn empty() -> Self {
+        ConvergenceReport {
+            iterations:0,
+            residual_norm:T::nan(),
+            residual_history:Vec::new(),
+        }
+    }
+}
+
+// Like `power_method`, a failed solve hands back the last iterate alongside the error.
+pub type SolverError<T> = (AlgaeError, Array<T>, ConvergenceReport<T>);
+pub type SolverResult<T> = Result<(Array<T>, ConvergenceReport<T>), SolverError<T>>;
+
+fn dot<T: Float>(a:&Array<T>, b:&Array<T>) -> T {
+    a.iter().zip(b.iter()).fold(T::zero(), |acc, (x, y)| acc + *x * *y)
+}
+
+// y += alpha * x
+fn axpy<T: Float>(y:&mut Array<T>, alpha:T, x:&Array<T>) {
+    for (a, b) in y.iter_mut().zip(x.iter()) {
+        *a = *a + alpha * *b;
+    }
+}
+
+fn precondition<T: Float>(preconditioner:Option<&dyn Preconditioner<T>>, r:&Array<T>) -> Array<T> {
+    match preconditioner {
+        Some(m) => m.apply(r),
+        None => r.clone(),
+    }
+}
+
+// Checks the sizes and returns the start vector and the initial residual.
+fn start<T: Float>(a:&dyn LinearOperator<T>, b:&Array<T>, x_zero:Option<Array<T>>)
+-> Result<(Array<T>, Array<T>), SolverError<T>> {
+    let n = a.dimension();
+    let x = x_zero.unwrap_or_else(|| Array::new_filled((1, n), T::zero()));
+    if let Err(e) = check_square(a.size()) {
+        return Err((e, x, ConvergenceReport::empty()));
+    }
+    for size in [b.size, x.size] {
+        if size != (1, n) {
+            return Err((AlgaeError::ShapeMismatch {
+                expected:(1, n),
+                found:size,
+            }, x, ConvergenceReport::empty()));
+        }
+    }
+    let r = b - &a.apply(&x);
+    Ok((x, r))
+}
+
+fn finish<T: Float>(a:&dyn LinearOperator<T>, b:&Array<T>, x:Array<T>, iterations:usize, residual_history:Vec<T>)
+-> (Array<T>, ConvergenceReport<T>) {
+    let residual_norm = (b - &a.apply(&x)).norm();
+    (x, ConvergenceReport {
+        iterations,
+        residual_norm,
+        residual_history,
+    })
+}
+
+fn fail<T: Float>(a:&dyn LinearOperator<T>, b:&Array<T>, x:Array<T>, iterations:usize, residual_history:Vec<T>, e:Option<AlgaeError>)
+-> SolverError<T> {
+    let (x, report) = finish(a, b, x, iterations, residual_history);
+    let e = e.unwrap_or(AlgaeError::NoConvergence {
+        iterations,
+        last_estimate:report.residual_norm.to_f64().unwrap_or(f64::NAN),
+    });
+    (e, x, report)
+}
+
+// Conjugate gradients for symmetric positive definite A and M.
+// A direction with p^T * A * p <= 0 shows that A isn't positive definite.
+pub fn conjugate_gradient<T: Float>(a:&dyn LinearOperator<T>, b:&Array<T>, x_zero:Option<Array<T>>,
+preconditioner:Option<&dyn Preconditioner<T>>, options:&SolverOptions<T>) -> SolverResult<T> {
+    let (mut x, mut r) = start(a, b, x_zero)?;
+    let threshold = options.tolerance * b.norm();
+    let mut history = Vec::<T>::with_capacity(options.iteration_cap);
+    let mut z = precondition(preconditioner, &r);
+    let mut p = z.clone();
+    let mut rho = dot(&r, &z);
+    for iteration in 0..options.iteration_cap {
+        if r.norm() <= threshold {
+            return Ok(finish(a, b, x, iteration, history));
+        }
+        let q = a.apply(&p);
+        let curvature = dot(&p, &q);
+        if curvature <= T::zero() || curvature.is_nan() {
+            return Err(fail(a, b, x, iteration, history, Some(AlgaeError::NotPositiveDefinite {
+                index:iteration,
+            })));
+        }
+        let alpha = rho / curvature;
+        axpy(&mut x, alpha, &p);
+        axpy(&mut r, -alpha, &q);
+        history.push(r.norm());
+        z = precondition(preconditioner, &r);
+        let rho_next = dot(&r, &z);
+        p = &z + &(p * (rho_next / rho));
+        rho = rho_next;
+    }
+    if r.norm() <= threshold {
+        return Ok(finish(a, b, x, options.iteration_cap, history));
+    }
+    Err(fail(a, b, x, options.iteration_cap, history, None))
+}
+
+// MINRES for symmetric, possibly indefinite A, following Paige and Saunders.
+// The preconditioner has to be symmetric positive definite.
+pub fn minres<T: Float>(a:&dyn LinearOperator<T>, b:&Array<T>, x_zero:Option<Array<T>>,
+preconditioner:Option<&dyn Preconditioner<T>>, options:&SolverOptions<T>) -> SolverResult<T> {
+    let (mut x, r) = start(a, b, x_zero)?;
+    let mut history = Vec::<T>::with_capacity(options.iteration_cap);
+    // The residual is measured in the M^-1 norm, which is the Euclidean norm without preconditioner.
+    let b_norm = dot(b, &precondition(preconditioner, b)).sqrt();
+    let threshold = options.tolerance * b_norm;
+    let mut y = precondition(preconditioner, &r);
+    let beta_one = dot(&r, &y);
+    if beta_one < T::zero() {
+        return Err(fail(a, b, x, 0, history, Some(AlgaeError::NotPositiveDefinite {
+            index:0,
+        })));
+    }
+    let beta_one = beta_one.sqrt();
+    if beta_one <= threshold {
+        return Ok(finish(a, b, x, 0, history));
+    }
+
+    let n = a.dimension();
+    let mut r1 = r.clone();
+    let mut r2 = r;
+    let (mut old_beta, mut beta) = (T::zero(), beta_one);
+    let (mut d_bar, mut epsilon, mut phi_bar) = (T::zero(), T::zero(), beta_one);
+    let (mut cs, mut sn) = (-T::one(), T::zero());
+    let mut w = Array::new_filled((1, n), T::zero());
+    let mut w2 = w.clone();
+    for iteration in 1..=options.iteration_cap {
+        let v = y * (T::one() / beta);
+        y = a.apply(&v);
+        if iteration >= 2 {
+            axpy(&mut y, -beta / old_beta, &r1);
+        }
+        let alpha = dot(&v, &y);
+        axpy(&mut y, -alpha / beta, &r2);
+        r1 = r2;
+        r2 = y;
+        y = precondition(preconditioner, &r2);
+        old_beta = beta;
+        beta = dot(&r2, &y);
+        if beta < T::zero() {
+            return Err(fail(a, b, x, iteration, history, Some(AlgaeError::NotPositiveDefinite {
+                index:iteration,
+            })));
+        }
+        beta = beta.sqrt();
+
+        // Applies the previous rotation and computes the next one.
+        let old_epsilon = epsilon;
+        let delta = cs * d_bar + sn * alpha;
+        let g_bar = sn * d_bar - cs * alpha;
+        epsilon = sn * beta;
+        d_bar = -cs * beta;
+        let gamma = g_bar.hypot(beta).max(T::epsilon());
+        cs = g_bar / gamma;
+        sn = beta / gamma;
+        let phi = cs * phi_bar;
+        phi_bar = sn * phi_bar;
+
+        let w1 = w2;
+        w2 = w;
+        w = v;
+        axpy(&mut w, -old_epsilon, &w1);
+        axpy(&mut w, -delta, &w2);
+        w *= T::one() / gamma;
+        axpy(&mut x, phi, &w);
+        history.push(phi_bar);
+        if phi_bar <= threshold || beta == T::zero() {
+            return Ok(finish(a, b, x, iteration, history));
+        }
+    }
+    Err(fail(a, b, x, options.iteration_cap, history, None))
+}
+
+// Restarted GMRES(m) with right preconditioning, so the monitored residual is the true one.
+pub fn gmres<T: Float>(a:&dyn LinearOperator<T>, b:&Array<T>, x_zero:Option<Array<T>>, restart:usize,
+preconditioner:Option<&dyn Preconditioner<T>>, options:&SolverOptions<T>) -> SolverResult<T> {
+    let (mut x, mut r) = start(a, b, x_zero)?;
+    let m = restart.max(1);
+    let threshold = options.tolerance * b.norm();
+    let mut history = Vec::<T>::with_capacity(options.iteration_cap);
+    let mut iterations = 0;
+    loop {
+        let beta = r.norm();
+        if beta <= threshold {
+            return Ok(finish(a, b, x, iterations, history));
+        }
+        if iterations >= options.iteration_cap {
+            return Err(fail(a, b, x, iterations, history, None));
+        }
+
+        let mut basis = Vec::<Array<T>>::with_capacity(m + 1);
+        basis.push(r * (T::one() / beta));
+        let mut h = vec![vec![T::zero(); m]; m + 1];
+        let mut rotations = Vec::<(T, T)>::with_capacity(m);
+        let mut g = vec![T::zero(); m + 1];
+        g[0] = beta;
+        let mut steps = 0;
+        while steps < m && iterations < options.iteration_cap {
+            let j = steps;
+            let mut w = a.apply(&precondition(preconditioner, &basis[j]));
+            for (i, v) in basis.iter().enumerate() {
+                h[i][j] = dot(&w, v);
+                axpy(&mut w, -h[i][j], v);
+            }
+            h[j + 1][j] = w.norm();
+            for (i, (c, s)) in rotations.iter().enumerate() {
+                let (upper, lower) = (h[i][j], h[i + 1][j]);
+                h[i][j] = *c * upper + *s * lower;
+                h[i + 1][j] = -*s * upper + *c * lower;
+            }
+            let radius = h[j][j].hypot(h[j + 1][j]);
+            let (c, s) = if radius == T::zero() { (T::one(), T::zero()) } else { (h[j][j] / radius, h[j + 1][j] / radius) };
+            let next_norm = w.norm();
+            h[j][j] = radius;
+            h[j + 1][j] = T::zero();
+            g[j + 1] = -s * g[j];
+            g[j] = c * g[j];
+            rotations.push((c, s));
+            steps += 1;
+            iterations += 1;
+            history.push(g[j + 1].abs());
+            if g[j + 1].abs() <= threshold || next_norm == T::zero() {
+                break;
+            }
+            basis.push(w * (T::one() / next_norm));
+        }
+
+        // Back substitution with the triangular H and the update x += M^-1 * V * y.
+        let mut y = vec![T::zero(); steps];
+        for i in (0..steps).rev() {
+            let sum = ((i + 1)..steps).fold(g[i], |acc, k| acc - h[i][k] * y[k]);
+            y[i] = sum / h[i][i];
+        }
+        let mut update = Array::new_filled((1, a.dimension()), T::zero());
+        for (v, coefficient) in basis.iter().zip(y.iter()) {
+            axpy(&mut update, *coefficient, v);
+        }
+        axpy(&mut x, T::one(), &precondition(preconditioner, &update));
+        r = b - &a.apply(&x);
+    }
+}
+
+// BiCGSTAB with right preconditioning for general A. A vanishing ρ or ω is a breakdown of the
+// method and reported as NoConvergence.
+pub fn bicgstab<T: Float>(a:&dyn LinearOperator<T>, b:&Array<T>, x_zero:Option<Array<T>>,
+preconditioner:Option<&dyn Preconditioner<T>>, options:&SolverOptions<T>) -> SolverResult<T> {
+    let (mut x, mut r) = start(a, b, x_zero)?;
+    let threshold = options.tolerance * b.norm();
+    let mut history = Vec::<T>::with_capacity(options.iteration_cap);
+    if r.norm() <= threshold {
+        return Ok(finish(a, b, x, 0, history));
+    }
+    let r_hat = r.clone();
+    let (mut rho, mut alpha, mut omega) = (T::one(), T::one(), T::one());
+    let mut v = Array::new_filled(r.size, T::zero());
+    let mut p = v.clone();
+    for iteration in 1..=options.iteration_cap {
+        let rho_next = dot(&r_hat, &r);
+        if rho_next == T::zero() || omega == T::zero() {
+            return Err(fail(a, b, x, iteration - 1, history, None));
+        }
+        let beta = (rho_next / rho) * (alpha / omega);
+        axpy(&mut p, -omega, &v);
+        p = &r + &(p * beta);
+        let p_hat = precondition(preconditioner, &p);
+        v = a.apply(&p_hat);
+        alpha = rho_next / dot(&r_hat, &v);
+        let mut s = r;
+        axpy(&mut s, -alpha, &v);
+        axpy(&mut x, alpha, &p_hat);
+        if s.norm() <= threshold {
+            history.push(s.norm());
+            return Ok(finish(a, b, x, iteration, history));
+        }
+        let s_hat = precondition(preconditioner, &s);
+        let t = a.apply(&s_hat);
+        omega = dot(&t, &s) / dot(&t, &t);
+        axpy(&mut x, omega, &s_hat);
+        r = s;
+        axpy(&mut r, -omega, &t);
+        history.push(r.norm());
+        if r.norm() <= threshold {
+            return Ok(finish(a, b, x, iteration, history));
+        }
+        rho = rho_next;
+    }
+    Err(fail(a, b, x, options.iteration_cap, history, None))
+}
+
+// M = diag(A)
+pub struct Jacobi<T> {
+    inverse_diagonal:Vec<T>,
+}
+
+impl<T: Float> Jacobi<T> {
+    pub fn new(a:&Array<T>) -> Result<Self, AlgaeError> {
+        let n = check_square(a.size)?;
+        let diagonal = (0..n).map(|i| a[(i, i)]).collect::<Vec<T>>();
+        Jacobi::from_diagonal(&diagonal)
+    }
+
+    // For operators that aren't dense arrays, e.g. with CsrMatrix::diagonal.
+    pub fn from_diagonal(diagonal:&[T]) -> Result<Self, AlgaeError> {
+        let mut inverse_diagonal = Vec::<T>::with_capacity(diagonal.len());
+        for (i, d) in diagonal.iter().enumerate() {
+            if *d == T::zero() {
+                return Err(AlgaeError::ZeroPivot {
+                    index:i,
+                });
+            }
+            inverse_diagonal.push(T::one() / *d);
+        }
+        Ok(Jacobi {
+            inverse_diagonal,
+        })
+    }
+}
+
+impl<T: Float> Preconditioner<T> for Jacobi<T> {
+    fn apply(&self, r:&Array<T>) -> Array<T> {
+        let mut z = r.clone();
+        for (a, d) in z.iter_mut().zip(self.inverse_diagonal.iter()) {
+            *a = *a * *d;
+        }
+        z
+    }
+}
+
+// Symmetric successive over-relaxation,
+// M = ω / (2 - ω) * (D / ω + L) * (D / ω)^-1 * (D / ω + U) with 0 < ω < 2.
+// Only the nonzero entries of A are kept, so dense and sparse matrices cost the same per entry.
+pub struct Ssor<T> {
+    a:CsrMatrix<T>,
+    diagonal:Vec<T>,
+    omega:T,
+}
+
+impl<T: Float> Ssor<T> {
+    pub fn new(a:&Array<T>, omega:T) -> Result<Self, AlgaeError> {
+        Ssor::from_csr(&CsrMatrix::from_array(a), omega)
+    }
+
+    pub fn from_csr(a:&CsrMatrix<T>, omega:T) -> Result<Self, AlgaeError> {
+        if omega <= T::zero() || omega >= constant(2.0) {
+            return Err(AlgaeError::InvalidParameter {
+                name:"omega",
+                value:omega.to_f64().unwrap_or(f64::NAN),
+            });
+        }
+        check_square(a.size)?;
+        let diagonal = a.diagonal();
+        if let Some(i) = diagonal.iter().position(|d| *d == T::zero()) {
+            return Err(AlgaeError::ZeroPivot {
+                index:i,
+            });
+        }
+        Ok(Ssor {
+            a:a.clone(),
+            diagonal,
+            omega,
+        })
+    }
+}
+
+impl<T: Float> Preconditioner<T> for Ssor<T> {
+    fn apply(&self, r:&Array<T>) -> Array<T> {
+        let n = self.diagonal.len();
+        let mut z = vec![T::zero(); n];
+        for i in 0..n {
+            let sum = self.a.row(i)
+                .take_while(|(j, _)| *j < i)
+                .fold(r[(i, 0)], |acc, (j, value)| acc - value * z[j]);
+            z[i] = sum * self.omega / self.diagonal[i];
+        }
+        for (value, d) in z.iter_mut().zip(self.diagonal.iter()) {
+            *value = *value * *d / self.omega;
+        }
+        for i in (0..n).rev() {
+            let sum = self.a.row(i)
+                .filter(|(j, _)| *j > i)
+                .fold(z[i], |acc, (j, value)| acc - value * z[j]);
+            z[i] = sum * self.omega / self.diagonal[i];
+        }
+        let scale = (constant::<T>(2.0) - self.omega) / self.omega;
+        Array::new_vec(z.iter().map(|value| *value * scale).collect())
+    }
+}
+
+// Incomplete LU factorisation without fill-in, L and U keep the sparsity pattern of A.
+// They're stored together in that pattern, L with an implicit unit diagonal.
+pub struct Ilu0<T> {
+    lu:CsrMatrix<T>,
+    diagonal:Vec<T>,
+}
+
+impl<T: Float> Ilu0<T> {
+    pub fn new(a:&Array<T>) -> Result<Self, AlgaeError> {
+        Ilu0::from_csr(&CsrMatrix::from_array(a))
+    }
+
+    pub fn from_csr(a:&CsrMatrix<T>) -> Result<Self, AlgaeError> {
+        let n = check_square(a.size)?;
+        let (indptr, indices) = (a.indptr(), a.indices());
+        let mut values = a.values().to_vec();
+        // Position of the diagonal entry of every row, if it's part of the pattern.
+        let mut diagonal_at = vec![None; n];
+        // Position of every column within the current row.
+        let mut position = vec![None; n];
+        for i in 0..n {
+            for p in indptr[i]..indptr[i + 1] {
+                position[indices[p]] = Some(p);
+            }
+            for p in indptr[i]..indptr[i + 1] {
+                let k = indices[p];
+                if k >= i {
+                    break;
+                }
+                if values[p] == T::zero() {
+                    continue;
+                }
+                let pivot = match diagonal_at[k] {
+                    Some(d) => values[d],
+                    None => T::zero(),
+                };
+                if pivot == T::zero() {
+                    return Err(AlgaeError::ZeroPivot {
+                        index:k,
+                    });
+                }
+                let factor = values[p] / pivot;
+                values[p] = factor;
+                for q in indptr[k]..indptr[k + 1] {
+                    if indices[q] > k {
+                        if let Some(target) = position[indices[q]] {
+                            values[target] = values[target] - factor * values[q];
+                        }
+                    }
+                }
+            }
+            diagonal_at[i] = position[i];
+            if diagonal_at[i].is_none_or(|d| values[d] == T::zero()) {
+                return Err(AlgaeError::ZeroPivot {
+                    index:i,
+                });
+            }
+            for p in indptr[i]..indptr[i + 1] {
+                position[indices[p]] = None;
+            }
+        }
+        let diagonal = diagonal_at.iter().map(|d| d.map_or(T::zero(), |d| values[d])).collect();
+        let lu = CsrMatrix::new(a.size, indptr.to_vec(), indices.to_vec(), values)
+            .unwrap_or_else(|e| panic!("Faulty implementation: the pattern of A is valid. {}", e));
+        Ok(Ilu0 {
+            lu,
+            diagonal,
+        })
+    }
+}
+
+impl<T: Float> Preconditioner<T> for Ilu0<T> {
+    fn apply(&self, r:&Array<T>) -> Array<T> {
+        let n = self.diagonal.len();
+        let mut z = vec![T::zero(); n];
+        for i in 0..n {
+            z[i] = self.lu.row(i)
+                .take_while(|(j, _)| *j < i)
+                .fold(r[(i, 0)], |acc, (j, value)| acc - value * z[j]);
+        }
+        for i in (0..n).rev() {
+            let sum = self.lu.row(i)
+                .filter(|(j, _)| *j > i)
+                .fold(z[i], |acc, (j, value)| acc - value * z[j]);
+            z[i] = sum / self.diagonal[i];
+        }
+        Array::new_vec(z)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::array::krylov::{Array, LinearOperator, Preconditioner, SolverOptions, Jacobi, Ssor, Ilu0};
+    use crate::array::krylov::{conjugate_gradient, minres, gmres, bicgstab};
+    use crate::array::sparse::CsrMatrix;
+    use crate::error::error::AlgaeError;
+
+    // The 2D Laplacian on a k x k grid, symmetric positive definite.
+    fn poisson(k:usize) -> Array<f64> {
+        let n = k * k;
+        let mut a = Array::new_filled((n, n), 0.0);
+        for i in 0..n {
+            a[(i, i)] = 4.0;
+            if i % k != 0 {
+                a[(i, i - 1)] = -1.0;
+                a[(i - 1, i)] = -1.0;
+            }
+            if i >= k {
+                a[(i, i - k)] = -1.0;
+                a[(i - k, i)] = -1.0;
+            }
+        }
+        a
+    }
+
+    // Convection-diffusion, diagonally dominant but not symmetric.
+    fn convection(n:usize) -> Array<f64> {
+        let mut a = Array::new_filled((n, n), 0.0);
+        for i in 0..n {
+            a[(i, i)] = 3.0;
+            if i > 0 {
+                a[(i, i - 1)] = -1.5;
+            }
+            if i + 1 < n {
+                a[(i, i + 1)] = -0.5;
+            }
+        }
+        a
+    }
+
+    fn rhs(n:usize) -> Array<f64> {
+        Array::new_vec((0..n).map(|i| f64::sin(i as f64) + 1.0).collect())
+    }
+
+    fn assert_solves(a:&Array<f64>, x:&Array<f64>, b:&Array<f64>) {
+        assert!((b - &a.apply(x)).norm() <= 1e-9 * b.norm());
+    }
+
+    #[test]
+    fn conjugate_gradient_with_and_without_preconditioner() {
+        let a = poisson(8);
+        let b = rhs(64);
+        let options = SolverOptions::default();
+        let (x, plain) = conjugate_gradient(&a, &b, None, None, &options).unwrap();
+        assert_solves(&a, &x, &b);
+        let ssor = Ssor::new(&a, 1.5).unwrap();
+        let (x, preconditioned) = conjugate_gradient(&a, &b, None, Some(&ssor), &options).unwrap();
+        assert_solves(&a, &x, &b);
+        assert!(preconditioned.iterations < plain.iterations);
+        assert_eq!(plain.iterations, plain.residual_history.len());
+    }
+
+    #[test]
+    fn conjugate_gradient_indefinite() {
+        let a = Array::new_mat(vec![vec![1.0, 0.0], vec![0.0, -1.0]]);
+        let b = Array::new_vec(vec![0.0, 1.0]);
+        match conjugate_gradient(&a, &b, None, None, &SolverOptions::default()) {
+            Ok((x, _)) => panic!("Wrong result: {}", x),
+            Err((e, _, _)) => assert!(matches!(e, AlgaeError::NotPositiveDefinite { .. })),
+        }
+    }
+
+    #[test]
+    fn minres_indefinite() {
+        let mut a = poisson(6);
+        for i in 0..36 {
+            a[(i, i)] -= 2.5;
+        }
+        let b = rhs(36);
+        let (x, _) = minres(&a, &b, None, None, &SolverOptions::default()).unwrap();
+        assert_solves(&a, &x, &b);
+        let jacobi = Jacobi::new(&poisson(6)).unwrap();
+        let (x, _) = minres(&poisson(6), &b, None, Some(&jacobi), &SolverOptions::default()).unwrap();
+        assert_solves(&poisson(6), &x, &b);
+    }
+
+    #[test]
+    fn gmres_nonsymmetric() {
+        let a = convection(50);
+        let b = rhs(50);
+        let options = SolverOptions::default();
+        let (x, plain) = gmres(&a, &b, None, 10, None, &options).unwrap();
+        assert_solves(&a, &x, &b);
+        let ilu = Ilu0::new(&a).unwrap();
+        let (x, preconditioned) = gmres(&a, &b, None, 10, Some(&ilu), &options).unwrap();
+        assert_solves(&a, &x, &b);
+        assert!(preconditioned.iterations < plain.iterations);
+    }
+
+    #[test]
+    fn bicgstab_nonsymmetric() {
+        let a = convection(50);
+        let b = rhs(50);
+        let (x, _) = bicgstab(&a, &b, None, None, &SolverOptions::default()).unwrap();
+        assert_solves(&a, &x, &b);
+        let jacobi = Jacobi::new(&a).unwrap();
+        let (x, _) = bicgstab(&a, &b, Some(x), Some(&jacobi), &SolverOptions::default()).unwrap();
+        assert_solves(&a, &x, &b);
+    }
+
+    #[test]
+    fn ilu0_of_tridiagonal_is_exact() {
+        // Tridiagonal matrices have no fill-in, so ILU(0) is the complete LU factorisation.
+        let a = convection(10);
+        let b = rhs(10);
+        let x = Ilu0::new(&a).unwrap().apply(&b);
+        assert_solves(&a, &x, &b);
+    }
+
+    #[test]
+    fn preconditioners_from_sparse_matrices() {
+        let a = poisson(6);
+        let sparse = CsrMatrix::from_array(&a);
+        let b = rhs(36);
+        let options = SolverOptions::default();
+        let dense_ssor = Ssor::new(&a, 1.2).unwrap().apply(&b);
+        let ssor = Ssor::from_csr(&sparse, 1.2).unwrap();
+        assert!((&dense_ssor - &ssor.apply(&b)).norm() < 1e-12);
+        let (x, _) = conjugate_gradient(&sparse, &b, None, Some(&ssor), &options).unwrap();
+        assert_solves(&a, &x, &b);
+
+        let dense_ilu = Ilu0::new(&a).unwrap().apply(&b);
+        let ilu = Ilu0::from_csr(&sparse).unwrap();
+        assert!((&dense_ilu - &ilu.apply(&b)).norm() < 1e-12);
+        let (x, _) = gmres(&sparse, &b, None, 10, Some(&ilu), &options).unwrap();
+        assert_solves(&a, &x, &b);
+
+        let missing_diagonal = CsrMatrix::from_array(&Array::new_mat(vec![vec![0.0, 1.0], vec![1.0, 0.0]]));
+        assert!(matches!(Ilu0::from_csr(&missing_diagonal), Err(AlgaeError::ZeroPivot { index:0 })));
+    }
+
+    #[test]
+    fn invalid_arguments() {
+        let a = poisson(3);
+        assert!(matches!(Ssor::new(&a, 2.0), Err(AlgaeError::InvalidParameter { name:"omega", .. })));
+        assert!(matches!(Ssor::new(&a, 0.0), Err(AlgaeError::InvalidParameter { .. })));
+        let rectangular = Array::new_filled((3, 2), 1.0);
+        let b = Array::new_vec(vec![1.0, 1.0]);
+        assert!(matches!(gmres(&rectangular, &b, None, 2, None, &SolverOptions::default()),
+            Err((AlgaeError::NotSquare { size:(3, 2) }, _, _))));
+        assert!(matches!(Jacobi::new(&rectangular), Err(AlgaeError::NotSquare { .. })));
+    }
+
+    #[test]
+    fn no_convergence_returns_last_iterate() {
+        let a = poisson(8);
+        let b = rhs(64);
+        let options = SolverOptions {
+            tolerance:1e-12,
+            iteration_cap:3,
+        };
+        match gmres(&a, &b, None, 20, None, &options) {
+            Ok((x, _)) => panic!("Wrong result: {}", x),
+            Err((e, x, report)) => {
+                assert!(matches!(e, AlgaeError::NoConvergence { iterations:3, .. }));
+                assert_eq!(3, report.iterations);
+                assert!(report.residual_norm < b.norm());
+                assert!(f64::abs((&b - &a.apply(&x)).norm() - report.residual_norm) < 1e-12);
+            },
+        }
+        let wrong_size = Array::new_vec(vec![1.0, 2.0]);
+        assert!(matches!(bicgstab(&a, &wrong_size, None, None, &options), Err((AlgaeError::ShapeMismatch { .. }, _, _))));
+    }
+}
@@ -0,0 +1,510 @@
+use crate::array::array::Array;
+use crate::array::checks::check_square;
+use crate::array::eigenvalues::{constant, real_schur, RealSchur};
+use crate::array::factorizations::PluFactorization;
+use crate::array::float_eq::FloatEq;
+use crate::error::error::AlgaeError;
+use num::traits::Float;
+use num::Complex;
+
+const MAX_ITERATIONS:usize = 100;
+const MAX_SQUARE_ROOTS:usize = 64;
+
+// Coefficients of the diagonal Padé approximants to exp, together with the largest
+// 1-norm for which each one is accurate to double precision (Higham, 2005).
+const PADE_3:[f64; 4] = [120.0, 60.0, 12.0, 1.0];
+const PADE_5:[f64; 6] = [30240.0, 15120.0, 3360.0, 420.0, 30.0, 1.0];
+const PADE_7:[f64; 8] = [17297280.0, 8648640.0, 1995840.0, 277200.0, 25200.0, 1512.0, 56.0, 1.0];
+const PADE_9:[f64; 10] = [
+    17643225600.0, 8821612800.0, 2075673600.0, 302702400.0, 30270240.0,
+    2162160.0, 110880.0, 3960.0, 90.0, 1.0,
+];
+const PADE_13:[f64; 14] = [
+    64764752532480000.0, 32382376266240000.0, 7771770303897600.0, 1187353796428800.0,
+    129060195264000.0, 10559470521600.0, 670442572800.0, 33522128640.0,
+    1323241920.0, 40840800.0, 960960.0, 16380.0, 182.0, 1.0,
+];
+const THETA:[(f64, &[f64]); 4] = [
+    (1.495585217958292e-2, &PADE_3),
+    (2.53939833006323e-1, &PADE_5),
+    (9.504178996162932e-1, &PADE_7),
+    (2.097847961257068e0, &PADE_9),
+];
+const THETA_13:f64 = 5.371920351148152;
+
+// The largest absolute column sum.
+fn norm_one<T: Float>(a:&Array<T>) -> T {
+    (0..a.size.0)
+        .map(|col| (0..a.size.1).fold(T::zero(), |acc, row| acc + a[(row, col)].abs()))
+        .fold(T::zero(), T::max)
+}
+
+// Eigenvalues of this magnitude are treated as zero.
+fn schur_tolerance<T: Float>(a:&Array<T>) -> T {
+    constant::<T>(a.size.0 as f64) * T::epsilon() * norm_one(a)
+}
+
+// Rejects negative real eigenvalues with NotReal, and zero ones with Singular if they're
+// not allowed. Complex pairs never lie on the real axis.
+fn check_real_eigenvalues<T: Float>(schur:&RealSchur<T>, tolerance:T, nonsingular:bool) -> Result<(), AlgaeError> {
+    for &(start, size) in schur.blocks.iter() {
+        if size == 1 {
+            let value = schur.t[(start, start)];
+            if value < -tolerance {
+                return Err(AlgaeError::NotReal);
+            }
+            if nonsingular && value <= tolerance {
+                return Err(AlgaeError::Singular);
+            }
+        }
+    }
+    Ok(())
+}
+
+// Principal square root of a quasi upper triangular matrix with the given diagonal blocks.
+// The result has the same block structure.
+fn quasi_triangular_sqrt<T: Float>(t:&Array<T>, blocks:&[(usize, usize)], tolerance:T) -> Result<Array<T>, AlgaeError> {
+    let n = t.size.0;
+    let two = constant::<T>(2.0);
+    let mut r = Array::new_filled((n, n), T::zero());
+    for (j, &(col, width)) in blocks.iter().enumerate() {
+        if width == 1 {
+            r[(col, col)] = t[(col, col)].max(T::zero()).sqrt();
+        } else {
+            // The eigenvalues theta ± i * mu have the root alpha + i * beta with
+            // alpha = sqrt((|lambda| + theta) / 2), and R = alpha * I + (T - theta * I) / (2 * alpha).
+            let (a, b, c, d) = (t[(col, col)], t[(col, col + 1)], t[(col + 1, col)], t[(col + 1, col + 1)]);
+            let theta = (a + d) / two;
+            let modulus = (a * d - b * c).max(T::zero()).sqrt();
+            let alpha = ((modulus + theta) / two).max(T::zero()).sqrt();
+            r[(col, col)] = alpha + (a - theta) / (two * alpha);
+            r[(col, col + 1)] = b / (two * alpha);
+            r[(col + 1, col)] = c / (two * alpha);
+            r[(col + 1, col + 1)] = alpha + (d - theta) / (two * alpha);
+        }
+        for &(row, height) in blocks[..j].iter().rev() {
+            // R_ii * X + X * R_jj = T_ij - sum over the blocks k in between of R_ik * R_kj.
+            let mut rhs = vec![T::zero(); height * width];
+            for a in 0..height {
+                for b in 0..width {
+                    let between = ((row + height)..col).fold(T::zero(), |acc, k| acc + r[(row + a, k)] * r[(k, col + b)]);
+                    rhs[a * width + b] = t[(row + a, col + b)] - between;
+                }
+            }
+            let x = solve_sylvester(&r, (row, height), (col, width), rhs, tolerance)?;
+            for a in 0..height {
+                for b in 0..width {
+                    r[(row + a, col + b)] = x[a * width + b];
+                }
+            }
+        }
+    }
+    Ok(r)
+}
+
+// Denman-Beavers iteration Y <- (Y + Z^-1) / 2, Z <- (Z + Y^-1) / 2 from Y = T and Z = I, which
+// converges quadratically to sqrt(T) and its inverse. Both iterates are scaled by
+// |det(Y) * det(Z)|^(-1/(2n)) first (Higham, 2008, section 6.3), the scale tends to one as the
+// iteration converges. Since the error squares with every step, the change of Y bounds the
+// error left after it by about its square.
+fn denman_beavers<T: Float + FloatEq>(t:&Array<T>) -> Result<Array<T>, AlgaeError> {
+    let n = t.size.0;
+    let (half, tolerance) = (constant::<T>(0.5), (constant::<T>(n as f64) * T::epsilon()).sqrt());
+    let mut y = t.clone();
+    let mut z = Array::identity(n);
+    let mut change = T::infinity();
+    for _ in 0..MAX_ITERATIONS {
+        let (y_plu, z_plu) = (PluFactorization::new(y.clone())?, PluFactorization::new(z.clone())?);
+        let scale = (-(y_plu.log_det().1 + z_plu.log_det().1) / constant(2.0 * n as f64)).exp();
+        let next_y = &(&y * scale + &z_plu.inverse()? / scale) * half;
+        z = &(&z * scale + &y_plu.inverse()? / scale) * half;
+        change = (&next_y - &y).norm();
+        y = next_y;
+        if change <= tolerance * y.norm() {
+            return Ok(y);
+        }
+    }
+    Err(AlgaeError::NoConvergence {
+        iterations:MAX_ITERATIONS,
+        last_estimate:change.to_f64().unwrap_or(f64::NAN),
+    })
+}
+
+// Solves R_ii * X + X * R_jj = rhs for the diagonal blocks (first index, size) of r as a Kronecker
+// system of at most four unknowns, X is stored row by row. A singular system only has a
+// solution, taken to be X = 0, if the right hand side vanishes.
+fn solve_sylvester<T: Float>(r:&Array<T>, (row, height):(usize, usize), (col, width):(usize, usize), rhs:Vec<T>, tolerance:T)
+-> Result<Vec<T>, AlgaeError> {
+    let len = height * width;
+    let mut m = vec![vec![T::zero(); len + 1]; len];
+    for a in 0..height {
+        for b in 0..width {
+            let eq = a * width + b;
+            for c in 0..height {
+                m[eq][c * width + b] = m[eq][c * width + b] + r[(row + a, row + c)];
+            }
+            for d in 0..width {
+                m[eq][a * width + d] = m[eq][a * width + d] + r[(col + d, col + b)];
+            }
+            m[eq][len] = rhs[eq];
+        }
+    }
+    let singular = || {
+        if rhs.iter().all(|value| value.abs() <= tolerance) {
+            Ok(vec![T::zero(); len])
+        } else {
+            Err(AlgaeError::Singular)
+        }
+    };
+    for k in 0..len {
+        let pivot = (k..len).fold(k, |best, i| if m[i][k].abs() > m[best][k].abs() { i } else { best });
+        if m[pivot][k].abs() <= tolerance {
+            return singular();
+        }
+        m.swap(k, pivot);
+        let (upper, lower) = m.split_at_mut(k + 1);
+        for row in lower.iter_mut() {
+            let factor = row[k] / upper[k][k];
+            for (x, y) in row.iter_mut().zip(upper[k].iter()).skip(k) {
+                *x = *x - factor * *y;
+            }
+        }
+    }
+    let mut x = vec![T::zero(); len];
+    for k in (0..len).rev() {
+        let sum = ((k + 1)..len).fold(m[k][len], |acc, j| acc - m[k][j] * x[j]);
+        x[k] = sum / m[k][k];
+    }
+    Ok(x)
+}
+
+// Returns (U, V) with r(A) = (V - U)^-1 * (V + U), where U holds the odd and V the even powers.
+fn pade<T: Float>(a:&Array<T>, coefficients:&[f64]) -> (Array<T>, Array<T>) {
+    let n = a.size.0;
+    let a_squared = a * a;
+    let mut power = Array::identity(n);
+    let mut odd = Array::new_filled(a.size, T::zero());
+    let mut even = Array::new_filled(a.size, T::zero());
+    for pair in coefficients.chunks(2) {
+        even += &(&power * constant::<T>(pair[0]));
+        if pair.len() > 1 {
+            odd += &(&power * constant::<T>(pair[1]));
+        }
+        power = &power * &a_squared;
+    }
+    (a * &odd, even)
+}
+
+// Gauss-Jordan elimination with partial pivoting on complex matrices, used to invert
+// eigenvector matrices. Nearly dependent eigenvectors are reported as Singular.
+fn complex_inverse<T: Float>(a:&Array<Complex<T>>) -> Result<Array<Complex<T>>, AlgaeError> {
+    let n = a.size.0;
+    let mut m = a.clone();
+    let mut inverse = Array::identity(n);
+    let scale = m.fold(T::zero(), |acc, z| acc.max(z.norm()));
+    let tolerance = T::epsilon() * constant(n as f64) * scale;
+    for k in 0..n {
+        let pivot_row = (k..n).fold(k, |best, row| if m[(row, k)].norm() > m[(best, k)].norm() { row } else { best });
+        if m[(pivot_row, k)].norm() <= tolerance {
+            return Err(AlgaeError::Singular);
+        }
+        m.swap_rows(k, pivot_row);
+        inverse.swap_rows(k, pivot_row);
+        let factor = m[(k, k)].inv();
+        for col in 0..n {
+            m[(k, col)] = m[(k, col)] * factor;
+            inverse[(k, col)] = inverse[(k, col)] * factor;
+        }
+        for row in (0..n).filter(|row| *row != k) {
+            let factor = m[(row, k)];
+            if factor.norm() == T::zero() {
+                continue;
+            }
+            for col in 0..n {
+                m[(row, col)] = m[(row, col)] - factor * m[(k, col)];
+                inverse[(row, col)] = inverse[(row, col)] - factor * inverse[(k, col)];
+            }
+        }
+    }
+    Ok(inverse)
+}
+
+impl<T: Float + FloatEq> Array<T> {
+    // Scaling and squaring with the Padé approximant of the lowest degree that is accurate
+    // for the scaled norm.
+    pub fn expm(&self) -> Result<Array<T>, AlgaeError> {
+        check_square(self.size)?;
+        let norm = norm_one(self).to_f64().unwrap_or(f64::INFINITY);
+        if !norm.is_finite() {
+            return Err(AlgaeError::NoConvergence {
+                iterations:0,
+                last_estimate:norm,
+            });
+        }
+        for (theta, coefficients) in THETA.iter() {
+            if norm <= *theta {
+                let (u, v) = pade(self, coefficients);
+                return PluFactorization::new(&v - &u)?.try_solve(&v + &u);
+            }
+        }
+        let squarings = f64::max(0.0, (norm / THETA_13).log2().ceil()) as i32;
+        let scaled = self * constant::<T>(2.0).powi(-squarings);
+        let (u, v) = pade(&scaled, &PADE_13);
+        let mut res = PluFactorization::new(&v - &u)?.try_solve(&v + &u)?;
+        for _ in 0..squarings {
+            res = &res * &res;
+        }
+        Ok(res)
+    }
+
+    // The principal square root through the real Schur form A = Q * T * Q^T, by the Denman-Beavers
+    // iteration on T. A negative real eigenvalue has no real principal root and gives NotReal.
+    // Denman-Beavers inverts its iterates, so a singular T falls back to the recurrence of
+    // Higham (1987), which takes closed form roots of the diagonal blocks and solves a Sylvester
+    // equation for every block above them. Zero eigenvalues are fine as long as a root exists.
+    pub fn sqrtm(&self) -> Result<Array<T>, AlgaeError> {
+        check_square(self.size)?;
+        let schur = real_schur(self)?;
+        let tolerance = schur_tolerance(self);
+        check_real_eigenvalues(&schur, tolerance, false)?;
+        let root = if check_real_eigenvalues(&schur, tolerance, true).is_ok() {
+            denman_beavers(&schur.t)?
+        } else {
+            quasi_triangular_sqrt(&schur.t, &schur.blocks, tolerance)?
+        };
+        Ok(&(&schur.q * &root) * &schur.q.transpose())
+    }
+
+    // The principal logarithm by inverse scaling and squaring on the real Schur form: square
+    // roots of T are taken until T^(1/2^k) is close to I, then log(X) = 2 * atanh(Z) with
+    // Z = (X - I) * (X + I)^-1 is summed as a series, scaled back by 2^k and transformed back.
+    pub fn logm(&self) -> Result<Array<T>, AlgaeError> {
+        check_square(self.size)?;
+        let schur = real_schur(self)?;
+        let tolerance = schur_tolerance(self);
+        check_real_eigenvalues(&schur, tolerance, true)?;
+        let n = self.size.0;
+        let identity = Array::identity(n);
+        let mut x = schur.t.clone();
+        let mut square_roots = 0;
+        while norm_one(&(&x - &identity)) > constant(0.25) {
+            if square_roots == MAX_SQUARE_ROOTS {
+                return Err(AlgaeError::NoConvergence {
+                    iterations:square_roots,
+                    last_estimate:norm_one(&(&x - &identity)).to_f64().unwrap_or(f64::NAN),
+                });
+            }
+            x = quasi_triangular_sqrt(&x, &schur.blocks, tolerance)?;
+            square_roots += 1;
+        }
+        let z = PluFactorization::new(&x + &identity)?.try_solve(&x - &identity)?;
+        let z_squared = &z * &z;
+        let mut term = z;
+        let mut res = term.clone();
+        for j in 1..MAX_ITERATIONS {
+            term = &term * &z_squared;
+            let summand = &term / constant::<T>((2 * j + 1) as f64);
+            res += &summand;
+            if summand.norm() <= T::epsilon() * res.norm() {
+                let res = res * constant::<T>(2.0).powi(square_roots as i32 + 1);
+                return Ok(&(&schur.q * &res) * &schur.q.transpose());
+            }
+        }
+        Err(AlgaeError::NoConvergence {
+            iterations:MAX_ITERATIONS,
+            last_estimate:term.norm().to_f64().unwrap_or(f64::NAN),
+        })
+    }
+
+    // Repeated squaring, negative exponents invert the matrix first.
+    pub fn powi(&self, exponent:i64) -> Result<Array<T>, AlgaeError> {
+        check_square(self.size)?;
+        let mut base = if exponent < 0 {
+            PluFactorization::new(self.clone())?.inverse()?
+        } else {
+            self.clone()
+        };
+        let mut exponent = exponent.unsigned_abs();
+        let mut res = Array::identity(self.size.0);
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                res = &res * &base;
+            }
+            exponent >>= 1;
+            if exponent > 0 {
+                base = &base * &base;
+            }
+        }
+        Ok(res)
+    }
+
+    // A^p = V * diag(lambda^p) * V^-1 using the principal branch of lambda^p, so the input has
+    // to be diagonalizable, otherwise Singular is returned. Results with a significant imaginary
+    // part are rejected.
+    pub fn powf(&self, exponent:T) -> Result<Array<T>, AlgaeError> {
+        check_square(self.size)?;
+        let n = self.size.0;
+        let decomposition = self.eig()?;
+        let vectors_inv = complex_inverse(&decomposition.vectors)?;
+        // Defective matrices still produce eigenvectors, but nearly parallel ones. Beyond a
+        // condition number of 1/sqrt(eps) the result would keep less than half of the digits.
+        let frobenius = |a:&Array<Complex<T>>| a.fold(T::zero(), |acc, z| acc + z.norm_sqr()).sqrt();
+        if frobenius(&decomposition.vectors) * frobenius(&vectors_inv) * T::epsilon().sqrt() > T::one() {
+            return Err(AlgaeError::Singular);
+        }
+        let zero = Complex::new(T::zero(), T::zero());
+        let mut scaled = decomposition.vectors.clone();
+        for (col, value) in decomposition.values.iter().enumerate() {
+            let power = if value.norm() == T::zero() {
+                if exponent < T::zero() {
+                    return Err(AlgaeError::Singular);
+                }
+                if exponent == T::zero() { Complex::new(T::one(), T::zero()) } else { zero }
+            } else {
+                value.powf(exponent)
+            };
+            for row in 0..n {
+                scaled[(row, col)] = scaled[(row, col)] * power;
+            }
+        }
+        let res = &scaled * &vectors_inv;
+        let real = res.map(|z| z.re);
+        let imaginary = res.map(|z| z.im);
+        let tolerance = constant::<T>(1e3 * n as f64) * T::epsilon() * real.norm().max(T::one());
+        if imaginary.norm() > tolerance {
+            return Err(AlgaeError::NotReal);
+        }
+        Ok(real)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::array::matrix_functions::Array;
+    use crate::array::checks::assert_close;
+    use crate::error::error::AlgaeError;
+
+    fn rotation(theta:f64) -> Array<f64> {
+        Array::new_mat(vec![
+            vec![theta.cos(), -theta.sin()],
+            vec![theta.sin(), theta.cos()],
+        ])
+    }
+
+    #[test]
+    fn expm_closed_forms() {
+        let diagonal = Array::new_mat(vec![vec![1.0, 0.0], vec![0.0, -2.0]]);
+        let expected = Array::new_mat(vec![vec![f64::exp(1.0), 0.0], vec![0.0, f64::exp(-2.0)]]);
+        assert_close(&expected, &diagonal.expm().unwrap(), 1e-14);
+
+        let nilpotent = Array::new_mat(vec![vec![0.0, 1.0], vec![0.0, 0.0]]);
+        assert_close(&Array::new_mat(vec![vec![1.0, 1.0], vec![0.0, 1.0]]), &nilpotent.expm().unwrap(), 1e-15);
+
+        let generator = Array::new_mat(vec![vec![0.0, -3.0], vec![3.0, 0.0]]);
+        assert_close(&rotation(3.0), &generator.expm().unwrap(), 1e-14);
+
+        assert_close(&Array::identity(3), &Array::new_filled((3, 3), 0.0).expm().unwrap(), 1e-16);
+    }
+
+    // A = V * diag(-17, -1) * V^-1 with V = [[3, 1], [4, 2]], a classic example where
+    // a truncated Taylor series suffers badly from cancellation.
+    #[test]
+    fn expm_scaling_and_squaring() {
+        let a = Array::new_mat(vec![vec![-49.0, 24.0], vec![-64.0, 31.0]]);
+        let v = Array::new_mat(vec![vec![3.0, 1.0], vec![4.0, 2.0]]);
+        let v_inv = Array::new_mat(vec![vec![1.0, -0.5], vec![-2.0, 1.5]]);
+        let d = Array::new_mat(vec![vec![f64::exp(-17.0), 0.0], vec![0.0, f64::exp(-1.0)]]);
+        let expected = &(&v * &d) * &v_inv;
+        assert_close(&expected, &a.expm().unwrap(), 1e-12);
+    }
+
+    #[test]
+    fn sqrtm() {
+        let diagonal = Array::new_mat(vec![vec![4.0, 0.0], vec![0.0, 9.0]]);
+        assert_close(&Array::new_mat(vec![vec![2.0, 0.0], vec![0.0, 3.0]]), &diagonal.sqrtm().unwrap(), 1e-14);
+
+        let jordan = Array::new_mat(vec![vec![1.0, 1.0], vec![0.0, 1.0]]);
+        assert_close(&Array::new_mat(vec![vec![1.0, 0.5], vec![0.0, 1.0]]), &jordan.sqrtm().unwrap(), 1e-14);
+
+        let a = Array::new_mat(vec![
+            vec![5.0, 2.0, 0.0],
+            vec![1.0, 4.0, 1.0],
+            vec![0.5, 0.0, 3.0],
+        ]);
+        let root = a.sqrtm().unwrap();
+        assert_close(&a, &(&root * &root), 1e-13);
+
+        assert_close(&rotation(1.0), &rotation(2.0).sqrtm().unwrap(), 1e-13);
+
+        // Eigenvalues eight orders of magnitude apart, the scaling keeps the iteration short.
+        let spread = Array::new_mat(vec![vec![1e-4, 1.0], vec![0.0, 1e4]]);
+        let expected = Array::new_mat(vec![vec![1e-2, 1.0 / (1e-2 + 1e2)], vec![0.0, 1e2]]);
+        assert_close(&expected, &spread.sqrtm().unwrap(), 1e-12);
+    }
+
+    #[test]
+    fn sqrtm_negative_eigenvalue() {
+        let a = Array::new_mat(vec![vec![-1.0, 0.0], vec![0.0, 4.0]]);
+        assert_eq!(Err(AlgaeError::NotReal), a.sqrtm());
+        assert_eq!(Err(AlgaeError::NotReal), a.logm());
+    }
+
+    #[test]
+    fn sqrtm_singular_positive_semidefinite() {
+        let diagonal = Array::new_mat(vec![vec![0.0, 0.0], vec![0.0, 4.0]]);
+        assert_close(&Array::new_mat(vec![vec![0.0, 0.0], vec![0.0, 2.0]]), &diagonal.sqrtm().unwrap(), 1e-15);
+
+        // Rank one with the eigenvalues 0 and 2, its root is A / sqrt(2).
+        let a = Array::new_mat(vec![vec![1.0, 1.0], vec![1.0, 1.0]]);
+        assert_close(&(&a / f64::sqrt(2.0)), &a.sqrtm().unwrap(), 1e-14);
+        assert_eq!(Err(AlgaeError::Singular), a.logm());
+
+        // A nilpotent Jordan block has no square root at all.
+        let nilpotent = Array::new_mat(vec![vec![0.0, 1.0], vec![0.0, 0.0]]);
+        assert_eq!(Err(AlgaeError::Singular), nilpotent.sqrtm());
+    }
+
+    #[test]
+    fn logm() {
+        let diagonal = Array::new_mat(vec![vec![f64::exp(1.0), 0.0], vec![0.0, f64::exp(2.0)]]);
+        assert_close(&Array::new_mat(vec![vec![1.0, 0.0], vec![0.0, 2.0]]), &diagonal.logm().unwrap(), 1e-13);
+
+        let generator = Array::new_mat(vec![vec![0.0, -2.5], vec![2.5, 0.0]]);
+        assert_close(&generator, &rotation(2.5).logm().unwrap(), 1e-12);
+
+        let x = Array::new_mat(vec![
+            vec![0.1, 0.4, -0.2],
+            vec![0.0, -0.3, 0.5],
+            vec![0.2, 0.1, 0.2],
+        ]);
+        assert_close(&x, &x.expm().unwrap().logm().unwrap(), 1e-13);
+    }
+
+    #[test]
+    fn powi() {
+        let a = Array::new_mat(vec![vec![1.0, 1.0], vec![1.0, 0.0]]);
+        // Powers of the Fibonacci matrix.
+        assert_close(&Array::new_mat(vec![vec![89.0, 55.0], vec![55.0, 34.0]]), &a.powi(10).unwrap(), 1e-16);
+        assert_close(&Array::identity(2), &a.powi(0).unwrap(), 1e-16);
+        assert_close(&Array::new_mat(vec![vec![2.0, -3.0], vec![-3.0, 5.0]]), &a.powi(-4).unwrap(), 1e-14);
+        let singular = Array::new_mat(vec![vec![1.0, 2.0], vec![2.0, 4.0]]);
+        assert_eq!(Err(AlgaeError::Singular), singular.powi(-1));
+        assert_eq!(Err(AlgaeError::NotSquare { size:(1, 2) }), Array::new_vec(vec![1.0, 2.0]).powi(2));
+    }
+
+    #[test]
+    fn powf() {
+        let diagonal = Array::new_mat(vec![vec![4.0, 0.0], vec![0.0, 9.0]]);
+        assert_close(&Array::new_mat(vec![vec![8.0, 0.0], vec![0.0, 27.0]]), &diagonal.powf(1.5).unwrap(), 1e-14);
+        assert_close(&rotation(0.6), &rotation(1.8).powf(1.0 / 3.0).unwrap(), 1e-13);
+
+        let symmetric = Array::new_mat(vec![vec![2.0, 1.0], vec![1.0, 2.0]]);
+        assert_close(&symmetric.sqrtm().unwrap(), &symmetric.powf(0.5).unwrap(), 1e-13);
+        assert_close(&symmetric.powi(-3).unwrap(), &symmetric.powf(-3.0).unwrap(), 1e-13);
+
+        let negative = Array::new_mat(vec![vec![-4.0, 0.0], vec![0.0, 1.0]]);
+        assert_eq!(Err(AlgaeError::NotReal), negative.powf(0.5));
+        let defective = Array::new_mat(vec![vec![1.0, 1.0], vec![0.0, 1.0]]);
+        assert_eq!(Err(AlgaeError::Singular), defective.powf(0.5));
+    }
+}
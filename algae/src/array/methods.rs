@@ -1,38 +1,32 @@
 use crate::array::array::Array;
+use crate::array::view::ArrayViewMut;
+use crate::array::float_eq::PivotMagnitude;
 use std::ops::{Add, Sub, Neg, Mul, Div};
 use num::traits::{One, Zero};
 
-pub(in crate::array) fn multiply_row<T: Copy + Clone + Mul<Output = T>>
-(a:&mut Array<T>, row:usize, factor:T, pivot_col:usize) {
+pub(in crate::array) fn multiply_row<T: Clone + Mul<Output = T>>
+(a:&mut ArrayViewMut<T>, row:usize, factor:T, pivot_col:usize) {
     for col in pivot_col..a.size.0 {
-        a[(row, col)] = a[(row, col)] * factor;
+        a[(row, col)] = a[(row, col)].clone() * factor.clone();
     }
 }
 
-pub(in crate::array) fn multiply_add_row<T: Copy + Clone + Add<Output = T> + Mul<Output = T>>
-(a:&mut Array<T>, from_row:usize, to_row:usize, factor:T, pivot_col:usize) {
+pub(in crate::array) fn multiply_add_row<T: Clone + Add<Output = T> + Mul<Output = T>>
+(a:&mut ArrayViewMut<T>, from_row:usize, to_row:usize, factor:T, pivot_col:usize) {
     for col in pivot_col..a.size.0 {
-        a[(to_row, col)] = a[(to_row, col)] + a[(from_row, col)] * factor;
+        a[(to_row, col)] = a[(to_row, col)].clone() + a[(from_row, col)].clone() * factor.clone();
     }
 }
 
-impl<T: Copy + Clone + Zero + One + PartialEq
+impl<T: Clone + Zero + One + PartialEq
  + Add<Output = T> + Sub<Output = T> + Neg<Output = T> + Mul<Output = T> + Div<Output = T>> 
 Array<T> {
     pub fn identity(size:usize) -> Self {
-        let mut content = Vec::<Vec<T>>::with_capacity(size);
+        let mut ret = Array::new_filled((size, size), T::zero());
         for i in 0..size {
-            let mut temp = Vec::<T>::with_capacity(size);
-            for j in 0..size {
-                if i == j {
-                    temp.push(T::one());
-                } else {
-                    temp.push(T::zero());
-                }
-            }
-            content.push(temp);
+            ret[(i, i)] = T::one();
         }
-        Array::new_mat(content)
+        ret
     }
 
     pub fn standard_vec(i:usize, len:usize) -> Array<T> {
@@ -67,45 +61,36 @@ Array<T> {
         ret[(to_row, from_row)] = factor;
         ret
     }
+}
 
+impl<T: Clone + Zero + One + PartialEq
+ + Add<Output = T> + Sub<Output = T> + Neg<Output = T> + Mul<Output = T> + Div<Output = T> + PivotMagnitude> 
+Array<T> {
+    // Gaussian elimination with partial pivoting.
     pub fn determinant(&self) -> T {
         let mut a = self.clone();
         let mut pivot = (0, 0);
         let mut det = T::one();
         while pivot.0 < a.size.1 && pivot.1 < a.size.0 {
-            // Ensure pivot position != 0
-            loop {
-                let mut temp = Vec::<Vec<T>>::with_capacity(a.size.1);
-                while pivot.0 < a.content.len() && T::is_zero(&a[pivot]) {
-                    temp.push(a.content.swap_remove(pivot.0));
-                }
-                if pivot.0 >= a.content.len() {
-                    return T::zero();
-                } 
-                temp = temp.clone().into_iter().rev().collect();
-                match temp.pop() {
-                    Some(t) => {
-                        temp.insert(0, t);
-                        // Swap pivot row with a.content.len()
-                        det = -det;
-                        a.content.append(&mut temp);
-                    },
-                    None => {},
-                }
-                if pivot.1 >= a.size.0 {
-                    return T::zero();
-                }
-                if !T::is_zero(&a[pivot]) {
-                    break;
+            let row = (pivot.0..a.size.1).fold(pivot.0, |best, row| {
+                if a[(row, pivot.1)].magnitude() > a[(best, pivot.1)].magnitude() {
+                    row
+                } else {
+                    best
                 }
+            });
+            if T::is_zero(&a[(row, pivot.1)]) {
+                return T::zero();
             }
-            det = det * a[pivot];
-            let factor = T::one() / a[pivot];
-            multiply_row(&mut a, pivot.0, factor, pivot.1);
+            if row != pivot.0 {
+                a.swap_rows(pivot.0, row);
+                det = -det;
+            }
+            det = det * a[pivot].clone();
             for row in (pivot.0 + 1)..a.size.1 {
                 if !T::is_zero(&a[(row, pivot.1)]) {
-                    let factor = -a[(row, pivot.1)];
-                    multiply_add_row(&mut a, pivot.0, row, factor, pivot.1);
+                    let factor = -a[(row, pivot.1)].clone() / a[pivot].clone();
+                    multiply_add_row(&mut a.view_mut(), pivot.0, row, factor, pivot.1);
                 }
             }
             pivot.0 += 1;
@@ -124,16 +109,10 @@ mod tests{
 
     #[test]
     fn test_multiply_row() {
-        let expected = Array {
-            content:vec![vec![2, 4], vec![1, 0]],
-            size:(2, 2),
-        };
+        let expected = Array::new_mat(vec![vec![2, 4], vec![1, 0]]);
         let actual = {
-            let mut temp = Array {
-                content:vec![vec![1, 2], vec![1, 0]],
-                size:(2, 2),
-            };
-            multiply_row(&mut temp, 0, 2, 0);
+            let mut temp = Array::new_mat(vec![vec![1, 2], vec![1, 0]]);
+            multiply_row(&mut temp.view_mut(), 0, 2, 0);
             temp
         };
         assert_eq!(expected, actual);
@@ -141,16 +120,10 @@ mod tests{
 
     #[test]
     fn test_multiply_add_row() {
-        let expected = Array {
-            content:vec![vec![1, 0], vec![0, 1]],
-            size:(2, 2),
-        };
+        let expected = Array::new_mat(vec![vec![1, 0], vec![0, 1]]);
         let actual = {
-            let mut temp = Array {
-                content:vec![vec![1, 0], vec![2, 1]],
-                size:(2, 2),
-            };
-            multiply_add_row(&mut temp, 0, 1, -2, 0);
+            let mut temp = Array::new_mat(vec![vec![1, 0], vec![2, 1]]);
+            multiply_add_row(&mut temp.view_mut(), 0, 1, -2, 0);
             temp
         };
         assert_eq!(expected, actual);
@@ -158,53 +131,38 @@ mod tests{
 
     #[test]
     fn identity() {
-        let expected = Array {
-            content:vec![vec![1, 0], vec![0, 1]],
-            size:(2, 2),
-        };
+        let expected = Array::new_mat(vec![vec![1, 0], vec![0, 1]]);
         let actual = Array::identity(2);
         assert_eq!(expected, actual);
     }
 
     #[test]
     fn elementary_swap() {
-        let expected = Array {
-            content:vec![vec![1, 0, 0], vec![0, 0, 1], vec![0, 1, 0]],
-            size:(3, 3),
-        };
+        let expected = Array::new_mat(vec![vec![1, 0, 0], vec![0, 0, 1], vec![0, 1, 0]]);
         let actual = Array::elementary_swap(3, (1, 2));
         assert_eq!(expected, actual);
     }
 
     #[test]
     fn elementary_multiply() {
-        let expected = Array {
-            content:vec![vec![1, 0, 0], vec![0, 3, 0], vec![0, 0, 1]],
-            size:(3, 3),
-        };
+        let expected = Array::new_mat(vec![vec![1, 0, 0], vec![0, 3, 0], vec![0, 0, 1]]);
         let actual = Array::elementary_multiply(3, 1, 3);
         assert_eq!(expected, actual);
     }
 
     #[test]
     fn elementary_add_into() {
-        let expected = Array {
-            content:vec![vec![1, 0, 0], vec![3, 1, 0], vec![0, 0, 1]],
-            size:(3, 3),
-        };
+        let expected = Array::new_mat(vec![vec![1, 0, 0], vec![3, 1, 0], vec![0, 0, 1]]);
         let actual = Array::elementary_add_into(3, 0, 1, 3);
         assert_eq!(expected, actual);
     }
 
     #[test]
     fn elementary_multiply_equivalency() {
-        let a = Array {
-            content:vec![vec![1, 0, 0], vec![0, 3, 0], vec![0, 0, 1]],
-            size:(3, 3),
-        };
+        let a = Array::new_mat(vec![vec![1, 0, 0], vec![0, 3, 0], vec![0, 0, 1]]);
         let expected = {
             let mut temp = a.clone(); 
-            multiply_row(&mut temp, 1, 3, 0);
+            multiply_row(&mut temp.view_mut(), 1, 3, 0);
             temp
         };
         let actual = Array::elementary_multiply(3, 1, 3) * a;
@@ -213,13 +171,10 @@ mod tests{
 
     #[test]
     fn elementary_add_into_equivalency() {
-        let a = Array {
-            content:vec![vec![1, 0, 0], vec![0, 3, 0], vec![0, 0, 1]],
-            size:(3, 3),
-        };
+        let a = Array::new_mat(vec![vec![1, 0, 0], vec![0, 3, 0], vec![0, 0, 1]]);
         let expected = {
             let mut temp = a.clone(); 
-            multiply_add_row(&mut temp, 0, 1, 3, 0);
+            multiply_add_row(&mut temp.view_mut(), 0, 1, 3, 0);
             temp
         };
         let actual = Array::elementary_add_into(3, 0, 1, 3) * a;
@@ -229,28 +184,22 @@ mod tests{
     #[test]
     fn determinant_0() {
         let expected = 0.0;
-        let actual = Array {
-            content:vec![
-                vec![1.0, 2.0, 3.0],
-                vec![1.0, 1.0, 3.0],
-                vec![3.0, 3.0, 9.0]
-            ],
-            size:(3, 3)
-        }.determinant();
+        let actual = Array::new_mat(vec![
+            vec![1.0, 2.0, 3.0],
+            vec![1.0, 1.0, 3.0],
+            vec![3.0, 3.0, 9.0]
+        ]).determinant();
         assert_eq!(expected, actual);
     }
 
     #[test]
     fn determinant() {
         let expected:f64 = 1.0;
-        let actual = Array {
-            content:vec![
-                vec![ 3.0, 1.0, 0.0],
-                vec![ 9.0, 3.0, 1.0],
-                vec![19.0, 6.0, 2.0]
-            ],
-            size:(3, 3)
-        }.determinant();
+        let actual = Array::new_mat(vec![
+            vec![ 3.0, 1.0, 0.0],
+            vec![ 9.0, 3.0, 1.0],
+            vec![19.0, 6.0, 2.0]
+        ]).determinant();
         assert!(expected.float_eq(&actual));
     }
 }
\ No newline at end of file
@@ -1,11 +1,18 @@
 pub mod array {
-    use std::ops::{Index, IndexMut, Add, Sub, Neg, Mul};
+    use crate::error::error::AlgaeError;
+    use crate::array::view::broadcast_zip;
+    use std::ops::{Index, IndexMut, Add, Sub, Neg, Mul, Div, AddAssign, SubAssign, MulAssign, DivAssign};
     use std::fmt;
+    use num::traits::Zero;
 
+    // The elements are stored in a single row-major buffer.
+    // `strides` follows the ordering of `size`: strides.0 is the distance between two
+    // neighbouring columns, strides.1 the distance between two neighbouring rows.
     #[derive(Debug)]
     pub struct Array<T> {
-        pub(in crate::array) content:Vec<Vec<T>>,
+        pub(in crate::array) content:Vec<T>,
         pub size:(usize, usize),
+        pub(in crate::array) strides:(usize, usize),
     }
     
     impl<T: Clone> Clone for Array<T> {
@@ -13,6 +20,7 @@ pub mod array {
             Array::<T> {
                 content:self.content.clone(),
                 size:self.size,
+                strides:self.strides,
             }
         }
     }
@@ -32,10 +40,10 @@ pub mod array {
             for row in 0..self.size.1 {
                 s.push('[');
                 for col in 0..(self.size.0 - 1) {
-                    s.push_str(&self.content[row][col].to_string());
+                    s.push_str(&self.content[self.offset(row, col)].to_string());
                     s.push_str(", ");
                 }
-                s.push_str(&self.content[row][self.size.0 - 1].to_string());
+                s.push_str(&self.content[self.offset(row, self.size.0 - 1)].to_string());
                 s.push(']');
                 if row != (self.size.1 - 1) {
                     s.push_str(", ")
@@ -46,24 +54,47 @@ pub mod array {
         }
     }
 
-    impl<T: Copy + Clone> Array<T> {
-        pub fn new_vec(content:Vec<T>) -> Self {
-            let width = 1;
-            let height = content.len();
-            let content = {
-                let mut temp = Vec::<Vec<T>>::with_capacity(height);
-                for t in content {
-                    temp.push(vec![t]);
-                }
-                temp
-            };
+    impl<T> Array<T> {
+        pub(in crate::array) fn from_content(content:Vec<T>, size:(usize, usize)) -> Self {
+            debug_assert_eq!(content.len(), size.0 * size.1);
             Array {
                 content,
-                size:(width, height)
+                size,
+                strides:(1, size.0),
             }
         }
 
+        #[inline]
+        pub(in crate::array) fn offset(&self, row:usize, col:usize) -> usize {
+            row * self.strides.1 + col * self.strides.0
+        }
+
+        pub(in crate::array) fn swap_rows(&mut self, a:usize, b:usize) {
+            if a == b {
+                return;
+            }
+            let (a, b) = (usize::min(a, b), usize::max(a, b));
+            let width = self.size.0;
+            let (head, tail) = self.content.split_at_mut(b * self.strides.1);
+            head[(a * self.strides.1)..(a * self.strides.1 + width)].swap_with_slice(&mut tail[..width]);
+        }
+    }
+
+    impl<T: Clone> Array<T> {
+        pub fn new_vec(content:Vec<T>) -> Self {
+            let width = 1;
+            let height = content.len();
+            Array::from_content(content, (width, height))
+        }
+
         pub fn new_mat(content:Vec<Vec<T>>) -> Self {
+            match Self::try_new_mat(content) {
+                Ok(array) => array,
+                Err(e) => panic!("Invalid array initialisation: sub vectors of a matrix must all have equal lengths. {}", e),
+            }
+        }
+
+        pub fn try_new_mat(content:Vec<Vec<T>>) -> Result<Self, AlgaeError> {
             let height = content.len();
             let width = {
                 if height > 0 {
@@ -72,53 +103,49 @@ pub mod array {
                     0
                 }
             };
+            let mut flat = Vec::<T>::with_capacity(width * height);
             for v in &content {
                 if v.len() != width {
-                    panic!("Invalid array initialisation: sub vectors of a matrix must all have equal lengths.");
+                    return Err(AlgaeError::ShapeMismatch {
+                        expected:(width, 1),
+                        found:(v.len(), 1),
+                    });
                 }
+                flat.extend_from_slice(v);
             }
-            Array {
-                content,
-                size:(width, height),
-            }
+            Ok(Array::from_content(flat, (width, height)))
         }
 
         pub fn new_filled(size:(usize, usize), value:T) -> Self {
-            let row = vec![value; size.0];
-            let mut content = Vec::<Vec<T>>::with_capacity(size.1);
-            while content.len() < size.1 {
-                content.push(row.clone());
-            }
-            Array {
-                content,
-                size,
-            }
+            Array::from_content(vec![value; size.0 * size.1], size)
         }
 
         pub fn transpose(&self) -> Self {
-            let mut content = Vec::<Vec<T>>::with_capacity(self.size.0);
+            let mut content = Vec::<T>::with_capacity(self.content.len());
             for col in 0..self.size.0 {
-                let mut temp = Vec::<T>::with_capacity(self.size.1);
                 for row in 0..self.size.1 {
-                    temp.push(self.content[row][col]);
+                    content.push(self.content[self.offset(row, col)].clone());
                 }
-                content.push(temp)
-            }
-            Array{
-                content,
-                size:(self.size.1, self.size.0)
             }
+            Array::from_content(content, (self.size.1, self.size.0))
         }
 
         pub fn get_row(&self, index:usize) -> Array<T> {
             if index >= self.size.1 {
                 panic!("Index out of bounds: the height is {} but the index is {}", self.size.1, index);
             }
+            let start = self.offset(index, 0);
+            Array::from_content(self.content[start..(start + self.size.0)].to_vec(), (self.size.0, 1))
+        }
 
-            Array::<T> {
-                content:vec![self.content[index].clone()],
-                size:(self.content[index].len(), 1),
+        pub fn try_get_row(&self, index:usize) -> Result<Array<T>, AlgaeError> {
+            if index >= self.size.1 {
+                return Err(AlgaeError::IndexOutOfBounds {
+                    index:(index, 0),
+                    size:self.size,
+                });
             }
+            Ok(self.get_row(index))
         }
 
         pub fn get_col(&self, index:usize) -> Array<T> {
@@ -127,82 +154,107 @@ pub mod array {
             }
             let mut content = Vec::<T>::with_capacity(self.size.1);
             for row in 0..self.size.1 {
-                content.push(self.content[row][index]);
+                content.push(self.content[self.offset(row, index)].clone());
             }
 
             Array::<T>::new_vec(content)
         }
 
+        pub fn try_get_col(&self, index:usize) -> Result<Array<T>, AlgaeError> {
+            if index >= self.size.0 {
+                return Err(AlgaeError::IndexOutOfBounds {
+                    index:(0, index),
+                    size:self.size,
+                });
+            }
+            Ok(self.get_col(index))
+        }
+
         pub fn concat_0_axis(a:Self, b:Self) -> Self {
-            if a.size.1 != b.size.1 {
-                panic!("To concatenate two arrays over the 0-axis they have to be equal in height.");
+            match Self::try_concat_0_axis(a, b) {
+                Ok(array) => array,
+                Err(e) => panic!("To concatenate two arrays over the 0-axis they have to be equal in height. {}", e),
             }
-            let mut content = a.content;
-            for row in 0..b.content.len() {
-                content[row].append(&mut b.content[row].clone());
+        }
+
+        pub fn try_concat_0_axis(a:Self, b:Self) -> Result<Self, AlgaeError> {
+            if a.size.1 != b.size.1 {
+                return Err(AlgaeError::ShapeMismatch {
+                    expected:(b.size.0, a.size.1),
+                    found:b.size,
+                });
             }
-            Array {
-                content,
-                size:(a.size.0 + b.size.0, a.size.1)
+            let mut content = Vec::<T>::with_capacity(a.content.len() + b.content.len());
+            for row in 0..a.size.1 {
+                let start = a.offset(row, 0);
+                content.extend_from_slice(&a.content[start..(start + a.size.0)]);
+                let start = b.offset(row, 0);
+                content.extend_from_slice(&b.content[start..(start + b.size.0)]);
             }
+            Ok(Array::from_content(content, (a.size.0 + b.size.0, a.size.1)))
         }
 
         pub fn concat_1_axis(a:Self, b:Self) -> Self {
+            match Self::try_concat_1_axis(a, b) {
+                Ok(array) => array,
+                Err(e) => panic!("To concatenate two arrays over the 1-axis they have to be equal in width. {}", e),
+            }
+        }
+
+        pub fn try_concat_1_axis(a:Self, b:Self) -> Result<Self, AlgaeError> {
             if a.size.0 != b.size.0 {
-                panic!("To concatenate two arrays over the 1-axis they have to be equal in width.");
+                return Err(AlgaeError::ShapeMismatch {
+                    expected:(a.size.0, b.size.1),
+                    found:b.size,
+                });
             }
             let mut content = a.content;
-            for row in b.content {
-                content.push(row);
-            }
-            Array {
-                content,
-                size:(a.size.0, a.size.1 + b.size.1),
-            }
+            content.extend(b.content);
+            Ok(Array::from_content(content, (a.size.0, a.size.1 + b.size.1)))
         }
 
         pub fn split_0_axis(m:Self, col:usize) -> (Self, Self) {
-            let mut a = m.clone();
             let b_size = (usize::saturating_sub(m.size.0, col), m.size.1);
-            a.size.0 = col;
-            let mut content = Vec::<Vec<T>>::with_capacity(m.size.1);
-            for row in 0..a.size.1 {
-                content.push(
-                    a.content[row].split_off(col)
-                );
-            }
-            let b = Array::<T> {
-                content,
-                size:b_size,
-            };
-            (a, b)
+            let mut a_content = Vec::<T>::with_capacity(col * m.size.1);
+            let mut b_content = Vec::<T>::with_capacity(b_size.0 * b_size.1);
+            for row in 0..m.size.1 {
+                let start = m.offset(row, 0);
+                let (a_row, b_row) = m.content[start..(start + m.size.0)].split_at(col);
+                a_content.extend_from_slice(a_row);
+                b_content.extend_from_slice(b_row);
+            }
+            (Array::from_content(a_content, (col, m.size.1)), Array::from_content(b_content, b_size))
         }
 
         pub fn split_1_axis(m:Self, row:usize) -> (Self, Self) {
-            let mut a = m.clone();
             let b_size = (m.size.0, usize::saturating_sub(m.size.1, row));
-            a.size.1 = row;
-            let content = a.content.split_off(row);
-            let b = Array::<T> {
-                content,
-                size:b_size,
-            };
-            (a, b)
+            let mut a_content = m.content;
+            let b_content = a_content.split_off(row * m.strides.1);
+            (Array::from_content(a_content, (m.size.0, row)), Array::from_content(b_content, b_size))
         }
 
         pub fn extend_to(&mut self, size:(usize, usize), value:T) {
-            if size.0 < self.size.0 || size.1 < self.size.1 {
-                panic!("You can't extend an array to a smaller size.");
+            if let Err(e) = self.try_extend_to(size, value) {
+                panic!("You can't extend an array to a smaller size. {}", e);
             }
-            let add_vec = vec![value; size.0 - self.size.0];
-            for row_index in 0..self.size.1 {
-                self.content[row_index].append(&mut add_vec.clone());  
+        }
+
+        pub fn try_extend_to(&mut self, size:(usize, usize), value:T) -> Result<(), AlgaeError> {
+            if size.0 < self.size.0 || size.1 < self.size.1 {
+                return Err(AlgaeError::ShapeMismatch {
+                    expected:(usize::max(size.0, self.size.0), usize::max(size.1, self.size.1)),
+                    found:size,
+                });
             }
-            let add_row = vec![value; size.0];
-            while self.content.len() < size.1 {
-                self.content.push(add_row.clone());
+            let mut content = Vec::<T>::with_capacity(size.0 * size.1);
+            for row in 0..self.size.1 {
+                let start = self.offset(row, 0);
+                content.extend_from_slice(&self.content[start..(start + self.size.0)]);
+                content.resize(content.len() + size.0 - self.size.0, value.clone());
             }
-            self.size = size;
+            content.resize(size.0 * size.1, value);
+            *self = Array::from_content(content, size);
+            Ok(())
         }
     
         pub fn extend_by(&mut self, size:(usize, usize), value:T) {
@@ -210,7 +262,7 @@ pub mod array {
         }
     }
 
-    impl<T: Copy + Clone> Index<(usize, usize)> for Array<T> {
+    impl<T: Clone> Index<(usize, usize)> for Array<T> {
         type Output = T;
 
         fn index(&self, i:(usize, usize)) -> &Self::Output {
@@ -219,146 +271,327 @@ pub mod array {
             } else if i.1 >= self.size.0 {
                 panic!("Index out of bounds: the width is {} but the index is {}", self.size.0, i.1);
             }
-            &self.content[i.0][i.1]
+            &self.content[self.offset(i.0, i.1)]
         }
     }
     
-    impl<T: Copy + Clone> IndexMut<(usize, usize)> for Array<T> {
+    impl<T: Clone> IndexMut<(usize, usize)> for Array<T> {
         fn index_mut(&mut self, i:(usize, usize)) -> &mut Self::Output {
             if i.0 >= self.size.1 {
                 panic!("Index out of bounds: the height is {} but the index is {}", self.size.1, i.0);
             } else if i.1 >= self.size.0 {
                 panic!("Index out of bounds: the width is {} but the index is {}", self.size.0, i.1);
             }
-            &mut self.content[i.0][i.1]
+            let offset = self.offset(i.0, i.1);
+            &mut self.content[offset]
         }
     }
 
-    impl<T: Copy + Clone + Add<Output = T>> Add for Array<T> {
+    impl<T: Clone + Add<Output = T>> Array<T> {
+        // Element-wise operations broadcast axes of length one, like NumPy does.
+        pub fn try_add(&self, other:&Self) -> Result<Self, AlgaeError> {
+            if self.size != other.size {
+                return broadcast_zip(self.view(), other.view(), |a, b| a + b);
+            }
+            let content = self.content.iter()
+                .zip(other.content.iter())
+                .map(|(a, b)| a.clone() + b.clone())
+                .collect();
+            Ok(Array::from_content(content, self.size))
+        }
+    }
+
+    impl<T: Clone + Add<Output = T>> Add for Array<T> {
         type Output = Array<T>;
         fn add(self, other:Self) -> Self {
-            if self.size != other.size {
-                panic!("To add two arrays their sizes must be equal");
+            match self.try_add(&other) {
+                Ok(array) => array,
+                Err(e) => panic!("To add two arrays their sizes must be broadcastable. {}", e),
+            }
+        }
+    }
+
+    impl<'b, T: Clone + Add<Output = T>> Add<&'b Array<T>> for &Array<T> {
+        type Output = Array<T>;
+        fn add(self, other:&'b Array<T>) -> Array<T> {
+            match self.try_add(other) {
+                Ok(array) => array,
+                Err(e) => panic!("To add two arrays their sizes must be broadcastable. {}", e),
+            }
+        }
+    }
+
+    impl<'b, T: Clone + Add<Output = T>> Add<&'b Array<T>> for Array<T> {
+        type Output = Array<T>;
+        fn add(mut self, other:&'b Array<T>) -> Array<T> {
+            self += other;
+            self
+        }
+    }
+
+    impl<'b, T: Clone + Add<Output = T>> AddAssign<&'b Array<T>> for Array<T> {
+        fn add_assign(&mut self, other:&'b Array<T>) {
+            if self.size == other.size {
+                for (a, b) in self.content.iter_mut().zip(other.content.iter()) {
+                    *a = a.clone() + b.clone();
+                }
+                return;
             }
-            let mut content = Vec::<Vec<T>>::with_capacity(self.size.1);
+            let other = match other.view().broadcast_to(self.size) {
+                Ok(view) => view,
+                Err(e) => panic!("To add two arrays their sizes must be broadcastable. {}", e),
+            };
             for row in 0..self.size.1 {
-                let mut temp = Vec::<T>::with_capacity(self.size.0);
                 for col in 0..self.size.0 {
-                    temp.push(self.content[row][col] + other.content[row][col]);
+                    let offset = self.offset(row, col);
+                    self.content[offset] = self.content[offset].clone() + other[(row, col)].clone();
                 }
-                content.push(temp);
-            } 
-            Array {
-                content,
-                size:self.size,
             }
         }
     }
 
-    impl<T: Copy + Clone + Sub<Output = T>> Sub for Array<T> {
+    impl<T: Clone + Add<Output = T>> AddAssign for Array<T> {
+        fn add_assign(&mut self, other:Array<T>) {
+            *self += &other;
+        }
+    }
+
+    impl<T: Clone + Sub<Output = T>> Array<T> {
+        pub fn try_sub(&self, other:&Self) -> Result<Self, AlgaeError> {
+            if self.size != other.size {
+                return broadcast_zip(self.view(), other.view(), |a, b| a - b);
+            }
+            let content = self.content.iter()
+                .zip(other.content.iter())
+                .map(|(a, b)| a.clone() - b.clone())
+                .collect();
+            Ok(Array::from_content(content, self.size))
+        }
+    }
+
+    impl<T: Clone + Sub<Output = T>> Sub for Array<T> {
         type Output = Array<T>;
         fn sub(self, other:Self) -> Self {
-            if self.size != other.size {
-                panic!("To subtract two arrays their sizes must be equal");
+            match self.try_sub(&other) {
+                Ok(array) => array,
+                Err(e) => panic!("To subtract two arrays their sizes must be broadcastable. {}", e),
             }
-            let mut content = Vec::<Vec<T>>::with_capacity(self.size.1);
-            for row in 0..self.size.1 {
-            let mut temp = Vec::<T>::with_capacity(self.size.0);
-                for col in 0..self.size.0 {
-                    temp.push(self.content[row][col] - other.content[row][col]);
-                }
-                content.push(temp);
-            } 
-            Array {
-                content,
-                size:self.size,
+        }
+    }
+
+    impl<'b, T: Clone + Sub<Output = T>> Sub<&'b Array<T>> for &Array<T> {
+        type Output = Array<T>;
+        fn sub(self, other:&'b Array<T>) -> Array<T> {
+            match self.try_sub(other) {
+                Ok(array) => array,
+                Err(e) => panic!("To subtract two arrays their sizes must be broadcastable. {}", e),
             }
         }
     }
 
-    impl<T: Copy + Clone + Neg<Output = T>> Neg for Array<T> {
+    impl<'b, T: Clone + Sub<Output = T>> Sub<&'b Array<T>> for Array<T> {
         type Output = Array<T>;
-        fn neg(self) -> Self {
-            let mut content = Vec::<Vec<T>>::with_capacity(self.size.1);
+        fn sub(mut self, other:&'b Array<T>) -> Array<T> {
+            self -= other;
+            self
+        }
+    }
+
+    impl<'b, T: Clone + Sub<Output = T>> SubAssign<&'b Array<T>> for Array<T> {
+        fn sub_assign(&mut self, other:&'b Array<T>) {
+            if self.size == other.size {
+                for (a, b) in self.content.iter_mut().zip(other.content.iter()) {
+                    *a = a.clone() - b.clone();
+                }
+                return;
+            }
+            let other = match other.view().broadcast_to(self.size) {
+                Ok(view) => view,
+                Err(e) => panic!("To subtract two arrays their sizes must be broadcastable. {}", e),
+            };
             for row in 0..self.size.1 {
-            let mut temp = Vec::<T>::with_capacity(self.size.0);
                 for col in 0..self.size.0 {
-                    temp.push(-self.content[row][col]);
+                    let offset = self.offset(row, col);
+                    self.content[offset] = self.content[offset].clone() - other[(row, col)].clone();
                 }
-                content.push(temp);
-            } 
-            Array {
-                content,
-                size:self.size,
             }
         }
     }
 
-    impl<T: Copy + Clone + Add<Output = T> + Mul<Output = T>> Mul<T> for Array<T> {
+    impl<T: Clone + Sub<Output = T>> SubAssign for Array<T> {
+        fn sub_assign(&mut self, other:Array<T>) {
+            *self -= &other;
+        }
+    }
+
+    impl<T: Clone + Neg<Output = T>> Neg for Array<T> {
         type Output = Array<T>;
-        fn mul(self, other:T) -> Self {
-            let mut content = Vec::<Vec<T>>::with_capacity(self.size.1);
-            for row in 0..self.size.1 {
-                let mut temp = Vec::<T>::with_capacity(self.size.0);
-                for col in 0..self.size.0 {
-                    temp.push(self.content[row][col] * other);
-                }
-                content.push(temp);
-            } 
-            Array {
-                content,
-                size:self.size,
-            }                
+        fn neg(mut self) -> Self {
+            for a in self.content.iter_mut() {
+                *a = -a.clone();
+            }
+            self
         }
     }
 
-    impl<T: Copy + Clone + Add<Output = T> + Mul<Output = T>> Mul for Array<T> {
+    impl<T: Clone + Neg<Output = T>> Neg for &Array<T> {
         type Output = Array<T>;
-        fn mul(self, other:Self) -> Self {
-            if self.size.0 != other.size.1 {
-                panic!("To multiply two arrays the first width has to be equal to the seconds height.");
+        fn neg(self) -> Array<T> {
+            let content = self.content.iter().map(|a| -a.clone()).collect();
+            Array::from_content(content, self.size)
+        }
+    }
+
+    impl<T: Clone + Add<Output = T> + Mul<Output = T>> Mul<T> for Array<T> {
+        type Output = Array<T>;
+        fn mul(mut self, other:T) -> Self {
+            self *= other;
+            self
+        }
+    }
+
+    impl<T: Clone + Add<Output = T> + Mul<Output = T>> Mul<T> for &Array<T> {
+        type Output = Array<T>;
+        fn mul(self, other:T) -> Array<T> {
+            let content = self.content.iter().map(|a| a.clone() * other.clone()).collect();
+            Array::from_content(content, self.size)
+        }
+    }
+
+    impl<T: Clone + Mul<Output = T>> MulAssign<T> for Array<T> {
+        fn mul_assign(&mut self, other:T) {
+            for a in self.content.iter_mut() {
+                *a = a.clone() * other.clone();
             }
-            fn multiply_col<T: Copy + Clone + Add<Output = T> + Mul<Output = T>>
-            (a:Array<T>, b:Array<T>) -> Array<T> {
-                let mut array = a.get_col(0) * b[(0, 0)];
-                for i in 1..a.size.0 {
-                    array = array + a.get_col(i) * b[(i, 0)];
-                }
-                array
+        }
+    }
+
+    impl<T: Clone + Div<Output = T>> Div<T> for Array<T> {
+        type Output = Array<T>;
+        fn div(mut self, other:T) -> Self {
+            self /= other;
+            self
+        }
+    }
+
+    impl<T: Clone + Div<Output = T>> Div<T> for &Array<T> {
+        type Output = Array<T>;
+        fn div(self, other:T) -> Array<T> {
+            let content = self.content.iter().map(|a| a.clone() / other.clone()).collect();
+            Array::from_content(content, self.size)
+        }
+    }
+
+    impl<T: Clone + Div<Output = T>> DivAssign<T> for Array<T> {
+        fn div_assign(&mut self, other:T) {
+            for a in self.content.iter_mut() {
+                *a = a.clone() / other.clone();
+            }
+        }
+    }
+
+    // Scalars on the left hand side can't be covered generically, so the float types are listed.
+    impl Mul<Array<f32>> for f32 {
+        type Output = Array<f32>;
+        fn mul(self, other:Array<f32>) -> Array<f32> {
+            other * self
+        }
+    }
+
+    impl<'a> Mul<&'a Array<f32>> for f32 {
+        type Output = Array<f32>;
+        fn mul(self, other:&'a Array<f32>) -> Array<f32> {
+            other * self
+        }
+    }
+
+    impl Mul<Array<f64>> for f64 {
+        type Output = Array<f64>;
+        fn mul(self, other:Array<f64>) -> Array<f64> {
+            other * self
+        }
+    }
+
+    impl<'a> Mul<&'a Array<f64>> for f64 {
+        type Output = Array<f64>;
+        fn mul(self, other:&'a Array<f64>) -> Array<f64> {
+            other * self
+        }
+    }
+
+    impl<T: Clone + Zero + Add<Output = T> + Mul<Output = T>> Mul for Array<T> {
+        type Output = Array<T>;
+        fn mul(self, other:Self) -> Self {
+            match self.try_matmul(&other) {
+                Ok(array) => array,
+                Err(e) => panic!("To multiply two arrays the first width has to be equal to the seconds height. {}", e),
             }
-            let mut array = multiply_col(self.clone(), other.get_col(0));
-            for i in 1..other.size.0 {
-                array = Array::concat_0_axis(array.clone(), multiply_col(self.clone(), other.get_col(i)));
+        }
+    }
+
+    impl<'b, T: Clone + Zero + Add<Output = T> + Mul<Output = T>> Mul<&'b Array<T>> for &Array<T> {
+        type Output = Array<T>;
+        fn mul(self, other:&'b Array<T>) -> Array<T> {
+            match self.try_matmul(other) {
+                Ok(array) => array,
+                Err(e) => panic!("To multiply two arrays the first width has to be equal to the seconds height. {}", e),
             }
-            array
         }
     }
 
-    impl <T: Copy + Clone + Mul<Output = T>> Array<T> {
+    impl<'b, T: Clone + Zero + Add<Output = T> + Mul<Output = T>> Mul<&'b Array<T>> for Array<T> {
+        type Output = Array<T>;
+        fn mul(self, other:&'b Array<T>) -> Array<T> {
+            &self * other
+        }
+    }
+
+    impl<T: Clone + Zero + Add<Output = T> + Mul<Output = T>> Array<T> {
+        pub fn try_matmul(&self, other:&Self) -> Result<Self, AlgaeError> {
+            if self.size.0 != other.size.1 {
+                return Err(AlgaeError::ShapeMismatch {
+                    expected:(other.size.0, self.size.0),
+                    found:other.size,
+                });
+            }
+            let mut array = Array::new_filled((other.size.0, self.size.1), T::zero());
+            Array::matmul_into(self, other, &mut array);
+            Ok(array)
+        }
+    }
+
+    impl <T: Clone + Mul<Output = T>> Array<T> {
         pub fn hadamard_product(self, other:Self) -> Self {
-            if self.size != other.size {
-                panic!("The factors of a hadamard_product need to have the same size.");
+            match self.try_hadamard_product(&other) {
+                Ok(array) => array,
+                Err(e) => panic!("The factors of a hadamard_product need to have broadcastable sizes. {}", e),
             }
-            let mut array = self.clone();
-            for row in 0..self.size.1 {
-                for col in 0..self.size.0 {
-                    array[(row, col)] = self[(row, col)] * other[(row, col)]; 
-                }
+        }
+
+        pub fn try_hadamard_product(&self, other:&Self) -> Result<Self, AlgaeError> {
+            if self.size != other.size {
+                return broadcast_zip(self.view(), other.view(), |a, b| a * b);
             }
-            array
-        }        
+            let content = self.content.iter()
+                .zip(other.content.iter())
+                .map(|(a, b)| a.clone() * b.clone())
+                .collect();
+            Ok(Array::from_content(content, self.size))
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::array::array::Array;
+    use crate::error::error::AlgaeError;
 
     #[test]
     fn new_mat() {
         let expected = Array {
-            content:vec![vec![1, 2], vec![2, -1]],
+            content:vec![1, 2, 2, -1],
             size:(2, 2),
+            strides:(1, 2),
         };
         let actual = Array::new_mat(vec![vec![1, 2], vec![2, -1]]);
         assert_eq!(expected, actual);
@@ -367,8 +600,9 @@ mod tests {
     #[test]
     fn new_vec() {
         let expected = Array {
-            content:vec![vec![1], vec![2]],
+            content:vec![1, 2],
             size:(1, 2),
+            strides:(1, 1),
         };
         let actual = Array::new_vec(vec![1, 2]);
         assert_eq!(expected, actual);
@@ -377,8 +611,9 @@ mod tests {
     #[test]
     fn new_filled() {
         let expected = Array {
-            content:vec![vec![1, 1], vec![1, 1]],
+            content:vec![1, 1, 1, 1],
             size:(2, 2),
+            strides:(1, 2),
         };
         let actual = Array::new_filled((2, 2), 1);
         assert_eq!(expected, actual);
@@ -386,118 +621,58 @@ mod tests {
 
     #[test]
     fn transpose() {
-        let expected = Array {
-            content:vec![vec![1, 2], vec![0, 1]],
-            size:(2, 2),
-        };
-        let actual = Array {
-            content:vec![vec![1, 0], vec![2, 1]],
-            size:(2, 2),
-        }.transpose();
+        let expected = Array::new_mat(vec![vec![1, 2], vec![0, 1]]);
+        let actual = Array::new_mat(vec![vec![1, 0], vec![2, 1]]).transpose();
         assert_eq!(expected, actual);
     }
 
     #[test]
     fn get_row() {
-        let expected = Array {
-            content:vec![vec![0, 1]],
-            size:(2, 1),
-        };
-        let actual = Array {
-            content:vec![vec![1, 2], vec![0, 1]],
-            size:(2, 2),
-        }.get_row(1);
+        let expected = Array::new_mat(vec![vec![0, 1]]);
+        let actual = Array::new_mat(vec![vec![1, 2], vec![0, 1]]).get_row(1);
         assert_eq!(expected, actual);
     }
 
     #[test]
     fn get_col() {
-        let expected = Array {
-            content:vec![vec![2], vec![1]],
-            size:(1, 2),
-        };
-        let actual = Array {
-            content:vec![vec![1, 2], vec![0, 1]],
-            size:(2, 2),
-        }.get_col(1);
+        let expected = Array::new_mat(vec![vec![2], vec![1]]);
+        let actual = Array::new_mat(vec![vec![1, 2], vec![0, 1]]).get_col(1);
         assert_eq!(expected, actual);
     }
 
     #[test]
     fn concat_0_axis() {
-        let expected = Array {
-            content:vec![vec![1, 2], vec![0, 1]],
-            size:(2, 2),
-        };
-        let actual = Array::concat_0_axis(Array {
-            content:vec![vec![1], vec![0]],
-            size:(1, 2),
-        }, Array {
-            content:vec![vec![2], vec![1]],
-            size:(1, 2),
-        });
+        let expected = Array::new_mat(vec![vec![1, 2], vec![0, 1]]);
+        let actual = Array::concat_0_axis(Array::new_mat(vec![vec![1], vec![0]]), Array::new_mat(vec![vec![2], vec![1]]));
         assert_eq!(expected, actual);
     }
 
     #[test]
     fn concat_1_axis() {
-        let expected = Array {
-            content:vec![vec![1, 2], vec![0, 1]],
-            size:(2, 2),
-        };
-        let actual = Array::concat_1_axis(Array {
-            content:vec![vec![1, 2]],
-            size:(2, 1),
-        }, Array {
-            content:vec![vec![0, 1]],
-            size:(2, 1),
-        });
+        let expected = Array::new_mat(vec![vec![1, 2], vec![0, 1]]);
+        let actual = Array::concat_1_axis(Array::new_mat(vec![vec![1, 2]]), Array::new_mat(vec![vec![0, 1]]));
         assert_eq!(expected, actual);
     }
 
     #[test]
     fn split_0_axis() {
-        let expected = (Array {
-            content:vec![vec![1], vec![0]],
-            size:(1, 2),
-        }, Array {
-            content:vec![vec![2], vec![1]],
-            size:(1, 2),
-        });
-        let actual = Array::split_0_axis(Array {
-            content:vec![vec![1, 2], vec![0, 1]],
-            size:(2, 2),
-        }, 1);
+        let expected = (Array::new_mat(vec![vec![1], vec![0]]), Array::new_mat(vec![vec![2], vec![1]]));
+        let actual = Array::split_0_axis(Array::new_mat(vec![vec![1, 2], vec![0, 1]]), 1);
         assert_eq!(expected, actual);
     }
 
     #[test]
     fn split_1_axis() {
-        let expected = (Array {
-            content:vec![vec![1, 2]],
-            size:(2, 1),
-        }, Array {
-            content:vec![vec![0, 1]],
-            size:(2, 1),
-        });
-        let actual = Array::split_1_axis(Array {
-            content:vec![vec![1, 2], vec![0, 1]],
-            size:(2, 2),
-        }, 1);
+        let expected = (Array::new_mat(vec![vec![1, 2]]), Array::new_mat(vec![vec![0, 1]]));
+        let actual = Array::split_1_axis(Array::new_mat(vec![vec![1, 2], vec![0, 1]]), 1);
         assert_eq!(expected, actual);
     }
 
     #[test]
     fn extend_to() {
-        let expected = Array {
-            content:vec![vec![1, 2, 0], vec![0, 1, 0], vec![0, 0, 0]],
-            size:(3, 3),
-        };
+        let expected = Array::new_mat(vec![vec![1, 2, 0], vec![0, 1, 0], vec![0, 0, 0]]);
         let actual = {
-            let mut temp = Array {
-                content:vec![vec![1, 2], vec![0, 1]],
-                size:(2, 2),
-            };
+            let mut temp = Array::new_mat(vec![vec![1, 2], vec![0, 1]]);
             temp.extend_to((3, 3), 0);
             temp
         };
@@ -506,15 +681,9 @@ mod tests {
 
     #[test]
     fn extend_by() {
-        let expected = Array {
-            content:vec![vec![1, 2, 0], vec![0, 1, 0], vec![0, 0, 0]],
-            size:(3, 3),
-        };
+        let expected = Array::new_mat(vec![vec![1, 2, 0], vec![0, 1, 0], vec![0, 0, 0]]);
         let actual = {
-            let mut temp = Array {
-                content:vec![vec![1, 2], vec![0, 1]],
-                size:(2, 2),
-            };
+            let mut temp = Array::new_mat(vec![vec![1, 2], vec![0, 1]]);
             temp.extend_by((1, 1), 0);
             temp
         };
@@ -524,24 +693,15 @@ mod tests {
     #[test]
     fn index() {
         let expected = 0;
-        let actual = Array {
-            content:vec![vec![1, 2], vec![0, 1]],
-            size:(2, 2),
-        }[(1, 0)];
+        let actual = Array::new_mat(vec![vec![1, 2], vec![0, 1]])[(1, 0)];
         assert_eq!(expected, actual);
     }
 
     #[test]
     fn mut_index() {
-        let expected = Array {
-            content:vec![vec![1, 2], vec![0, 1]],
-            size:(2, 2),
-        };
+        let expected = Array::new_mat(vec![vec![1, 2], vec![0, 1]]);
         let actual = {
-            let mut temp = Array {
-                content:vec![vec![1, 2], vec![1, 1]],
-                size:(2, 2),
-            };
+            let mut temp = Array::new_mat(vec![vec![1, 2], vec![1, 1]]);
             temp[(1, 0)] = 0;
             temp
         };
@@ -550,77 +710,165 @@ mod tests {
 
     #[test]
     fn add() {
-        let expected = Array {
-            content:vec![vec![1, 2], vec![0, 1]],
-            size:(2, 2),
-        };
-        let actual = Array {
-            content:vec![vec![1, 0], vec![-1, 1]],
-            size:(2, 2),
-        } + Array {
-            content:vec![vec![0, 2], vec![1, 0]],
-            size:(2, 2),
-        };
+        let expected = Array::new_mat(vec![vec![1, 2], vec![0, 1]]);
+        let actual = Array::new_mat(vec![vec![1, 0], vec![-1, 1]]) + Array::new_mat(vec![vec![0, 2], vec![1, 0]]);
         assert_eq!(expected, actual);
     }
 
     #[test]
     fn sub() {
-        let expected = Array {
-            content:vec![vec![1, 2], vec![0, 1]],
-            size:(2, 2),
-        };
-        let actual = Array {
-            content:vec![vec![1, 0], vec![-1, 1]],
-            size:(2, 2),
-        } - Array {
-            content:vec![vec![0, -2], vec![-1, 0]],
-            size:(2, 2),
-        };
+        let expected = Array::new_mat(vec![vec![1, 2], vec![0, 1]]);
+        let actual = Array::new_mat(vec![vec![1, 0], vec![-1, 1]]) - Array::new_mat(vec![vec![0, -2], vec![-1, 0]]);
         assert_eq!(expected, actual);
     }
 
     #[test]
     fn neg() {
-        let expected = Array {
-            content:vec![vec![1, 2], vec![0, 1]],
-            size:(2, 2),
-        };
-        let actual = -Array {
-            content:vec![vec![-1, -2], vec![0, -1]],
-            size:(2, 2),
-        };
+        let expected = Array::new_mat(vec![vec![1, 2], vec![0, 1]]);
+        let actual = -Array::new_mat(vec![vec![-1, -2], vec![0, -1]]);
         assert_eq!(expected, actual);
     }
 
     #[test]
     fn scaler_prod() {
-        let expected = Array {
-            content:vec![vec![2, 4], vec![0, 2]],
-            size:(2, 2),
-        };
-        let actual = Array {
-            content:vec![vec![1, 2], vec![0, 1]],
-            size:(2, 2),
-        } * 2;
+        let expected = Array::new_mat(vec![vec![2, 4], vec![0, 2]]);
+        let actual = Array::new_mat(vec![vec![1, 2], vec![0, 1]]) * 2;
         assert_eq!(expected, actual);
     }
 
     #[test]
     fn mat_mul() {
-        let expected = Array {
-            content:vec![vec![4, 3], vec![1, 0]],
-            size:(2, 2),
+        let expected = Array::new_mat(vec![vec![4, 3], vec![1, 0]]);
+        let actual = Array::new_mat(vec![vec![1, 2], vec![0, 1]]) * Array::new_mat(vec![vec![2, 3], vec![1, 0]]);
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn ref_ops() {
+        let a = Array::new_mat(vec![vec![1, 0], vec![-1, 1]]);
+        let b = Array::new_mat(vec![vec![0, 2], vec![1, 0]]);
+        assert_eq!(a.clone() + b.clone(), &a + &b);
+        assert_eq!(a.clone() - b.clone(), &a - &b);
+        assert_eq!(a.clone() * b.clone(), &a * &b);
+        assert_eq!(-a.clone(), -&a);
+        assert_eq!(a.clone() * 3, &a * 3);
+        assert_eq!(a.clone() * b.clone() * a.clone(), &a * &b * &a);
+        assert_eq!(a.clone() + b.clone() - a.clone(), &a + &b - &a);
+    }
+
+    #[test]
+    fn compound_assignment() {
+        let expected = Array::new_mat(vec![vec![2.0, 1.0], vec![-1.0, 2.0]]);
+        let actual = {
+            let mut temp = Array::new_mat(vec![vec![1.0, 0.0], vec![-1.0, 1.0]]);
+            temp += Array::new_mat(vec![vec![1.0, 2.0], vec![0.0, 1.0]]);
+            temp -= &Array::new_mat(vec![vec![0.0, 1.0], vec![0.0, 0.0]]);
+            temp *= 2.0;
+            temp /= 4.0;
+            temp *= 2.0;
+            temp
         };
-        let actual = Array {
-            content:vec![vec![1, 2], vec![0, 1]],
-            size:(2, 2),
-        } * Array {
-            content:vec![vec![2, 3], vec![1, 0]],
-            size:(2, 2),
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn scalar_div() {
+        let expected = Array::new_mat(vec![vec![0.5, 1.0], vec![0.0, 0.5]]);
+        let a = Array::new_mat(vec![vec![1.0, 2.0], vec![0.0, 1.0]]);
+        assert_eq!(expected, &a / 2.0);
+        assert_eq!(expected, a / 2.0);
+    }
+
+    #[test]
+    fn scalar_on_the_left() {
+        let a = Array::new_mat(vec![vec![1.0, 2.0], vec![0.0, 1.0]]);
+        assert_eq!(a.clone() * 2.0, 2.0 * &a);
+        assert_eq!(a.clone() * 2.0, 2.0 * a.clone());
+        let b = Array::new_mat(vec![vec![1.0f32, 2.0], vec![0.0, 1.0]]);
+        assert_eq!(b.clone() * 0.5, 0.5 * b);
+    }
+
+    #[test]
+    fn try_new_mat() {
+        let expected = Err(AlgaeError::ShapeMismatch {
+            expected:(2, 1),
+            found:(1, 1),
+        });
+        let actual = Array::try_new_mat(vec![vec![1, 2], vec![2]]);
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn try_add() {
+        let a = Array::new_mat(vec![vec![1, 0], vec![-1, 1]]);
+        let expected = Err(AlgaeError::BroadcastMismatch {
+            left:(2, 2),
+            right:(3, 1),
+        });
+        assert_eq!(expected, a.try_add(&Array::new_mat(vec![vec![1, 2, 3]])));
+        assert_eq!(expected, a.try_sub(&Array::new_mat(vec![vec![1, 2, 3]])));
+        assert_eq!(expected, a.try_hadamard_product(&Array::new_mat(vec![vec![1, 2, 3]])));
+        assert_eq!(Ok(a.clone() + a.clone()), a.try_add(&a));
+    }
+
+    #[test]
+    fn broadcast_column_vector() {
+        let expected = Array::new_mat(vec![vec![2, 1], vec![1, 3]]);
+        let actual = Array::new_mat(vec![vec![1, 0], vec![-1, 1]]) + Array::new_vec(vec![1, 2]);
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn broadcast_row_vector() {
+        let expected = Array::new_mat(vec![vec![0, -2], vec![-2, -1]]);
+        let actual = Array::new_mat(vec![vec![1, 0], vec![-1, 1]]) - Array::new_mat(vec![vec![1, 2]]);
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn broadcast_outer() {
+        // A column and a row vector broadcast to their outer product.
+        let expected = Array::new_mat(vec![vec![1, 2, 3], vec![2, 4, 6]]);
+        let actual = Array::new_vec(vec![1, 2]).hadamard_product(Array::new_mat(vec![vec![1, 2, 3]]));
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn broadcast_assign() {
+        let expected = Array::new_mat(vec![vec![2, 1], vec![1, 3]]);
+        let actual = {
+            let mut temp = Array::new_mat(vec![vec![1, 0], vec![-1, 1]]);
+            temp += &Array::new_vec(vec![1, 2]);
+            temp
         };
         assert_eq!(expected, actual);
     }
+
+    #[test]
+    fn try_matmul() {
+        let a = Array::new_mat(vec![vec![1, 2], vec![0, 1]]);
+        let expected = Err(AlgaeError::ShapeMismatch {
+            expected:(3, 2),
+            found:(3, 1),
+        });
+        assert_eq!(expected, a.try_matmul(&Array::new_mat(vec![vec![1, 2, 3]])));
+    }
+
+    #[test]
+    fn try_concat() {
+        let a = Array::new_mat(vec![vec![1, 2], vec![0, 1]]);
+        let b = Array::new_mat(vec![vec![1, 2, 3]]);
+        assert!(Array::try_concat_0_axis(a.clone(), b.clone()).is_err());
+        assert!(Array::try_concat_1_axis(a.clone(), b).is_err());
+        assert!(a.clone().try_extend_to((1, 1), 0).is_err());
+        assert_eq!(
+            Err(AlgaeError::IndexOutOfBounds {
+                index:(2, 0),
+                size:(2, 2),
+            }),
+            a.try_get_row(2),
+        );
+    }
 }
 
 pub mod float_eq;
@@ -628,3 +876,19 @@ pub mod methods;
 pub mod field_methods;
 pub mod eigenvalues;
 pub mod factorizations;
+pub mod view;
+pub mod gemm;
+pub mod iter;
+pub mod reductions;
+pub mod svd;
+pub mod tensor;
+pub mod eigs;
+pub mod krylov;
+pub mod sparse;
+pub mod sparse_factorizations;
+pub mod finite_field;
+pub mod matrix_functions;
+pub mod structured;
+pub mod elementary;
+pub mod orthogonal;
+pub mod checks;
@@ -0,0 +1,217 @@
+use crate::array::array::Array;
+use crate::array::eigenvalues::constant;
+use crate::error::error::AlgaeError;
+use num::traits::Float;
+
+// Every function here treats the columns of an array as the vectors it works on.
+
+pub(in crate::array) fn dot<T: Float>(a:&[T], b:&[T]) -> T {
+    a.iter().zip(b.iter()).fold(T::zero(), |acc, (x, y)| acc + *x * *y)
+}
+
+fn columns<T: Float>(a:&Array<T>) -> Vec<Vec<T>> {
+    (0..a.size.0).map(|col| (0..a.size.1).map(|row| a[(row, col)]).collect()).collect()
+}
+
+pub(in crate::array) fn from_columns<T: Float>(cols:&[Vec<T>], height:usize) -> Array<T> {
+    let mut array = Array::new_filled((cols.len(), height), T::zero());
+    for (col, values) in cols.iter().enumerate() {
+        for (row, value) in values.iter().enumerate() {
+            array[(row, col)] = *value;
+        }
+    }
+    array
+}
+
+// Modified Gram-Schmidt against the orthonormal basis. A single pass loses orthogonality in
+// proportion to the condition number, the second pass restores it ("twice is enough").
+// Returns the coefficients of the removed components, summed over both passes.
+pub(in crate::array) fn orthogonalize<T: Float>(v:&mut [T], basis:&[Vec<T>]) -> Vec<T> {
+    let mut coefficients = vec![T::zero(); basis.len()];
+    for _ in 0..2 {
+        for (c, b) in coefficients.iter_mut().zip(basis.iter()) {
+            let projection = dot(v, b);
+            *c = *c + projection;
+            for (x, y) in v.iter_mut().zip(b.iter()) {
+                *x = *x - projection * *y;
+            }
+        }
+    }
+    coefficients
+}
+
+// Extends orthonormal vectors of length `len` by standard basis vectors until there are `count`
+// of them. Each step takes the standard basis vector with the largest remainder, its squared
+// norm is at least the missing dimension divided by len, so no cancellation can occur.
+pub(in crate::array) fn complete_basis<T: Float>(mut basis:Vec<Vec<T>>, len:usize, count:usize) -> Vec<Vec<T>> {
+    let mut candidates = (0..len).collect::<Vec<usize>>();
+    while basis.len() < count {
+        let remainder = |i:usize| T::one() - basis.iter().fold(T::zero(), |acc, b| acc + b[i] * b[i]);
+        let (position, &i) = candidates.iter().enumerate()
+            .max_by(|a, b| remainder(*a.1).partial_cmp(&remainder(*b.1)).unwrap_or(std::cmp::Ordering::Equal))
+            .unwrap_or_else(|| panic!("Faulty implementation: ran out of standard basis vectors"));
+        candidates.swap_remove(position);
+        let mut v = vec![T::zero(); len];
+        v[i] = T::one();
+        orthogonalize(&mut v, &basis);
+        let norm = dot(&v, &v).sqrt();
+        basis.push(v.iter().map(|x| *x / norm).collect());
+    }
+    basis
+}
+
+// Relative size below which the remainder of a vector counts as linearly dependent.
+fn default_tolerance<T: Float>(size:(usize, usize)) -> T {
+    T::epsilon() * constant(10.0 * usize::max(size.0, size.1) as f64)
+}
+
+impl<T: Float> Array<T> {
+    // Orthonormal basis of the column space, in the order of the columns. A column is left out
+    // if it's dependent on the ones before it, so the width of the result is the rank.
+    pub fn orthonormalize(&self) -> Array<T> {
+        self.orthonormalize_with(None)
+    }
+
+    // A column is dropped once its remainder after orthogonalization is at most
+    // tolerance times its original norm. None scales the machine epsilon by the larger dimension.
+    pub fn orthonormalize_with(&self, tolerance:Option<T>) -> Array<T> {
+        let tolerance = tolerance.unwrap_or(default_tolerance(self.size));
+        let mut basis = Vec::<Vec<T>>::with_capacity(usize::min(self.size.0, self.size.1));
+        for mut v in columns(self) {
+            let original = dot(&v, &v).sqrt();
+            orthogonalize(&mut v, &basis);
+            let norm = dot(&v, &v).sqrt();
+            if norm > tolerance * original {
+                basis.push(v.iter().map(|x| *x / norm).collect());
+            }
+        }
+        from_columns(&basis, self.size.1)
+    }
+
+    // Same as orthonormalize, the orthonormal basis is the natural one for the projections below.
+    pub fn column_space(&self) -> Array<T> {
+        self.orthonormalize()
+    }
+
+    // Orthonormal basis of the vectors orthogonal to every column, its width is height - rank.
+    pub fn orthogonal_complement(&self) -> Array<T> {
+        let len = self.size.1;
+        let basis = columns(&self.orthonormalize());
+        let rank = basis.len();
+        from_columns(&complete_basis(basis, len, len)[rank..], len)
+    }
+
+    // Orthogonal projection of every column onto the column space of subspace.
+    pub fn project_onto(&self, subspace:&Array<T>) -> Array<T> {
+        match self.try_project_onto(subspace) {
+            Ok(projection) => projection,
+            Err(e) => panic!("To project onto a subspace both arrays have to be equal in height. {}", e),
+        }
+    }
+
+    pub fn try_project_onto(&self, subspace:&Array<T>) -> Result<Array<T>, AlgaeError> {
+        if self.size.1 != subspace.size.1 {
+            return Err(AlgaeError::ShapeMismatch {
+                expected:(self.size.0, subspace.size.1),
+                found:self.size,
+            });
+        }
+        let q = subspace.orthonormalize();
+        q.try_matmul(&q.transpose().try_matmul(self)?)
+    }
+
+    // Whether the columns are orthonormal, i.e. A^T * A = I. For square arrays this is the
+    // usual definition of an orthogonal matrix.
+    pub fn is_orthogonal(&self) -> bool {
+        self.is_orthogonal_with(None)
+    }
+
+    // Every entry of A^T * A - I has to be at most tolerance in magnitude.
+    // None scales the machine epsilon by the larger dimension.
+    pub fn is_orthogonal_with(&self, tolerance:Option<T>) -> bool {
+        let tolerance = tolerance.unwrap_or(default_tolerance(self.size));
+        let cols = columns(self);
+        for (i, a) in cols.iter().enumerate() {
+            for (j, b) in cols.iter().enumerate().skip(i) {
+                let expected = if i == j { T::one() } else { T::zero() };
+                if (dot(a, b) - expected).abs() > tolerance {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::array::orthogonal::Array;
+    use crate::array::checks::assert_close;
+
+    #[test]
+    fn orthonormalize_spans_the_columns() {
+        let a = Array::new_mat(vec![
+            vec![1.0, 2.0, 0.0],
+            vec![1.0, 0.0, 1.0],
+            vec![0.0, 1.0, 3.0],
+            vec![2.0, -1.0, 1.0],
+        ]);
+        let q = a.orthonormalize();
+        assert_eq!((3, 4), q.size);
+        assert!(q.is_orthogonal());
+        assert_close(&a, &a.project_onto(&q), 1e-12);
+    }
+
+    #[test]
+    fn orthonormalize_drops_dependent_columns() {
+        let a = Array::new_mat(vec![
+            vec![1.0, 2.0, 0.0, 1.0],
+            vec![0.0, 0.0, 0.0, 1.0],
+            vec![1.0, 2.0, 0.0, 0.0],
+        ]);
+        let q = a.column_space();
+        assert_eq!((2, 3), q.size);
+        assert!(q.is_orthogonal());
+    }
+
+    #[test]
+    fn reorthogonalization_on_ill_conditioned_columns() {
+        let n = 8;
+        let hilbert = Array::new_mat((0..n)
+            .map(|i| (0..n).map(|j| 1.0 / (i + j + 1) as f64).collect())
+            .collect());
+        let q = hilbert.orthonormalize();
+        assert_eq!((n, n), q.size);
+        assert!(q.is_orthogonal_with(Some(1e-14)));
+    }
+
+    #[test]
+    fn projection_and_complement() {
+        let plane = Array::new_mat(vec![vec![1.0, 0.0], vec![1.0, 1.0], vec![0.0, 1.0]]);
+        let v = Array::new_vec(vec![3.0, -1.0, 2.0]);
+        let projection = v.project_onto(&plane);
+        let residual = &v - &projection;
+        assert_close(&Array::new_filled((1, 2), 0.0), &(plane.transpose() * residual.clone()), 1e-12);
+        assert_close(&projection, &projection.project_onto(&plane), 1e-12);
+
+        let complement = plane.orthogonal_complement();
+        assert_eq!((1, 3), complement.size);
+        assert!(complement.is_orthogonal());
+        assert_close(&Array::new_filled((1, 2), 0.0), &(plane.transpose() * complement.clone()), 1e-12);
+        assert_close(&residual, &v.project_onto(&complement), 1e-12);
+
+        let full = Array::concat_0_axis(plane.orthonormalize(), complement);
+        assert!(full.is_orthogonal());
+        assert_eq!(0, Array::<f64>::identity(3).orthogonal_complement().size.0);
+        assert_eq!((3, 3), Array::<f64>::new_filled((2, 3), 0.0).orthogonal_complement().size);
+        assert!(v.try_project_onto(&Array::identity(2)).is_err());
+    }
+
+    #[test]
+    fn is_orthogonal() {
+        let (s, c) = f64::sin_cos(0.3);
+        assert!(Array::new_mat(vec![vec![c, -s], vec![s, c]]).is_orthogonal());
+        assert!(!Array::new_mat(vec![vec![1.0, 1.0], vec![0.0, 1.0]]).is_orthogonal());
+        assert!(!Array::new_mat(vec![vec![2.0, 0.0], vec![0.0, 1.0]]).is_orthogonal());
+    }
+}
@@ -0,0 +1,308 @@
+use crate::array::array::Array;
+use crate::array::view::ArrayView;
+use crate::error::error::AlgaeError;
+use std::ops::{Add, Mul, Div};
+use num::traits::{Zero, One, Float, FromPrimitive};
+
+// Values that aren't comparable with themselves are NaNs. They win every comparison,
+// unless `skip_nan` is set, in which case they are ignored.
+fn extremum<T: Copy + PartialOrd, I: Iterator<Item = T>>
+(iter:I, replaces:fn(&T, &T) -> bool, skip_nan:bool) -> Option<(usize, T)> {
+    let mut best:Option<(usize, T)> = None;
+    for (i, value) in iter.enumerate() {
+        if value.partial_cmp(&value).is_none() {
+            if skip_nan {
+                continue;
+            }
+            return Some((i, value));
+        }
+        best = match best {
+            Some((_, b)) if !replaces(&value, &b) => best,
+            _ => Some((i, value)),
+        };
+    }
+    best
+}
+
+fn count<T: FromPrimitive>(n:usize) -> T {
+    match T::from_usize(n) {
+        Some(n) => n,
+        None => panic!("The number of elements {} isn't representable by the element type", n),
+    }
+}
+
+impl<T> Array<T> {
+    // Axis 0 runs along a row, like in concat_0_axis, so reducing it leaves one value per row.
+    // Axis 1 runs along a column and leaves one value per column.
+    fn lanes(&self, axis:usize) -> Vec<ArrayView<'_, T>> {
+        match axis {
+            0 => self.rows().collect(),
+            1 => self.cols().collect(),
+            _ => panic!("Invalid axis. {}", AlgaeError::InvalidAxis {
+                axis,
+                rank:2,
+            }),
+        }
+    }
+
+    fn from_lanes<U>(values:Vec<U>, axis:usize) -> Array<U> {
+        let len = values.len();
+        match axis {
+            0 => Array::from_content(values, (1, len)),
+            _ => Array::from_content(values, (len, 1)),
+        }
+    }
+
+    fn position(&self, i:usize) -> (usize, usize) {
+        (i / self.size.0, i % self.size.0)
+    }
+}
+
+impl<T: Copy + Clone + Zero + Add<Output = T>> Array<T> {
+    pub fn sum(&self) -> T {
+        self.fold(T::zero(), |acc, a| acc + a)
+    }
+
+    pub fn sum_axis(&self, axis:usize) -> Array<T> {
+        let values = self.lanes(axis).iter()
+            .map(|lane| lane.iter().fold(T::zero(), |acc, a| acc + *a))
+            .collect();
+        Self::from_lanes(values, axis)
+    }
+}
+
+impl<T: Copy + Clone + One + Mul<Output = T>> Array<T> {
+    pub fn product(&self) -> T {
+        self.fold(T::one(), |acc, a| acc * a)
+    }
+
+    pub fn product_axis(&self, axis:usize) -> Array<T> {
+        let values = self.lanes(axis).iter()
+            .map(|lane| lane.iter().fold(T::one(), |acc, a| acc * *a))
+            .collect();
+        Self::from_lanes(values, axis)
+    }
+}
+
+impl<T: Copy + Clone + Zero + Add<Output = T> + Div<Output = T> + FromPrimitive> Array<T> {
+    // None for an empty array, like `min` and `max`. Dividing by the zero count would panic
+    // for integer elements.
+    pub fn mean(&self) -> Option<T> {
+        let n = self.size.0 * self.size.1;
+        if n == 0 {
+            return None;
+        }
+        Some(self.sum() / count(n))
+    }
+
+    // None if the lanes along `axis` are empty.
+    pub fn mean_axis(&self, axis:usize) -> Option<Array<T>> {
+        let len = if axis == 0 { self.size.0 } else { self.size.1 };
+        let mut array = self.sum_axis(axis);
+        if len == 0 {
+            return None;
+        }
+        let len = count(len);
+        array.map_inplace(|a| a / len);
+        Some(array)
+    }
+}
+
+// NaNs propagate: if the array contains one, it is the result of every comparison.
+impl<T: Copy + Clone + PartialOrd> Array<T> {
+    pub fn min(&self) -> Option<T> {
+        extremum(self.iter().copied(), |a, b| a < b, false).map(|(_, v)| v)
+    }
+
+    pub fn max(&self) -> Option<T> {
+        extremum(self.iter().copied(), |a, b| a > b, false).map(|(_, v)| v)
+    }
+
+    // Returns the (row, col) index of the first minimum.
+    pub fn argmin(&self) -> Option<(usize, usize)> {
+        extremum(self.iter().copied(), |a, b| a < b, false).map(|(i, _)| self.position(i))
+    }
+
+    pub fn argmax(&self) -> Option<(usize, usize)> {
+        extremum(self.iter().copied(), |a, b| a > b, false).map(|(i, _)| self.position(i))
+    }
+
+    pub fn min_axis(&self, axis:usize) -> Option<Array<T>> {
+        let values = self.lanes(axis).iter()
+            .map(|lane| extremum(lane.iter().copied(), |a, b| a < b, false).map(|(_, v)| v))
+            .collect::<Option<Vec<T>>>()?;
+        Some(Self::from_lanes(values, axis))
+    }
+
+    pub fn max_axis(&self, axis:usize) -> Option<Array<T>> {
+        let values = self.lanes(axis).iter()
+            .map(|lane| extremum(lane.iter().copied(), |a, b| a > b, false).map(|(_, v)| v))
+            .collect::<Option<Vec<T>>>()?;
+        Some(Self::from_lanes(values, axis))
+    }
+
+    // The indices count along the reduced axis, i.e. they are column indices for axis 0.
+    pub fn argmin_axis(&self, axis:usize) -> Option<Vec<usize>> {
+        self.lanes(axis).iter()
+            .map(|lane| extremum(lane.iter().copied(), |a, b| a < b, false).map(|(i, _)| i))
+            .collect()
+    }
+
+    pub fn argmax_axis(&self, axis:usize) -> Option<Vec<usize>> {
+        self.lanes(axis).iter()
+            .map(|lane| extremum(lane.iter().copied(), |a, b| a > b, false).map(|(i, _)| i))
+            .collect()
+    }
+}
+
+impl<T: Float + FromPrimitive> Array<T> {
+    // `ddof` is subtracted from the number of elements in the divisor, 0 gives the population
+    // variance and 1 the unbiased sample variance.
+    pub fn var(&self, ddof:usize) -> T {
+        let mean = self.mean().unwrap_or_else(T::nan);
+        let squares = self.fold(T::zero(), |acc, a| acc + (a - mean) * (a - mean));
+        squares / count((self.size.0 * self.size.1).saturating_sub(ddof))
+    }
+
+    pub fn std(&self, ddof:usize) -> T {
+        self.var(ddof).sqrt()
+    }
+
+    pub fn var_axis(&self, axis:usize, ddof:usize) -> Array<T> {
+        let values = self.lanes(axis).iter()
+            .map(|lane| lane.to_array().var(ddof))
+            .collect();
+        Self::from_lanes(values, axis)
+    }
+
+    pub fn std_axis(&self, axis:usize, ddof:usize) -> Array<T> {
+        let mut array = self.var_axis(axis, ddof);
+        array.map_inplace(|a| a.sqrt());
+        array
+    }
+
+    pub fn nan_mean(&self) -> T {
+        let n = self.iter().filter(|a| !a.is_nan()).count();
+        self.nan_sum() / count(n)
+    }
+}
+
+impl<T: Float> Array<T> {
+    // The Euclidean norm for vectors and the Frobenius norm for matrices.
+    pub fn norm(&self) -> T {
+        self.fold(T::zero(), |acc, a| acc + a * a).sqrt()
+    }
+
+    pub fn norm_l1(&self) -> T {
+        self.fold(T::zero(), |acc, a| acc + a.abs())
+    }
+
+    // The largest absolute value, NaNs propagate.
+    pub fn norm_max(&self) -> T {
+        match extremum(self.iter().map(|a| a.abs()), |a, b| a > b, false) {
+            Some((_, v)) => v,
+            None => T::zero(),
+        }
+    }
+
+    pub fn norm_axis(&self, axis:usize) -> Array<T> {
+        let values = self.lanes(axis).iter()
+            .map(|lane| lane.iter().fold(T::zero(), |acc, a| acc + *a * *a).sqrt())
+            .collect();
+        Self::from_lanes(values, axis)
+    }
+
+    // The nan_* variants ignore NaNs instead of propagating them.
+    pub fn nan_sum(&self) -> T {
+        self.fold(T::zero(), |acc, a| if a.is_nan() { acc } else { acc + a })
+    }
+
+    pub fn nan_min(&self) -> Option<T> {
+        extremum(self.iter().copied(), |a, b| a < b, true).map(|(_, v)| v)
+    }
+
+    pub fn nan_max(&self) -> Option<T> {
+        extremum(self.iter().copied(), |a, b| a > b, true).map(|(_, v)| v)
+    }
+
+    pub fn nan_argmin(&self) -> Option<(usize, usize)> {
+        extremum(self.iter().copied(), |a, b| a < b, true).map(|(i, _)| self.position(i))
+    }
+
+    pub fn nan_argmax(&self) -> Option<(usize, usize)> {
+        extremum(self.iter().copied(), |a, b| a > b, true).map(|(i, _)| self.position(i))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::array::reductions::Array;
+    use crate::array::float_eq::FloatEq;
+
+    #[test]
+    fn sum_and_product() {
+        let a = Array::new_mat(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+        assert_eq!(21, a.sum());
+        assert_eq!(720, a.product());
+        assert_eq!(Array::new_vec(vec![6, 15]), a.sum_axis(0));
+        assert_eq!(Array::new_mat(vec![vec![5, 7, 9]]), a.sum_axis(1));
+        assert_eq!(Array::new_mat(vec![vec![4, 10, 18]]), a.product_axis(1));
+    }
+
+    #[test]
+    #[should_panic]
+    fn invalid_axis() {
+        Array::new_mat(vec![vec![1, 2]]).sum_axis(2);
+    }
+
+    #[test]
+    fn mean_var_std() {
+        let a = Array::new_mat(vec![vec![1.0, 2.0], vec![3.0, 6.0]]);
+        assert!(a.mean().unwrap().float_eq(&3.0));
+        assert!(a.var(0).float_eq(&3.5));
+        assert!(a.var(1).float_eq(&(14.0 / 3.0)));
+        assert!(a.mean_axis(1).unwrap().float_eq(&Array::new_mat(vec![vec![2.0, 4.0]])));
+        assert!(a.var_axis(0, 0).float_eq(&Array::new_vec(vec![0.25, 2.25])));
+        assert!(a.std_axis(0, 0).float_eq(&Array::new_vec(vec![0.5, 1.5])));
+        assert_eq!(None, Array::<i32>::new_vec(vec![]).mean());
+        assert_eq!(None, Array::<i32>::new_vec(vec![]).mean_axis(1));
+    }
+
+    #[test]
+    fn min_max() {
+        let a = Array::new_mat(vec![vec![3, 1, 4], vec![1, 5, 9]]);
+        assert_eq!(Some(1), a.min());
+        assert_eq!(Some(9), a.max());
+        assert_eq!(Some((0, 1)), a.argmin());
+        assert_eq!(Some((1, 2)), a.argmax());
+        assert_eq!(Some(Array::new_vec(vec![4, 9])), a.max_axis(0));
+        assert_eq!(Some(Array::new_mat(vec![vec![1, 1, 4]])), a.min_axis(1));
+        assert_eq!(Some(vec![2, 2]), a.argmax_axis(0));
+        assert_eq!(Some(vec![1, 0, 0]), a.argmin_axis(1));
+        assert_eq!(None, Array::<i32>::new_vec(vec![]).max());
+    }
+
+    #[test]
+    fn norms() {
+        let a = Array::new_mat(vec![vec![3.0, -4.0], vec![0.0, 0.0]]);
+        assert!(a.norm().float_eq(&5.0));
+        assert!(a.norm_l1().float_eq(&7.0));
+        assert!(a.norm_max().float_eq(&4.0));
+        assert!(a.norm_axis(0).float_eq(&Array::new_vec(vec![5.0, 0.0])));
+    }
+
+    #[test]
+    fn nan_handling() {
+        let a = Array::new_mat(vec![vec![1.0, f64::NAN], vec![-2.0, 3.0]]);
+        assert!(a.sum().is_nan());
+        assert!(a.max().unwrap().is_nan());
+        assert!(a.min().unwrap().is_nan());
+        assert_eq!(Some((0, 1)), a.argmax());
+        assert!(a.nan_sum().float_eq(&2.0));
+        assert!(a.nan_mean().float_eq(&(2.0 / 3.0)));
+        assert_eq!(Some(3.0), a.nan_max());
+        assert_eq!(Some(-2.0), a.nan_min());
+        assert_eq!(Some((1, 1)), a.nan_argmax());
+        assert_eq!(Some((1, 0)), a.nan_argmin());
+    }
+}
@@ -0,0 +1,834 @@
+use crate::array::array::Array;
+use crate::array::checks::check_index;
+use crate::array::krylov::LinearOperator;
+use crate::error::error::AlgaeError;
+use std::ops::{Add, Mul, Range};
+use num::traits::Zero;
+
+// Sizes follow the convention of Array::size, i.e. (width, height), indices are (row, col).
+
+// Unordered (row, col, value) triplets, convenient for assembling a matrix entry by entry.
+// Duplicates are kept until the matrix is converted, then they are summed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CooMatrix<T> {
+    rows:Vec<usize>,
+    cols:Vec<usize>,
+    values:Vec<T>,
+    pub size:(usize, usize),
+}
+
+// Compressed sparse rows: the entries of row i are values[indptr[i]..indptr[i + 1]] in the
+// columns indices[indptr[i]..indptr[i + 1]], which are strictly increasing.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsrMatrix<T> {
+    indptr:Vec<usize>,
+    indices:Vec<usize>,
+    values:Vec<T>,
+    pub size:(usize, usize),
+}
+
+// Compressed sparse columns, stored as the CSR representation of the transpose.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CscMatrix<T> {
+    transposed:CsrMatrix<T>,
+    pub size:(usize, usize),
+}
+
+fn check_range(range:&Range<usize>, len:usize, size:(usize, usize), row:bool) -> Result<(), AlgaeError> {
+    if range.start > range.end || range.end > len {
+        let index = if row { (range.end, 0) } else { (0, range.end) };
+        return Err(AlgaeError::IndexOutOfBounds {
+            index,
+            size,
+        });
+    }
+    Ok(())
+}
+
+impl<T: Copy + Clone> CooMatrix<T> {
+    pub fn new(size:(usize, usize)) -> Self {
+        CooMatrix {
+            rows:Vec::new(),
+            cols:Vec::new(),
+            values:Vec::new(),
+            size,
+        }
+    }
+
+    pub fn from_triplets(size:(usize, usize), triplets:&[(usize, usize, T)]) -> Result<Self, AlgaeError> {
+        let mut coo = CooMatrix::new(size);
+        for (row, col, value) in triplets {
+            coo.try_push(*row, *col, *value)?;
+        }
+        Ok(coo)
+    }
+
+    pub fn push(&mut self, row:usize, col:usize, value:T) {
+        if let Err(e) = self.try_push(row, col, value) {
+            panic!("The entry has to lie inside the matrix. {}", e);
+        }
+    }
+
+    pub fn try_push(&mut self, row:usize, col:usize, value:T) -> Result<(), AlgaeError> {
+        check_index((row, col), self.size)?;
+        self.rows.push(row);
+        self.cols.push(col);
+        self.values.push(value);
+        Ok(())
+    }
+
+    // The number of stored triplets, duplicates included.
+    pub fn nnz(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn triplets(&self) -> impl Iterator<Item = (usize, usize, T)> + '_ {
+        self.rows.iter()
+            .zip(self.cols.iter())
+            .zip(self.values.iter())
+            .map(|((row, col), value)| (*row, *col, *value))
+    }
+}
+
+impl<T: Copy + Clone + Zero + Add<Output = T>> CooMatrix<T> {
+    pub fn from_array(array:&Array<T>) -> Self {
+        let mut coo = CooMatrix::new(array.size);
+        for ((row, col), value) in array.indexed_iter() {
+            if !value.is_zero() {
+                coo.push(row, col, *value);
+            }
+        }
+        coo
+    }
+
+    pub fn to_array(&self) -> Array<T> {
+        let mut array = Array::new_filled(self.size, T::zero());
+        for (row, col, value) in self.triplets() {
+            array[(row, col)] = array[(row, col)] + value;
+        }
+        array
+    }
+
+    // Sorts the triplets by row with a counting sort, then sums the duplicates of every row.
+    pub fn to_csr(&self) -> CsrMatrix<T> {
+        let height = self.size.1;
+        let mut indptr = vec![0; height + 1];
+        for row in self.rows.iter() {
+            indptr[row + 1] += 1;
+        }
+        for i in 0..height {
+            indptr[i + 1] += indptr[i];
+        }
+        let mut next = indptr.clone();
+        let mut order = vec![0; self.nnz()];
+        for (i, row) in self.rows.iter().enumerate() {
+            order[next[*row]] = i;
+            next[*row] += 1;
+        }
+
+        let mut compressed = vec![0; height + 1];
+        let mut indices = Vec::<usize>::with_capacity(self.nnz());
+        let mut values = Vec::<T>::with_capacity(self.nnz());
+        for row in 0..height {
+            let mut entries = order[indptr[row]..indptr[row + 1]].iter()
+                .map(|i| (self.cols[*i], self.values[*i]))
+                .collect::<Vec<(usize, T)>>();
+            entries.sort_by_key(|(col, _)| *col);
+            let start = indices.len();
+            for (col, value) in entries {
+                if indices.len() > start && indices[indices.len() - 1] == col {
+                    let last = values.len() - 1;
+                    values[last] = values[last] + value;
+                } else {
+                    indices.push(col);
+                    values.push(value);
+                }
+            }
+            compressed[row + 1] = indices.len();
+        }
+        CsrMatrix {
+            indptr:compressed,
+            indices,
+            values,
+            size:self.size,
+        }
+    }
+
+    pub fn to_csc(&self) -> CscMatrix<T> {
+        let transposed = CooMatrix {
+            rows:self.cols.clone(),
+            cols:self.rows.clone(),
+            values:self.values.clone(),
+            size:(self.size.1, self.size.0),
+        };
+        CscMatrix {
+            transposed:transposed.to_csr(),
+            size:self.size,
+        }
+    }
+}
+
+impl<T: Copy + Clone> CsrMatrix<T> {
+    pub fn new(size:(usize, usize), indptr:Vec<usize>, indices:Vec<usize>, values:Vec<T>) -> Result<Self, AlgaeError> {
+        if indptr.len() != size.1 + 1 {
+            return Err(AlgaeError::ShapeMismatch {
+                expected:(1, size.1 + 1),
+                found:(1, indptr.len()),
+            });
+        }
+        if indices.len() != values.len() || indptr[0] != 0 || indptr[size.1] != indices.len() {
+            return Err(AlgaeError::ShapeMismatch {
+                expected:(1, indptr[size.1]),
+                found:(1, indices.len()),
+            });
+        }
+        for row in 0..size.1 {
+            if indptr[row] > indptr[row + 1] || indptr[row + 1] > indices.len() {
+                return Err(AlgaeError::IndexOutOfBounds {
+                    index:(row, 0),
+                    size,
+                });
+            }
+            let cols = &indices[indptr[row]..indptr[row + 1]];
+            for (i, col) in cols.iter().enumerate() {
+                // The columns of a row must be strictly increasing.
+                if *col >= size.0 || (i > 0 && cols[i - 1] >= *col) {
+                    return Err(AlgaeError::IndexOutOfBounds {
+                        index:(row, *col),
+                        size,
+                    });
+                }
+            }
+        }
+        Ok(CsrMatrix {
+            indptr,
+            indices,
+            values,
+            size,
+        })
+    }
+
+    pub fn nnz(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn indptr(&self) -> &[usize] {
+        &self.indptr
+    }
+
+    pub fn indices(&self) -> &[usize] {
+        &self.indices
+    }
+
+    pub fn values(&self) -> &[T] {
+        &self.values
+    }
+
+    // The stored entries (col, value) of one row.
+    pub fn row(&self, index:usize) -> impl Iterator<Item = (usize, T)> + '_ {
+        if index >= self.size.1 {
+            panic!("Index out of bounds: the height is {} but the index is {}", self.size.1, index);
+        }
+        let range = self.indptr[index]..self.indptr[index + 1];
+        self.indices[range.clone()].iter().copied().zip(self.values[range].iter().copied())
+    }
+
+    pub fn to_coo(&self) -> CooMatrix<T> {
+        let mut coo = CooMatrix::new(self.size);
+        for row in 0..self.size.1 {
+            for (col, value) in self.row(row) {
+                coo.push(row, col, value);
+            }
+        }
+        coo
+    }
+
+    // A counting sort by column, the result has sorted indices again.
+    pub fn transpose(&self) -> CsrMatrix<T> {
+        let width = self.size.0;
+        let mut indptr = vec![0; width + 1];
+        for col in self.indices.iter() {
+            indptr[col + 1] += 1;
+        }
+        for i in 0..width {
+            indptr[i + 1] += indptr[i];
+        }
+        let mut next = indptr.clone();
+        let mut indices = vec![0; self.nnz()];
+        let mut values = self.values.clone();
+        for row in 0..self.size.1 {
+            for i in self.indptr[row]..self.indptr[row + 1] {
+                let col = self.indices[i];
+                indices[next[col]] = row;
+                values[next[col]] = self.values[i];
+                next[col] += 1;
+            }
+        }
+        CsrMatrix {
+            indptr,
+            indices,
+            values,
+            size:(self.size.1, self.size.0),
+        }
+    }
+
+    pub fn to_csc(&self) -> CscMatrix<T> {
+        CscMatrix {
+            transposed:self.transpose(),
+            size:self.size,
+        }
+    }
+
+    pub fn slice_rows(&self, rows:Range<usize>) -> CsrMatrix<T> {
+        match self.try_slice_rows(rows) {
+            Ok(matrix) => matrix,
+            Err(e) => panic!("The sliced rows have to lie inside the matrix. {}", e),
+        }
+    }
+
+    pub fn try_slice_rows(&self, rows:Range<usize>) -> Result<CsrMatrix<T>, AlgaeError> {
+        check_range(&rows, self.size.1, self.size, true)?;
+        let (start, end) = (self.indptr[rows.start], self.indptr[rows.end]);
+        Ok(CsrMatrix {
+            indptr:self.indptr[rows.start..=rows.end].iter().map(|i| i - start).collect(),
+            indices:self.indices[start..end].to_vec(),
+            values:self.values[start..end].to_vec(),
+            size:(self.size.0, rows.end - rows.start),
+        })
+    }
+
+    pub fn slice_cols(&self, cols:Range<usize>) -> CsrMatrix<T> {
+        match self.try_slice_cols(cols) {
+            Ok(matrix) => matrix,
+            Err(e) => panic!("The sliced columns have to lie inside the matrix. {}", e),
+        }
+    }
+
+    pub fn try_slice_cols(&self, cols:Range<usize>) -> Result<CsrMatrix<T>, AlgaeError> {
+        check_range(&cols, self.size.0, self.size, false)?;
+        let mut indptr = Vec::<usize>::with_capacity(self.size.1 + 1);
+        let mut indices = Vec::<usize>::new();
+        let mut values = Vec::<T>::new();
+        indptr.push(0);
+        for row in 0..self.size.1 {
+            for (col, value) in self.row(row).filter(|(col, _)| cols.contains(col)) {
+                indices.push(col - cols.start);
+                values.push(value);
+            }
+            indptr.push(indices.len());
+        }
+        Ok(CsrMatrix {
+            indptr,
+            indices,
+            values,
+            size:(cols.end - cols.start, self.size.1),
+        })
+    }
+}
+
+impl<T: Copy + Clone + Zero> CsrMatrix<T> {
+    pub fn from_array(array:&Array<T>) -> Self {
+        let mut indptr = Vec::<usize>::with_capacity(array.size.1 + 1);
+        let mut indices = Vec::<usize>::new();
+        let mut values = Vec::<T>::new();
+        indptr.push(0);
+        for row in array.rows() {
+            for ((_, col), value) in row.indexed_iter() {
+                if !value.is_zero() {
+                    indices.push(col);
+                    values.push(*value);
+                }
+            }
+            indptr.push(indices.len());
+        }
+        CsrMatrix {
+            indptr,
+            indices,
+            values,
+            size:array.size,
+        }
+    }
+
+    pub fn to_array(&self) -> Array<T> {
+        let mut array = Array::new_filled(self.size, T::zero());
+        for row in 0..self.size.1 {
+            for (col, value) in self.row(row) {
+                array[(row, col)] = value;
+            }
+        }
+        array
+    }
+
+    // Missing entries are zero.
+    pub fn get(&self, row:usize, col:usize) -> T {
+        if let Err(e) = check_index((row, col), self.size) {
+            panic!("{}", e);
+        }
+        let range = self.indptr[row]..self.indptr[row + 1];
+        match self.indices[range.clone()].binary_search(&col) {
+            Ok(i) => self.values[range.start + i],
+            Err(_) => T::zero(),
+        }
+    }
+
+    pub fn diagonal(&self) -> Vec<T> {
+        (0..usize::min(self.size.0, self.size.1)).map(|i| self.get(i, i)).collect()
+    }
+}
+
+impl<T: Copy + Clone + Zero + Add<Output = T> + Mul<Output = T>> CsrMatrix<T> {
+    pub fn try_mul_dense(&self, other:&Array<T>) -> Result<Array<T>, AlgaeError> {
+        if self.size.0 != other.size.1 {
+            return Err(AlgaeError::ShapeMismatch {
+                expected:(other.size.0, self.size.0),
+                found:other.size,
+            });
+        }
+        let mut array = Array::new_filled((other.size.0, self.size.1), T::zero());
+        for row in 0..self.size.1 {
+            for (k, value) in self.row(row) {
+                for col in 0..other.size.0 {
+                    array[(row, col)] = array[(row, col)] + value * other[(k, col)];
+                }
+            }
+        }
+        Ok(array)
+    }
+
+    // Gustavson's algorithm, accumulating every row of the product in a dense buffer.
+    pub fn try_mul(&self, other:&CsrMatrix<T>) -> Result<CsrMatrix<T>, AlgaeError> {
+        if self.size.0 != other.size.1 {
+            return Err(AlgaeError::ShapeMismatch {
+                expected:(other.size.0, self.size.0),
+                found:other.size,
+            });
+        }
+        let width = other.size.0;
+        let mut accumulator = vec![T::zero(); width];
+        let mut occupied = vec![false; width];
+        let mut indptr = Vec::<usize>::with_capacity(self.size.1 + 1);
+        let mut indices = Vec::<usize>::new();
+        let mut values = Vec::<T>::new();
+        indptr.push(0);
+        for row in 0..self.size.1 {
+            let start = indices.len();
+            for (k, a) in self.row(row) {
+                for (col, b) in other.row(k) {
+                    if !occupied[col] {
+                        occupied[col] = true;
+                        indices.push(col);
+                    }
+                    accumulator[col] = accumulator[col] + a * b;
+                }
+            }
+            indices[start..].sort_unstable();
+            for col in indices[start..].iter() {
+                values.push(accumulator[*col]);
+                accumulator[*col] = T::zero();
+                occupied[*col] = false;
+            }
+            indptr.push(indices.len());
+        }
+        Ok(CsrMatrix {
+            indptr,
+            indices,
+            values,
+            size:(width, self.size.1),
+        })
+    }
+
+    // Merges the sorted rows of both matrices.
+    pub fn try_add(&self, other:&CsrMatrix<T>) -> Result<CsrMatrix<T>, AlgaeError> {
+        if self.size != other.size {
+            return Err(AlgaeError::ShapeMismatch {
+                expected:self.size,
+                found:other.size,
+            });
+        }
+        let mut indptr = Vec::<usize>::with_capacity(self.size.1 + 1);
+        let mut indices = Vec::<usize>::with_capacity(self.nnz() + other.nnz());
+        let mut values = Vec::<T>::with_capacity(self.nnz() + other.nnz());
+        indptr.push(0);
+        for row in 0..self.size.1 {
+            let mut a = self.row(row).peekable();
+            let mut b = other.row(row).peekable();
+            loop {
+                let next = match (a.peek(), b.peek()) {
+                    (Some((i, x)), Some((j, y))) if i == j => {
+                        let next = (*i, *x + *y);
+                        a.next();
+                        b.next();
+                        next
+                    },
+                    (Some((i, _)), Some((j, _))) if i < j => a.next().unwrap(),
+                    (_, Some(_)) => b.next().unwrap(),
+                    (Some(_), None) => a.next().unwrap(),
+                    (None, None) => break,
+                };
+                indices.push(next.0);
+                values.push(next.1);
+            }
+            indptr.push(indices.len());
+        }
+        Ok(CsrMatrix {
+            indptr,
+            indices,
+            values,
+            size:self.size,
+        })
+    }
+}
+
+impl<T: Copy + Clone> CscMatrix<T> {
+    // The entries of column j are values[indptr[j]..indptr[j + 1]] in the rows
+    // indices[indptr[j]..indptr[j + 1]].
+    pub fn new(size:(usize, usize), indptr:Vec<usize>, indices:Vec<usize>, values:Vec<T>) -> Result<Self, AlgaeError> {
+        let transposed = CsrMatrix::new((size.1, size.0), indptr, indices, values)?;
+        Ok(CscMatrix {
+            transposed,
+            size,
+        })
+    }
+
+    pub fn nnz(&self) -> usize {
+        self.transposed.nnz()
+    }
+
+    pub fn indptr(&self) -> &[usize] {
+        self.transposed.indptr()
+    }
+
+    pub fn indices(&self) -> &[usize] {
+        self.transposed.indices()
+    }
+
+    pub fn values(&self) -> &[T] {
+        self.transposed.values()
+    }
+
+    // The stored entries (row, value) of one column.
+    pub fn col(&self, index:usize) -> impl Iterator<Item = (usize, T)> + '_ {
+        self.transposed.row(index)
+    }
+
+    pub fn transpose(&self) -> CsrMatrix<T> {
+        self.transposed.clone()
+    }
+
+    pub fn to_csr(&self) -> CsrMatrix<T> {
+        self.transposed.transpose()
+    }
+
+    pub fn to_coo(&self) -> CooMatrix<T> {
+        let mut coo = CooMatrix::new(self.size);
+        for (row, col, value) in self.transposed.to_coo().triplets() {
+            coo.push(col, row, value);
+        }
+        coo
+    }
+
+    pub fn slice_rows(&self, rows:Range<usize>) -> CscMatrix<T> {
+        match self.try_slice_rows(rows) {
+            Ok(matrix) => matrix,
+            Err(e) => panic!("The sliced rows have to lie inside the matrix. {}", e),
+        }
+    }
+
+    pub fn try_slice_rows(&self, rows:Range<usize>) -> Result<CscMatrix<T>, AlgaeError> {
+        check_range(&rows, self.size.1, self.size, true)?;
+        let transposed = self.transposed.try_slice_cols(rows)?;
+        Ok(CscMatrix {
+            size:(transposed.size.1, transposed.size.0),
+            transposed,
+        })
+    }
+
+    pub fn slice_cols(&self, cols:Range<usize>) -> CscMatrix<T> {
+        match self.try_slice_cols(cols) {
+            Ok(matrix) => matrix,
+            Err(e) => panic!("The sliced columns have to lie inside the matrix. {}", e),
+        }
+    }
+
+    pub fn try_slice_cols(&self, cols:Range<usize>) -> Result<CscMatrix<T>, AlgaeError> {
+        check_range(&cols, self.size.0, self.size, false)?;
+        let transposed = self.transposed.try_slice_rows(cols)?;
+        Ok(CscMatrix {
+            size:(transposed.size.1, transposed.size.0),
+            transposed,
+        })
+    }
+}
+
+impl<T: Copy + Clone + Zero> CscMatrix<T> {
+    pub fn from_array(array:&Array<T>) -> Self {
+        CscMatrix {
+            transposed:CsrMatrix::from_array(&array.transpose()),
+            size:array.size,
+        }
+    }
+
+    pub fn to_array(&self) -> Array<T> {
+        self.transposed.to_array().transpose()
+    }
+
+    pub fn get(&self, row:usize, col:usize) -> T {
+        if let Err(e) = check_index((row, col), self.size) {
+            panic!("{}", e);
+        }
+        self.transposed.get(col, row)
+    }
+}
+
+impl<T: Copy + Clone + Zero + Add<Output = T> + Mul<Output = T>> CscMatrix<T> {
+    pub fn try_mul_dense(&self, other:&Array<T>) -> Result<Array<T>, AlgaeError> {
+        if self.size.0 != other.size.1 {
+            return Err(AlgaeError::ShapeMismatch {
+                expected:(other.size.0, self.size.0),
+                found:other.size,
+            });
+        }
+        let mut array = Array::new_filled((other.size.0, self.size.1), T::zero());
+        for k in 0..self.size.0 {
+            for (row, value) in self.col(k) {
+                for col in 0..other.size.0 {
+                    array[(row, col)] = array[(row, col)] + value * other[(k, col)];
+                }
+            }
+        }
+        Ok(array)
+    }
+
+    // (A * B)^T = B^T * A^T, which multiplies the stored transposes as CSR matrices.
+    pub fn try_mul(&self, other:&CscMatrix<T>) -> Result<CscMatrix<T>, AlgaeError> {
+        if self.size.0 != other.size.1 {
+            return Err(AlgaeError::ShapeMismatch {
+                expected:(other.size.0, self.size.0),
+                found:other.size,
+            });
+        }
+        Ok(CscMatrix {
+            transposed:other.transposed.try_mul(&self.transposed)?,
+            size:(other.size.0, self.size.1),
+        })
+    }
+
+    pub fn try_add(&self, other:&CscMatrix<T>) -> Result<CscMatrix<T>, AlgaeError> {
+        if self.size != other.size {
+            return Err(AlgaeError::ShapeMismatch {
+                expected:self.size,
+                found:other.size,
+            });
+        }
+        Ok(CscMatrix {
+            transposed:self.transposed.try_add(&other.transposed)?,
+            size:self.size,
+        })
+    }
+}
+
+impl<'b, T: Copy + Clone + Zero + Add<Output = T> + Mul<Output = T>> Mul<&'b Array<T>> for &CsrMatrix<T> {
+    type Output = Array<T>;
+    fn mul(self, other:&'b Array<T>) -> Array<T> {
+        match self.try_mul_dense(other) {
+            Ok(array) => array,
+            Err(e) => panic!("To multiply two matrices the first width has to be equal to the seconds height. {}", e),
+        }
+    }
+}
+
+impl<'b, T: Copy + Clone + Zero + Add<Output = T> + Mul<Output = T>> Mul<&'b CsrMatrix<T>> for &CsrMatrix<T> {
+    type Output = CsrMatrix<T>;
+    fn mul(self, other:&'b CsrMatrix<T>) -> CsrMatrix<T> {
+        match self.try_mul(other) {
+            Ok(matrix) => matrix,
+            Err(e) => panic!("To multiply two matrices the first width has to be equal to the seconds height. {}", e),
+        }
+    }
+}
+
+impl<'b, T: Copy + Clone + Zero + Add<Output = T> + Mul<Output = T>> Add<&'b CsrMatrix<T>> for &CsrMatrix<T> {
+    type Output = CsrMatrix<T>;
+    fn add(self, other:&'b CsrMatrix<T>) -> CsrMatrix<T> {
+        match self.try_add(other) {
+            Ok(matrix) => matrix,
+            Err(e) => panic!("To add two sparse matrices they have to be of equal size. {}", e),
+        }
+    }
+}
+
+impl<'b, T: Copy + Clone + Zero + Add<Output = T> + Mul<Output = T>> Mul<&'b Array<T>> for &CscMatrix<T> {
+    type Output = Array<T>;
+    fn mul(self, other:&'b Array<T>) -> Array<T> {
+        match self.try_mul_dense(other) {
+            Ok(array) => array,
+            Err(e) => panic!("To multiply two matrices the first width has to be equal to the seconds height. {}", e),
+        }
+    }
+}
+
+impl<'b, T: Copy + Clone + Zero + Add<Output = T> + Mul<Output = T>> Mul<&'b CscMatrix<T>> for &CscMatrix<T> {
+    type Output = CscMatrix<T>;
+    fn mul(self, other:&'b CscMatrix<T>) -> CscMatrix<T> {
+        match self.try_mul(other) {
+            Ok(matrix) => matrix,
+            Err(e) => panic!("To multiply two matrices the first width has to be equal to the seconds height. {}", e),
+        }
+    }
+}
+
+impl<'b, T: Copy + Clone + Zero + Add<Output = T> + Mul<Output = T>> Add<&'b CscMatrix<T>> for &CscMatrix<T> {
+    type Output = CscMatrix<T>;
+    fn add(self, other:&'b CscMatrix<T>) -> CscMatrix<T> {
+        match self.try_add(other) {
+            Ok(matrix) => matrix,
+            Err(e) => panic!("To add two sparse matrices they have to be of equal size. {}", e),
+        }
+    }
+}
+
+impl<T: Copy + Clone + Zero + Add<Output = T> + Mul<Output = T>> LinearOperator<T> for CsrMatrix<T> {
+    fn dimension(&self) -> usize {
+        self.size.1
+    }
+
+    fn size(&self) -> (usize, usize) {
+        self.size
+    }
+
+    fn apply(&self, x:&Array<T>) -> Array<T> {
+        self * x
+    }
+}
+
+impl<T: Copy + Clone + Zero + Add<Output = T> + Mul<Output = T>> LinearOperator<T> for CscMatrix<T> {
+    fn dimension(&self) -> usize {
+        self.size.1
+    }
+
+    fn size(&self) -> (usize, usize) {
+        self.size
+    }
+
+    fn apply(&self, x:&Array<T>) -> Array<T> {
+        self * x
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::array::sparse::{Array, CooMatrix, CsrMatrix, CscMatrix};
+    use crate::array::krylov::{conjugate_gradient, Jacobi, SolverOptions};
+    use crate::error::error::AlgaeError;
+
+    fn dense() -> Array<i32> {
+        Array::new_mat(vec![
+            vec![1, 0, 2, 0],
+            vec![0, 0, 3, 0],
+            vec![4, 5, 0, 6],
+        ])
+    }
+
+    #[test]
+    fn triplet_assembly_sums_duplicates() {
+        let coo = CooMatrix::from_triplets((4, 3), &[
+            (2, 3, 6), (0, 0, 1), (2, 0, 4), (1, 2, 1), (0, 2, 2), (2, 1, 5), (1, 2, 2),
+        ]).unwrap();
+        assert_eq!(7, coo.nnz());
+        let csr = coo.to_csr();
+        assert_eq!(6, csr.nnz());
+        assert_eq!(&[0, 2, 3, 6], csr.indptr());
+        assert_eq!(&[0, 2, 2, 0, 1, 3], csr.indices());
+        assert_eq!(dense(), csr.to_array());
+        assert_eq!(dense(), coo.to_array());
+        assert_eq!(dense(), coo.to_csc().to_array());
+        assert!(matches!(CooMatrix::from_triplets((4, 3), &[(3, 0, 1)]), Err(AlgaeError::IndexOutOfBounds { .. })));
+    }
+
+    #[test]
+    fn conversions() {
+        let a = dense();
+        let csr = CsrMatrix::from_array(&a);
+        let csc = CscMatrix::from_array(&a);
+        assert_eq!(a, csc.to_array());
+        assert_eq!(csr, csc.to_csr());
+        assert_eq!(csc, csr.to_csc());
+        assert_eq!(csr, csr.to_coo().to_csr());
+        assert_eq!(a, csc.to_coo().to_array());
+        assert_eq!(a.transpose(), csr.transpose().to_array());
+        assert_eq!(5, csr.get(2, 1));
+        assert_eq!(0, csc.get(1, 1));
+        assert_eq!(vec![(0, 2), (1, 3)], csc.col(2).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn validation() {
+        assert!(CsrMatrix::new((3, 2), vec![0, 1, 2], vec![0, 2], vec![1.0, 2.0]).is_ok());
+        assert!(CsrMatrix::new((3, 2), vec![0, 1, 2], vec![0, 3], vec![1.0, 2.0]).is_err());
+        assert!(CsrMatrix::new((3, 1), vec![0, 2], vec![2, 1], vec![1.0, 2.0]).is_err());
+        assert!(CsrMatrix::new((3, 2), vec![0, 2], vec![0, 1], vec![1.0, 2.0]).is_err());
+    }
+
+    #[test]
+    fn multiplication() {
+        let a = dense();
+        let b = Array::new_mat(vec![
+            vec![1, 2],
+            vec![0, 1],
+            vec![3, 0],
+            vec![1, 1],
+        ]);
+        let expected = &a * &b;
+        assert_eq!(expected, &CsrMatrix::from_array(&a) * &b);
+        assert_eq!(expected, &CscMatrix::from_array(&a) * &b);
+        assert_eq!(expected, (&CsrMatrix::from_array(&a) * &CsrMatrix::from_array(&b)).to_array());
+        assert_eq!(expected, (&CscMatrix::from_array(&a) * &CscMatrix::from_array(&b)).to_array());
+        assert!(CsrMatrix::from_array(&a).try_mul_dense(&a).is_err());
+    }
+
+    #[test]
+    fn addition() {
+        let a = dense();
+        let b = Array::new_mat(vec![
+            vec![0, 1, -2, 0],
+            vec![0, 0, 0, 0],
+            vec![1, 0, 0, 1],
+        ]);
+        let expected = &a + &b;
+        assert_eq!(expected, (&CsrMatrix::from_array(&a) + &CsrMatrix::from_array(&b)).to_array());
+        assert_eq!(expected, (&CscMatrix::from_array(&a) + &CscMatrix::from_array(&b)).to_array());
+        assert!(CsrMatrix::from_array(&a).try_add(&CsrMatrix::from_array(&b.transpose())).is_err());
+    }
+
+    #[test]
+    fn slicing() {
+        let a = dense();
+        let csr = CsrMatrix::from_array(&a);
+        let csc = CscMatrix::from_array(&a);
+        let expected = Array::new_mat(vec![vec![0, 3], vec![5, 0]]);
+        assert_eq!(expected, csr.slice_rows(1..3).slice_cols(1..3).to_array());
+        assert_eq!(expected, csc.slice_cols(1..3).slice_rows(1..3).to_array());
+        assert_eq!(Array::new_mat(vec![vec![0, 0, 3, 0]]), csr.slice_rows(1..2).to_array());
+        assert!(csr.try_slice_cols(2..5).is_err());
+    }
+
+    #[test]
+    fn krylov_solver_accepts_sparse() {
+        let n = 50;
+        let mut coo = CooMatrix::new((n, n));
+        for i in 0..n {
+            coo.push(i, i, 2.5);
+            if i + 1 < n {
+                coo.push(i, i + 1, -1.0);
+                coo.push(i + 1, i, -1.0);
+            }
+        }
+        let csr = coo.to_csr();
+        let b = Array::new_filled((1, n), 1.0);
+        let jacobi = Jacobi::from_diagonal(&csr.diagonal()).unwrap();
+        let (x, _) = conjugate_gradient(&csr, &b, None, Some(&jacobi), &SolverOptions::default()).unwrap();
+        assert!((&b - &(&csr.to_array() * &x)).norm() < 1e-9);
+        let (y, _) = conjugate_gradient(&coo.to_csc(), &b, None, None, &SolverOptions::default()).unwrap();
+        assert!((&x - &y).norm() < 1e-9);
+    }
+}
@@ -0,0 +1,738 @@
+use crate::array::array::Array;
+use crate::array::checks::check_square;
+use crate::array::sparse::{CooMatrix, CsrMatrix};
+use crate::error::error::AlgaeError;
+use std::collections::{BTreeSet, VecDeque};
+use num::traits::Float;
+
+// Symmetric permutations that reduce the fill-in of a factorisation. All orderings work on the
+// pattern of A + A^T and return p with p[k] = the original index that is eliminated k-th.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FillReducingOrdering {
+    Natural,
+    // Minimises the bandwidth, good for matrices from meshes and chains.
+    ReverseCuthillMcKee,
+    // Greedily eliminates the node of minimum degree in the elimination graph.
+    MinimumDegree,
+}
+
+// The sorted neighbours of every node in the graph of A + A^T, without self loops.
+fn symmetric_adjacency<T: Copy + Clone>(a:&CsrMatrix<T>) -> Vec<Vec<usize>> {
+    let n = a.size.1;
+    let mut adjacency = vec![Vec::<usize>::new(); n];
+    for row in 0..n {
+        for (col, _) in a.row(row) {
+            if row != col {
+                adjacency[row].push(col);
+                adjacency[col].push(row);
+            }
+        }
+    }
+    for neighbours in adjacency.iter_mut() {
+        neighbours.sort_unstable();
+        neighbours.dedup();
+    }
+    adjacency
+}
+
+fn inverse_permutation(p:&[usize]) -> Vec<usize> {
+    let mut inverse = vec![0; p.len()];
+    for (k, i) in p.iter().enumerate() {
+        inverse[*i] = k;
+    }
+    inverse
+}
+
+pub fn reverse_cuthill_mckee<T: Copy + Clone>(a:&CsrMatrix<T>) -> Vec<usize> {
+    let adjacency = symmetric_adjacency(a);
+    let n = adjacency.len();
+    let mut visited = vec![false; n];
+    let mut order = Vec::<usize>::with_capacity(n);
+    while order.len() < n {
+        // Every connected component starts at an unvisited node of minimum degree.
+        let start = match (0..n).filter(|i| !visited[*i]).min_by_key(|i| adjacency[*i].len()) {
+            Some(start) => start,
+            None => break,
+        };
+        visited[start] = true;
+        let mut queue = VecDeque::from(vec![start]);
+        while let Some(node) = queue.pop_front() {
+            order.push(node);
+            let mut next = adjacency[node].iter()
+                .copied()
+                .filter(|i| !visited[*i])
+                .collect::<Vec<usize>>();
+            next.sort_by_key(|i| adjacency[*i].len());
+            for i in next {
+                visited[i] = true;
+                queue.push_back(i);
+            }
+        }
+    }
+    order.reverse();
+    order
+}
+
+pub fn minimum_degree<T: Copy + Clone>(a:&CsrMatrix<T>) -> Vec<usize> {
+    let mut graph = symmetric_adjacency(a).into_iter()
+        .map(|neighbours| neighbours.into_iter().collect::<BTreeSet<usize>>())
+        .collect::<Vec<BTreeSet<usize>>>();
+    let n = graph.len();
+    let mut eliminated = vec![false; n];
+    let mut order = Vec::<usize>::with_capacity(n);
+    for _ in 0..n {
+        let node = match (0..n).filter(|i| !eliminated[*i]).min_by_key(|i| graph[*i].len()) {
+            Some(node) => node,
+            None => break,
+        };
+        // Eliminating a node turns its neighbours into a clique.
+        let neighbours = std::mem::take(&mut graph[node]);
+        for i in neighbours.iter() {
+            graph[*i].remove(&node);
+            graph[*i].extend(neighbours.iter().filter(|j| *j != i));
+        }
+        eliminated[node] = true;
+        order.push(node);
+    }
+    order
+}
+
+fn ordering<T: Copy + Clone>(a:&CsrMatrix<T>, ordering:FillReducingOrdering) -> Vec<usize> {
+    match ordering {
+        FillReducingOrdering::Natural => (0..a.size.1).collect(),
+        FillReducingOrdering::ReverseCuthillMcKee => reverse_cuthill_mckee(a),
+        FillReducingOrdering::MinimumDegree => minimum_degree(a),
+    }
+}
+
+// Checks b and applies the solver to every column of b.
+fn solve_columns<T: Float, F: Fn(&mut [T])>(n:usize, b:Array<T>, solve:F) -> Result<Array<T>, AlgaeError> {
+    if b.size.1 != n {
+        return Err(AlgaeError::ShapeMismatch {
+            expected:(b.size.0, n),
+            found:b.size,
+        });
+    }
+    let mut x = b;
+    for col in 0..x.size.0 {
+        let mut column = (0..n).map(|row| x[(row, col)]).collect::<Vec<T>>();
+        solve(&mut column);
+        for (row, value) in column.iter().enumerate() {
+            x[(row, col)] = *value;
+        }
+    }
+    Ok(x)
+}
+
+// The ordering and the pattern of the Cholesky factor L of P * A * P^T, computed once per
+// sparsity pattern with the elimination tree.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SymbolicCholesky {
+    permutation:Vec<usize>,
+    inverse:Vec<usize>,
+    // The sorted rows below the diagonal of every column of L.
+    pattern:Vec<Vec<usize>>,
+    // The columns k < j with L[j, k] != 0 of every row j.
+    row_pattern:Vec<Vec<usize>>,
+    pub size:(usize, usize),
+}
+
+impl SymbolicCholesky {
+    // Only the pattern of the lower triangle of P * A * P^T is used, A is assumed to be symmetric.
+    pub fn new<T: Copy + Clone>(a:&CsrMatrix<T>, fill_reducing:FillReducingOrdering) -> Result<Self, AlgaeError> {
+        let n = check_square(a.size)?;
+        let permutation = ordering(a, fill_reducing);
+        let inverse = inverse_permutation(&permutation);
+
+        let mut pattern = vec![BTreeSet::<usize>::new(); n];
+        for row in 0..n {
+            for (col, _) in a.row(row) {
+                let (i, j) = (inverse[row], inverse[col]);
+                if i != j {
+                    pattern[usize::min(i, j)].insert(usize::max(i, j));
+                }
+            }
+        }
+        // The pattern of a column is its own one joined with those of its children in the
+        // elimination tree, the parent of a column is its first row below the diagonal.
+        let mut children = vec![Vec::<usize>::new(); n];
+        for j in 0..n {
+            for c in std::mem::take(&mut children[j]) {
+                let inherited = pattern[c].iter().copied().filter(|i| *i > j).collect::<Vec<usize>>();
+                pattern[j].extend(inherited);
+            }
+            if let Some(parent) = pattern[j].iter().next() {
+                children[*parent].push(j);
+            }
+        }
+
+        let pattern = pattern.into_iter()
+            .map(|rows| rows.into_iter().collect::<Vec<usize>>())
+            .collect::<Vec<Vec<usize>>>();
+        let mut row_pattern = vec![Vec::<usize>::new(); n];
+        for (k, rows) in pattern.iter().enumerate() {
+            for i in rows {
+                row_pattern[*i].push(k);
+            }
+        }
+        Ok(SymbolicCholesky {
+            permutation,
+            inverse,
+            pattern,
+            row_pattern,
+            size:a.size,
+        })
+    }
+
+    pub fn permutation(&self) -> &[usize] {
+        &self.permutation
+    }
+
+    // The number of entries of L, the diagonal included.
+    pub fn nnz(&self) -> usize {
+        self.pattern.iter().map(|rows| rows.len() + 1).sum()
+    }
+
+    pub fn factorize<T: Float>(&self, a:&CsrMatrix<T>) -> Result<SparseCholesky<T>, AlgaeError> {
+        let mut cholesky = SparseCholesky {
+            symbolic:self.clone(),
+            diagonal:Vec::new(),
+            values:Vec::new(),
+        };
+        cholesky.refactorize(a)?;
+        Ok(cholesky)
+    }
+}
+
+// P * A * P^T = L * L^T for sparse symmetric positive definite A.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SparseCholesky<T> {
+    symbolic:SymbolicCholesky,
+    diagonal:Vec<T>,
+    // The values of L below the diagonal, aligned with symbolic.pattern.
+    values:Vec<Vec<T>>,
+}
+
+impl<T: Float> SparseCholesky<T> {
+    pub fn new(a:&CsrMatrix<T>, fill_reducing:FillReducingOrdering) -> Result<Self, AlgaeError> {
+        SymbolicCholesky::new(a, fill_reducing)?.factorize(a)
+    }
+
+    pub fn symbolic(&self) -> &SymbolicCholesky {
+        &self.symbolic
+    }
+
+    // Numeric factorisation of a matrix with the analysed pattern (or a subset of it), e.g.
+    // after the values changed. Left-looking, column by column.
+    pub fn refactorize(&mut self, a:&CsrMatrix<T>) -> Result<(), AlgaeError> {
+        let symbolic = &self.symbolic;
+        if a.size != symbolic.size {
+            return Err(AlgaeError::ShapeMismatch {
+                expected:symbolic.size,
+                found:a.size,
+            });
+        }
+        let n = a.size.0;
+        // The lower triangle of P * A * P^T by columns.
+        let mut columns = vec![Vec::<(usize, T)>::new(); n];
+        for row in 0..n {
+            for (col, value) in a.row(row) {
+                let (i, j) = (symbolic.inverse[row], symbolic.inverse[col]);
+                if i >= j {
+                    if i != j && symbolic.pattern[j].binary_search(&i).is_err() {
+                        return Err(AlgaeError::PatternMismatch {
+                            index:(row, col),
+                        });
+                    }
+                    columns[j].push((i, value));
+                }
+            }
+        }
+
+        let mut diagonal = vec![T::zero(); n];
+        let mut values = symbolic.pattern.iter().map(|rows| vec![T::zero(); rows.len()]).collect::<Vec<Vec<T>>>();
+        let mut x = vec![T::zero(); n];
+        for j in 0..n {
+            for (i, value) in columns[j].iter() {
+                x[*i] = x[*i] + *value;
+            }
+            for k in symbolic.row_pattern[j].iter() {
+                let rows = &symbolic.pattern[*k];
+                let start = match rows.binary_search(&j) {
+                    Ok(start) => start,
+                    Err(_) => continue,
+                };
+                let l_jk = values[*k][start];
+                x[j] = x[j] - l_jk * l_jk;
+                for (i, l_ik) in rows[(start + 1)..].iter().zip(values[*k][(start + 1)..].iter()) {
+                    x[*i] = x[*i] - *l_ik * l_jk;
+                }
+            }
+            if x[j] <= T::zero() || x[j].is_nan() {
+                return Err(AlgaeError::NotPositiveDefinite {
+                    index:symbolic.permutation[j],
+                });
+            }
+            diagonal[j] = x[j].sqrt();
+            x[j] = T::zero();
+            for (i, value) in symbolic.pattern[j].iter().zip(values[j].iter_mut()) {
+                *value = x[*i] / diagonal[j];
+                x[*i] = T::zero();
+            }
+        }
+        self.diagonal = diagonal;
+        self.values = values;
+        Ok(())
+    }
+
+    // The factor L of the permuted matrix.
+    pub fn l(&self) -> CsrMatrix<T> {
+        let n = self.symbolic.size.0;
+        let mut coo = CooMatrix::new((n, n));
+        for j in 0..n {
+            coo.push(j, j, self.diagonal[j]);
+            for (i, value) in self.symbolic.pattern[j].iter().zip(self.values[j].iter()) {
+                coo.push(*i, j, *value);
+            }
+        }
+        coo.to_csr()
+    }
+
+    pub fn solve(&self, b:Array<T>) -> Array<T> {
+        match self.try_solve(b) {
+            Ok(x) => x,
+            Err(e) => panic!("The right hand side must have as many rows as the matrix. {}", e),
+        }
+    }
+
+    pub fn try_solve(&self, b:Array<T>) -> Result<Array<T>, AlgaeError> {
+        let symbolic = &self.symbolic;
+        solve_columns(symbolic.size.0, b, |column| {
+            let mut y = symbolic.permutation.iter().map(|i| column[*i]).collect::<Vec<T>>();
+            for j in 0..y.len() {
+                y[j] = y[j] / self.diagonal[j];
+                for (i, l_ij) in symbolic.pattern[j].iter().zip(self.values[j].iter()) {
+                    y[*i] = y[*i] - *l_ij * y[j];
+                }
+            }
+            for j in (0..y.len()).rev() {
+                let sum = symbolic.pattern[j].iter()
+                    .zip(self.values[j].iter())
+                    .fold(y[j], |acc, (i, l_ij)| acc - *l_ij * y[*i]);
+                y[j] = sum / self.diagonal[j];
+            }
+            for (k, i) in symbolic.permutation.iter().enumerate() {
+                column[*i] = y[k];
+            }
+        })
+    }
+}
+
+// The column ordering of a sparse LU factorisation, the rows are chosen by partial pivoting
+// during the first numeric factorisation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SymbolicLu {
+    column_permutation:Vec<usize>,
+    pub size:(usize, usize),
+}
+
+impl SymbolicLu {
+    pub fn new<T: Copy + Clone>(a:&CsrMatrix<T>, fill_reducing:FillReducingOrdering) -> Result<Self, AlgaeError> {
+        check_square(a.size)?;
+        Ok(SymbolicLu {
+            column_permutation:ordering(a, fill_reducing),
+            size:a.size,
+        })
+    }
+
+    pub fn factorize<T: Float>(&self, a:&CsrMatrix<T>) -> Result<SparseLu<T>, AlgaeError> {
+        let mut lu = SparseLu {
+            column_permutation:self.column_permutation.clone(),
+            pivot_rows:Vec::new(),
+            l:Vec::new(),
+            u:Vec::new(),
+            size:self.size,
+        };
+        lu.factorize(a, true)?;
+        Ok(lu)
+    }
+}
+
+// P * A * Q = L * U with unit lower triangular L, computed column by column with the
+// left-looking algorithm of Gilbert and Peierls.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SparseLu<T> {
+    column_permutation:Vec<usize>,
+    // pivot_rows[k] is the original row of the k-th pivot, this defines P.
+    pivot_rows:Vec<usize>,
+    // The columns of L below the diagonal and of U above and on it, as (step, value) pairs.
+    l:Vec<Vec<(usize, T)>>,
+    u:Vec<Vec<(usize, T)>>,
+    pub size:(usize, usize),
+}
+
+impl<T: Float> SparseLu<T> {
+    pub fn new(a:&CsrMatrix<T>, fill_reducing:FillReducingOrdering) -> Result<Self, AlgaeError> {
+        SymbolicLu::new(a, fill_reducing)?.factorize(a)
+    }
+
+    // Factorises a matrix with new values, reusing the ordering and the pivot sequence of the
+    // first factorisation. A pivot that became zero gives a ZeroPivot error in its step.
+    pub fn refactorize(&mut self, a:&CsrMatrix<T>) -> Result<(), AlgaeError> {
+        if a.size != self.size {
+            return Err(AlgaeError::ShapeMismatch {
+                expected:self.size,
+                found:a.size,
+            });
+        }
+        self.factorize(a, false)
+    }
+
+    fn factorize(&mut self, a:&CsrMatrix<T>, pivoting:bool) -> Result<(), AlgaeError> {
+        let n = self.size.0;
+        let a = a.to_csc();
+        // The step each row was chosen as pivot in, None for rows that aren't pivotal yet.
+        let mut step_of_row:Vec<Option<usize>> = vec![None; n];
+        let mut pivot_rows = Vec::<usize>::with_capacity(n);
+        // Columns of L with original row indices, relabelled to steps at the end.
+        let mut l = Vec::<Vec<(usize, T)>>::with_capacity(n);
+        let mut u = Vec::<Vec<(usize, T)>>::with_capacity(n);
+        let mut x = vec![T::zero(); n];
+        let mut touched = vec![false; n];
+        let mut visited = vec![false; n];
+
+        for k in 0..n {
+            let col = self.column_permutation[k];
+            let mut rows = Vec::<usize>::new();
+            for (row, value) in a.col(col) {
+                x[row] = value;
+                touched[row] = true;
+                rows.push(row);
+            }
+
+            // The steps reachable from the pattern of the column in the graph of L, in
+            // topological order, solve the triangular system L * x = a in that order.
+            let mut postorder = Vec::<usize>::new();
+            for start in rows.clone() {
+                if let Some(s) = step_of_row[start] {
+                    if visited[s] {
+                        continue;
+                    }
+                    visited[s] = true;
+                    let mut stack = vec![(s, 0)];
+                    while let Some((s, next)) = stack.pop() {
+                        let child = l[s][next..].iter()
+                            .position(|(row, _)| step_of_row[*row].is_some_and(|t| !visited[t]))
+                            .map(|i| next + i);
+                        match child {
+                            Some(i) => {
+                                stack.push((s, i + 1));
+                                let t = step_of_row[l[s][i].0].unwrap();
+                                visited[t] = true;
+                                stack.push((t, 0));
+                            },
+                            None => postorder.push(s),
+                        }
+                    }
+                }
+            }
+            for s in postorder.iter().rev() {
+                let x_s = x[pivot_rows[*s]];
+                for (row, l_rs) in l[*s].iter() {
+                    if !touched[*row] {
+                        touched[*row] = true;
+                        rows.push(*row);
+                    }
+                    x[*row] = x[*row] - *l_rs * x_s;
+                }
+            }
+            for s in postorder.iter() {
+                visited[*s] = false;
+            }
+
+            let pivot = if pivoting {
+                rows.iter()
+                    .copied()
+                    .filter(|row| step_of_row[*row].is_none())
+                    .fold(None, |best:Option<usize>, row| match best {
+                        Some(b) if x[b].abs() >= x[row].abs() => Some(b),
+                        _ => Some(row),
+                    })
+            } else {
+                Some(self.pivot_rows[k])
+            };
+            let pivot = match pivot {
+                Some(pivot) if x[pivot] != T::zero() && !x[pivot].is_nan() => pivot,
+                _ => {
+                    for row in rows {
+                        x[row] = T::zero();
+                        touched[row] = false;
+                    }
+                    return Err(if pivoting { AlgaeError::Singular } else { AlgaeError::ZeroPivot { index:k } });
+                },
+            };
+
+            let pivot_value = x[pivot];
+            let mut u_k = Vec::<(usize, T)>::new();
+            let mut l_k = Vec::<(usize, T)>::new();
+            for row in rows {
+                match step_of_row[row] {
+                    Some(s) => u_k.push((s, x[row])),
+                    None if row == pivot => u_k.push((k, pivot_value)),
+                    None => l_k.push((row, x[row] / pivot_value)),
+                }
+                x[row] = T::zero();
+                touched[row] = false;
+            }
+            u_k.sort_by_key(|(s, _)| *s);
+            step_of_row[pivot] = Some(k);
+            pivot_rows.push(pivot);
+            l.push(l_k);
+            u.push(u_k);
+        }
+
+        for column in l.iter_mut() {
+            for entry in column.iter_mut() {
+                entry.0 = step_of_row[entry.0].unwrap_or(entry.0);
+            }
+            column.sort_by_key(|(s, _)| *s);
+        }
+        self.pivot_rows = pivot_rows;
+        self.l = l;
+        self.u = u;
+        Ok(())
+    }
+
+    pub fn column_permutation(&self) -> &[usize] {
+        &self.column_permutation
+    }
+
+    pub fn row_permutation(&self) -> &[usize] {
+        &self.pivot_rows
+    }
+
+    // The unit lower triangular factor in the permuted numbering.
+    pub fn l(&self) -> CsrMatrix<T> {
+        let n = self.size.0;
+        let mut coo = CooMatrix::new((n, n));
+        for (k, column) in self.l.iter().enumerate() {
+            coo.push(k, k, T::one());
+            for (s, value) in column.iter() {
+                coo.push(*s, k, *value);
+            }
+        }
+        coo.to_csr()
+    }
+
+    pub fn u(&self) -> CsrMatrix<T> {
+        let n = self.size.0;
+        let mut coo = CooMatrix::new((n, n));
+        for (k, column) in self.u.iter().enumerate() {
+            for (s, value) in column.iter() {
+                coo.push(*s, k, *value);
+            }
+        }
+        coo.to_csr()
+    }
+
+    pub fn solve(&self, b:Array<T>) -> Array<T> {
+        match self.try_solve(b) {
+            Ok(x) => x,
+            Err(e) => panic!("The right hand side must have as many rows as the matrix. {}", e),
+        }
+    }
+
+    pub fn try_solve(&self, b:Array<T>) -> Result<Array<T>, AlgaeError> {
+        solve_columns(self.size.0, b, |column| {
+            let mut y = self.pivot_rows.iter().map(|i| column[*i]).collect::<Vec<T>>();
+            for (k, l_k) in self.l.iter().enumerate() {
+                for (s, value) in l_k.iter() {
+                    y[*s] = y[*s] - *value * y[k];
+                }
+            }
+            for (k, u_k) in self.u.iter().enumerate().rev() {
+                // The last entry of a column of U is its diagonal.
+                let (diagonal, above) = match u_k.split_last() {
+                    Some(((_, diagonal), above)) => (*diagonal, above),
+                    None => continue,
+                };
+                y[k] = y[k] / diagonal;
+                for (s, value) in above.iter() {
+                    y[*s] = y[*s] - *value * y[k];
+                }
+            }
+            for (k, col) in self.column_permutation.iter().enumerate() {
+                column[*col] = y[k];
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::array::sparse_factorizations::{Array, CooMatrix, CsrMatrix, FillReducingOrdering};
+    use crate::array::sparse_factorizations::{SparseCholesky, SparseLu, SymbolicCholesky, reverse_cuthill_mckee, minimum_degree};
+    use crate::error::error::AlgaeError;
+
+    fn poisson(k:usize) -> CsrMatrix<f64> {
+        let n = k * k;
+        let mut coo = CooMatrix::new((n, n));
+        for i in 0..n {
+            coo.push(i, i, 4.0);
+            if i % k != 0 {
+                coo.push(i, i - 1, -1.0);
+                coo.push(i - 1, i, -1.0);
+            }
+            if i >= k {
+                coo.push(i, i - k, -1.0);
+                coo.push(i - k, i, -1.0);
+            }
+        }
+        coo.to_csr()
+    }
+
+    fn rhs(n:usize) -> Array<f64> {
+        Array::new_vec((0..n).map(|i| f64::cos(i as f64) + 0.5).collect())
+    }
+
+    fn bandwidth(a:&CsrMatrix<f64>, p:&[usize]) -> usize {
+        let mut inverse = vec![0; p.len()];
+        for (k, i) in p.iter().enumerate() {
+            inverse[*i] = k;
+        }
+        (0..a.size.1)
+            .flat_map(|row| a.row(row).map(move |(col, _)| (row, col)))
+            .map(|(row, col)| (inverse[row] as isize - inverse[col] as isize).unsigned_abs())
+            .max()
+            .unwrap_or(0)
+    }
+
+    #[test]
+    fn reverse_cuthill_mckee_reduces_bandwidth() {
+        // A path graph whose nodes are numbered in a scattered order.
+        let n = 20;
+        let label = |i:usize| (7 * i) % n;
+        let mut coo = CooMatrix::new((n, n));
+        for i in 0..n {
+            coo.push(label(i), label(i), 2.0);
+            if i + 1 < n {
+                coo.push(label(i), label(i + 1), -1.0);
+                coo.push(label(i + 1), label(i), -1.0);
+            }
+        }
+        let a = coo.to_csr();
+        let natural = (0..n).collect::<Vec<usize>>();
+        let p = reverse_cuthill_mckee(&a);
+        assert!(bandwidth(&a, &natural) > 1);
+        assert_eq!(1, bandwidth(&a, &p));
+    }
+
+    #[test]
+    fn minimum_degree_avoids_fill() {
+        // An arrow matrix fills in completely if the hub is eliminated first.
+        let n = 10;
+        let mut coo = CooMatrix::new((n, n));
+        for i in 0..n {
+            coo.push(i, i, n as f64);
+            if i > 0 {
+                coo.push(0, i, 1.0);
+                coo.push(i, 0, 1.0);
+            }
+        }
+        let a = coo.to_csr();
+        // Once only one leaf is left the hub ties with it.
+        assert!(!minimum_degree(&a)[..(n - 2)].contains(&0));
+        let natural = SymbolicCholesky::new(&a, FillReducingOrdering::Natural).unwrap();
+        let reordered = SymbolicCholesky::new(&a, FillReducingOrdering::MinimumDegree).unwrap();
+        assert_eq!(n * (n + 1) / 2, natural.nnz());
+        assert_eq!(2 * n - 1, reordered.nnz());
+    }
+
+    #[test]
+    fn cholesky_solve() {
+        let a = poisson(6);
+        let b = rhs(36);
+        for ordering in [FillReducingOrdering::Natural, FillReducingOrdering::ReverseCuthillMcKee, FillReducingOrdering::MinimumDegree] {
+            let cholesky = SparseCholesky::new(&a, ordering).unwrap();
+            let x = cholesky.solve(b.clone());
+            assert!((&b - &(&a * &x)).norm() < 1e-12);
+            let l = cholesky.l().to_array();
+            let permuted = {
+                let dense = a.to_array();
+                let perm = cholesky.symbolic().permutation();
+                let mut permuted = dense.clone();
+                for i in 0..36 {
+                    for j in 0..36 {
+                        permuted[(i, j)] = dense[(perm[i], perm[j])];
+                    }
+                }
+                permuted
+            };
+            assert!((&l * &l.transpose() - permuted).norm_max() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn cholesky_refactorize() {
+        let a = poisson(5);
+        let mut cholesky = SparseCholesky::new(&a, FillReducingOrdering::MinimumDegree).unwrap();
+        let shifted = &a + &CsrMatrix::from_array(&(Array::identity(25) * 3.0));
+        cholesky.refactorize(&shifted).unwrap();
+        let b = rhs(25);
+        assert!((&b - &(&shifted * &cholesky.solve(b.clone()))).norm() < 1e-12);
+
+        let mut coo = a.to_coo();
+        coo.push(0, 24, 0.1);
+        coo.push(24, 0, 0.1);
+        let symbolic = SymbolicCholesky::new(&a, FillReducingOrdering::Natural).unwrap();
+        assert!(matches!(symbolic.factorize(&coo.to_csr()), Err(AlgaeError::PatternMismatch { .. })));
+        let indefinite = &a + &CsrMatrix::from_array(&(Array::identity(25) * -10.0));
+        assert!(matches!(cholesky.refactorize(&indefinite), Err(AlgaeError::NotPositiveDefinite { .. })));
+    }
+
+    #[test]
+    fn lu_solve_with_pivoting() {
+        // Zero diagonal entries force row exchanges.
+        let dense = Array::new_mat(vec![
+            vec![0.0, 2.0, 0.0, 1.0, 0.0],
+            vec![3.0, 0.0, 0.0, 0.0, 1.0],
+            vec![0.0, 1.0, 0.0, 4.0, 0.0],
+            vec![0.0, 0.0, 5.0, 0.0, 2.0],
+            vec![1.0, 0.0, 1.0, 0.0, 0.0],
+        ]);
+        let a = CsrMatrix::from_array(&dense);
+        let b = Array::new_mat(vec![
+            vec![1.0, 0.0],
+            vec![2.0, 1.0],
+            vec![3.0, 0.0],
+            vec![4.0, 1.0],
+            vec![5.0, 0.0],
+        ]);
+        for ordering in [FillReducingOrdering::Natural, FillReducingOrdering::ReverseCuthillMcKee, FillReducingOrdering::MinimumDegree] {
+            let lu = SparseLu::new(&a, ordering).unwrap();
+            let x = lu.solve(b.clone());
+            assert!((&b - &(&dense * &x)).norm_max() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn lu_nonsymmetric_and_refactorize() {
+        let n = 30;
+        let mut coo = CooMatrix::new((n, n));
+        for i in 0..n {
+            coo.push(i, i, 3.0 + (i % 4) as f64);
+            coo.push(i, (i + 7) % n, -1.0);
+            coo.push((i + 3) % n, i, 0.5);
+        }
+        let a = coo.to_csr();
+        let b = rhs(n);
+        let mut lu = SparseLu::new(&a, FillReducingOrdering::MinimumDegree).unwrap();
+        assert!((&b - &(&a * &lu.solve(b.clone()))).norm() < 1e-12);
+        let scaled = &a + &a;
+        lu.refactorize(&scaled).unwrap();
+        assert!((&b - &(&scaled * &lu.solve(b.clone()))).norm() < 1e-12);
+
+        let singular = CsrMatrix::from_array(&Array::new_mat(vec![vec![1.0, 2.0], vec![2.0, 4.0]]));
+        assert!(matches!(SparseLu::new(&singular, FillReducingOrdering::Natural), Err(AlgaeError::Singular)));
+    }
+}
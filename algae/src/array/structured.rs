@@ -0,0 +1,1043 @@
+use crate::array::array::Array;
+use crate::array::checks::{check_index, check_square};
+use crate::array::factorizations::LdltFactorization;
+use crate::array::krylov::LinearOperator;
+use crate::error::error::AlgaeError;
+use std::ops::{Add, Sub, Mul, Div};
+use num::traits::{Float, Zero};
+
+// Sizes follow the convention of Array::size, i.e. (width, height), indices are (row, col).
+// Every type stores only the entries its structure allows to be nonzero.
+
+// Diagonal matrix, only the diagonal is kept.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagonal<T> {
+    diagonal:Vec<T>,
+    pub size:(usize, usize),
+}
+
+// Upper triangular matrix, row i holds the columns i..n, packed one row after another.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UpperTriangular<T> {
+    packed:Vec<T>,
+    pub size:(usize, usize),
+}
+
+// Lower triangular matrix, row i holds the columns 0..=i, packed one row after another.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LowerTriangular<T> {
+    packed:Vec<T>,
+    pub size:(usize, usize),
+}
+
+// Square matrix that is zero outside of `lower` sub- and `upper` superdiagonals.
+// Row i holds the columns i - lower..=i + upper, positions outside of the matrix are zero.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Banded<T> {
+    bands:Vec<T>,
+    lower:usize,
+    upper:usize,
+    pub size:(usize, usize),
+}
+
+// Symmetric matrix, stored as its lower triangle.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Symmetric<T> {
+    lower:LowerTriangular<T>,
+    pub size:(usize, usize),
+}
+
+// Matrix that is constant along its diagonals: entry (i, j) is first_col[i - j] below
+// and first_row[j - i] above the diagonal.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Toeplitz<T> {
+    first_col:Vec<T>,
+    first_row:Vec<T>,
+    pub size:(usize, usize),
+}
+
+// Banded LU factorization with partial pivoting. The row interchanges let U grow to
+// lower + upper superdiagonals, so the rows are stored with that width from the start.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BandedLu<T> {
+    factors:Vec<T>,
+    pivots:Vec<usize>,
+    lower:usize,
+    upper:usize,
+    pub size:(usize, usize),
+}
+
+fn check_rhs<T>(size:(usize, usize), b:&Array<T>) -> Result<(), AlgaeError> {
+    if b.size.1 != size.1 {
+        return Err(AlgaeError::ShapeMismatch {
+            expected:(b.size.0, size.1),
+            found:b.size,
+        });
+    }
+    Ok(())
+}
+
+fn check_mul<T>(size:(usize, usize), other:&Array<T>) -> Result<(), AlgaeError> {
+    if size.0 != other.size.1 {
+        return Err(AlgaeError::ShapeMismatch {
+            expected:(other.size.0, size.0),
+            found:other.size,
+        });
+    }
+    Ok(())
+}
+
+impl<T: Copy + Clone + Zero> Diagonal<T> {
+    pub fn new(diagonal:Vec<T>) -> Self {
+        let n = diagonal.len();
+        Diagonal {
+            diagonal,
+            size:(n, n),
+        }
+    }
+
+    // Takes the diagonal of a square array, everything else is ignored.
+    pub fn from_array(array:&Array<T>) -> Result<Self, AlgaeError> {
+        check_square(array.size)?;
+        Ok(Diagonal::new((0..array.size.0).map(|i| array[(i, i)]).collect()))
+    }
+
+    pub fn diagonal(&self) -> &[T] {
+        &self.diagonal
+    }
+
+    pub fn get(&self, row:usize, col:usize) -> T {
+        if let Err(e) = check_index((row, col), self.size) {
+            panic!("{}", e);
+        }
+        if row == col { self.diagonal[row] } else { T::zero() }
+    }
+
+    pub fn to_array(&self) -> Array<T> {
+        let mut array = Array::new_filled(self.size, T::zero());
+        for (i, value) in self.diagonal.iter().enumerate() {
+            array[(i, i)] = *value;
+        }
+        array
+    }
+}
+
+impl<T: Copy + Clone + Zero + Add<Output = T> + Mul<Output = T>> Diagonal<T> {
+    // Scales the rows of other, O(n * k).
+    pub fn try_mul_dense(&self, other:&Array<T>) -> Result<Array<T>, AlgaeError> {
+        check_mul(self.size, other)?;
+        let mut array = other.clone();
+        for row in 0..other.size.1 {
+            for col in 0..other.size.0 {
+                array[(row, col)] = self.diagonal[row] * other[(row, col)];
+            }
+        }
+        Ok(array)
+    }
+}
+
+impl<T: Copy + Clone + Zero + Sub<Output = T> + Mul<Output = T> + Div<Output = T>> Diagonal<T> {
+    pub fn solve(&self, b:Array<T>) -> Array<T> {
+        match self.try_solve(b) {
+            Ok(x) => x,
+            Err(e) => panic!("Couldn't solve the diagonal system. {}", e),
+        }
+    }
+
+    pub fn try_solve(&self, mut b:Array<T>) -> Result<Array<T>, AlgaeError> {
+        check_rhs(self.size, &b)?;
+        if self.diagonal.iter().any(|d| d.is_zero()) {
+            return Err(AlgaeError::Singular);
+        }
+        for row in 0..b.size.1 {
+            for col in 0..b.size.0 {
+                b[(row, col)] = b[(row, col)] / self.diagonal[row];
+            }
+        }
+        Ok(b)
+    }
+}
+
+impl<T: Copy + Clone + Zero> UpperTriangular<T> {
+    // `rows[i]` holds the columns i..n of row i.
+    pub fn new(rows:Vec<Vec<T>>) -> Result<Self, AlgaeError> {
+        let n = rows.len();
+        let mut packed = Vec::<T>::with_capacity(n * (n + 1) / 2);
+        for (i, row) in rows.iter().enumerate() {
+            if row.len() != n - i {
+                return Err(AlgaeError::ShapeMismatch {
+                    expected:(n - i, 1),
+                    found:(row.len(), 1),
+                });
+            }
+            packed.extend_from_slice(row);
+        }
+        Ok(UpperTriangular {
+            packed,
+            size:(n, n),
+        })
+    }
+
+    // Takes the upper triangle of a square array, the entries below the diagonal are ignored.
+    pub fn from_array(array:&Array<T>) -> Result<Self, AlgaeError> {
+        check_square(array.size)?;
+        let n = array.size.0;
+        UpperTriangular::new((0..n).map(|row| (row..n).map(|col| array[(row, col)]).collect()).collect())
+    }
+
+    fn offset(&self, row:usize) -> usize {
+        row * self.size.0 - row * row.saturating_sub(1) / 2
+    }
+
+    pub fn get(&self, row:usize, col:usize) -> T {
+        if let Err(e) = check_index((row, col), self.size) {
+            panic!("{}", e);
+        }
+        if col < row { T::zero() } else { self.packed[self.offset(row) + col - row] }
+    }
+
+    pub fn transpose(&self) -> LowerTriangular<T> {
+        let n = self.size.0;
+        LowerTriangular::new((0..n).map(|row| (0..=row).map(|col| self.get(col, row)).collect()).collect())
+            .unwrap_or_else(|e| panic!("Faulty implementation: {}", e))
+    }
+
+    pub fn to_array(&self) -> Array<T> {
+        let mut array = Array::new_filled(self.size, T::zero());
+        for row in 0..self.size.1 {
+            for col in row..self.size.0 {
+                array[(row, col)] = self.packed[self.offset(row) + col - row];
+            }
+        }
+        array
+    }
+}
+
+impl<T: Copy + Clone + Zero + Add<Output = T> + Mul<Output = T>> UpperTriangular<T> {
+    pub fn try_mul_dense(&self, other:&Array<T>) -> Result<Array<T>, AlgaeError> {
+        check_mul(self.size, other)?;
+        let mut array = Array::new_filled((other.size.0, self.size.1), T::zero());
+        for row in 0..self.size.1 {
+            let start = self.offset(row);
+            for k in row..self.size.0 {
+                let value = self.packed[start + k - row];
+                for col in 0..other.size.0 {
+                    array[(row, col)] = array[(row, col)] + value * other[(k, col)];
+                }
+            }
+        }
+        Ok(array)
+    }
+}
+
+impl<T: Copy + Clone + Zero + Sub<Output = T> + Mul<Output = T> + Div<Output = T>> UpperTriangular<T> {
+    pub fn solve(&self, b:Array<T>) -> Array<T> {
+        match self.try_solve(b) {
+            Ok(x) => x,
+            Err(e) => panic!("Couldn't solve the triangular system. {}", e),
+        }
+    }
+
+    // Back substitution, O(n^2) per right hand side.
+    pub fn try_solve(&self, mut b:Array<T>) -> Result<Array<T>, AlgaeError> {
+        check_rhs(self.size, &b)?;
+        let n = self.size.0;
+        for row in (0..n).rev() {
+            let start = self.offset(row);
+            let pivot = self.packed[start];
+            if pivot.is_zero() {
+                return Err(AlgaeError::Singular);
+            }
+            for col in 0..b.size.0 {
+                let mut sum = b[(row, col)];
+                for k in (row + 1)..n {
+                    sum = sum - self.packed[start + k - row] * b[(k, col)];
+                }
+                b[(row, col)] = sum / pivot;
+            }
+        }
+        Ok(b)
+    }
+}
+
+impl<T: Copy + Clone + Zero> LowerTriangular<T> {
+    // `rows[i]` holds the columns 0..=i of row i.
+    pub fn new(rows:Vec<Vec<T>>) -> Result<Self, AlgaeError> {
+        let n = rows.len();
+        let mut packed = Vec::<T>::with_capacity(n * (n + 1) / 2);
+        for (i, row) in rows.iter().enumerate() {
+            if row.len() != i + 1 {
+                return Err(AlgaeError::ShapeMismatch {
+                    expected:(i + 1, 1),
+                    found:(row.len(), 1),
+                });
+            }
+            packed.extend_from_slice(row);
+        }
+        Ok(LowerTriangular {
+            packed,
+            size:(n, n),
+        })
+    }
+
+    // Takes the lower triangle of a square array, the entries above the diagonal are ignored.
+    pub fn from_array(array:&Array<T>) -> Result<Self, AlgaeError> {
+        check_square(array.size)?;
+        let n = array.size.0;
+        LowerTriangular::new((0..n).map(|row| (0..=row).map(|col| array[(row, col)]).collect()).collect())
+    }
+
+    fn offset(&self, row:usize) -> usize {
+        row * (row + 1) / 2
+    }
+
+    pub fn get(&self, row:usize, col:usize) -> T {
+        if let Err(e) = check_index((row, col), self.size) {
+            panic!("{}", e);
+        }
+        if col > row { T::zero() } else { self.packed[self.offset(row) + col] }
+    }
+
+    pub fn transpose(&self) -> UpperTriangular<T> {
+        let n = self.size.0;
+        UpperTriangular::new((0..n).map(|row| (row..n).map(|col| self.get(col, row)).collect()).collect())
+            .unwrap_or_else(|e| panic!("Faulty implementation: {}", e))
+    }
+
+    pub fn to_array(&self) -> Array<T> {
+        let mut array = Array::new_filled(self.size, T::zero());
+        for row in 0..self.size.1 {
+            for col in 0..=row {
+                array[(row, col)] = self.packed[self.offset(row) + col];
+            }
+        }
+        array
+    }
+}
+
+impl<T: Copy + Clone + Zero + Add<Output = T> + Mul<Output = T>> LowerTriangular<T> {
+    pub fn try_mul_dense(&self, other:&Array<T>) -> Result<Array<T>, AlgaeError> {
+        check_mul(self.size, other)?;
+        let mut array = Array::new_filled((other.size.0, self.size.1), T::zero());
+        for row in 0..self.size.1 {
+            let start = self.offset(row);
+            for k in 0..=row {
+                let value = self.packed[start + k];
+                for col in 0..other.size.0 {
+                    array[(row, col)] = array[(row, col)] + value * other[(k, col)];
+                }
+            }
+        }
+        Ok(array)
+    }
+}
+
+impl<T: Copy + Clone + Zero + Sub<Output = T> + Mul<Output = T> + Div<Output = T>> LowerTriangular<T> {
+    pub fn solve(&self, b:Array<T>) -> Array<T> {
+        match self.try_solve(b) {
+            Ok(x) => x,
+            Err(e) => panic!("Couldn't solve the triangular system. {}", e),
+        }
+    }
+
+    // Forward substitution, O(n^2) per right hand side.
+    pub fn try_solve(&self, mut b:Array<T>) -> Result<Array<T>, AlgaeError> {
+        check_rhs(self.size, &b)?;
+        for row in 0..self.size.0 {
+            let start = self.offset(row);
+            let pivot = self.packed[start + row];
+            if pivot.is_zero() {
+                return Err(AlgaeError::Singular);
+            }
+            for col in 0..b.size.0 {
+                let mut sum = b[(row, col)];
+                for k in 0..row {
+                    sum = sum - self.packed[start + k] * b[(k, col)];
+                }
+                b[(row, col)] = sum / pivot;
+            }
+        }
+        Ok(b)
+    }
+}
+
+impl<T: Copy + Clone + Zero> Banded<T> {
+    // `rows[i]` holds the columns i - lower..=i + upper of row i, including the positions
+    // that lie outside of the matrix, which have to be zero.
+    pub fn new(lower:usize, upper:usize, rows:Vec<Vec<T>>) -> Result<Self, AlgaeError> {
+        let n = rows.len();
+        let width = lower + upper + 1;
+        let mut bands = Vec::<T>::with_capacity(n * width);
+        for row in rows.iter() {
+            if row.len() != width {
+                return Err(AlgaeError::ShapeMismatch {
+                    expected:(width, 1),
+                    found:(row.len(), 1),
+                });
+            }
+            bands.extend_from_slice(row);
+        }
+        Ok(Banded {
+            bands,
+            lower,
+            upper,
+            size:(n, n),
+        })
+    }
+
+    // Takes the bands of a square array, everything outside of them is ignored.
+    pub fn from_array(array:&Array<T>, lower:usize, upper:usize) -> Result<Self, AlgaeError> {
+        check_square(array.size)?;
+        let n = array.size.0;
+        let rows = (0..n)
+            .map(|row| (0..(lower + upper + 1))
+                .map(|k| match (row + k).checked_sub(lower) {
+                    Some(col) if col < n => array[(row, col)],
+                    _ => T::zero(),
+                })
+                .collect())
+            .collect();
+        Banded::new(lower, upper, rows)
+    }
+
+    pub fn bandwidth(&self) -> (usize, usize) {
+        (self.lower, self.upper)
+    }
+
+    // Column range of the band in the given row.
+    fn columns(&self, row:usize) -> std::ops::Range<usize> {
+        row.saturating_sub(self.lower)..usize::min(self.size.0, row + self.upper + 1)
+    }
+
+    fn at(&self, row:usize, col:usize) -> usize {
+        row * (self.lower + self.upper + 1) + col + self.lower - row
+    }
+
+    pub fn get(&self, row:usize, col:usize) -> T {
+        if let Err(e) = check_index((row, col), self.size) {
+            panic!("{}", e);
+        }
+        if self.columns(row).contains(&col) { self.bands[self.at(row, col)] } else { T::zero() }
+    }
+
+    pub fn to_array(&self) -> Array<T> {
+        let mut array = Array::new_filled(self.size, T::zero());
+        for row in 0..self.size.1 {
+            for col in self.columns(row) {
+                array[(row, col)] = self.bands[self.at(row, col)];
+            }
+        }
+        array
+    }
+}
+
+impl<T: Copy + Clone + Zero + Add<Output = T> + Mul<Output = T>> Banded<T> {
+    pub fn try_mul_dense(&self, other:&Array<T>) -> Result<Array<T>, AlgaeError> {
+        check_mul(self.size, other)?;
+        let mut array = Array::new_filled((other.size.0, self.size.1), T::zero());
+        for row in 0..self.size.1 {
+            for k in self.columns(row) {
+                let value = self.bands[self.at(row, k)];
+                for col in 0..other.size.0 {
+                    array[(row, col)] = array[(row, col)] + value * other[(k, col)];
+                }
+            }
+        }
+        Ok(array)
+    }
+}
+
+impl<T: Float> Banded<T> {
+    // Gaussian elimination with partial pivoting inside the band, O(n * lower * (lower + upper)).
+    pub fn lu(&self) -> Result<BandedLu<T>, AlgaeError> {
+        let n = self.size.0;
+        let (lower, upper) = (self.lower, self.upper + self.lower);
+        let width = lower + upper + 1;
+        let at = |row:usize, col:usize| row * width + col + lower - row;
+        let mut factors = vec![T::zero(); n * width];
+        for row in 0..n {
+            for col in self.columns(row) {
+                factors[at(row, col)] = self.bands[self.at(row, col)];
+            }
+        }
+        let mut pivots = Vec::<usize>::with_capacity(n);
+        for k in 0..n {
+            let last_row = usize::min(n - 1, k + lower);
+            let last_col = usize::min(n - 1, k + upper);
+            let mut pivot_row = k;
+            for row in (k + 1)..=last_row {
+                if factors[at(row, k)].abs() > factors[at(pivot_row, k)].abs() {
+                    pivot_row = row;
+                }
+            }
+            pivots.push(pivot_row);
+            if factors[at(pivot_row, k)] == T::zero() {
+                return Err(AlgaeError::ZeroPivot {
+                    index:k,
+                });
+            }
+            if pivot_row != k {
+                for col in k..=last_col {
+                    factors.swap(at(k, col), at(pivot_row, col));
+                }
+            }
+            for row in (k + 1)..=last_row {
+                let factor = factors[at(row, k)] / factors[at(k, k)];
+                factors[at(row, k)] = factor;
+                for col in (k + 1)..=last_col {
+                    factors[at(row, col)] = factors[at(row, col)] - factor * factors[at(k, col)];
+                }
+            }
+        }
+        Ok(BandedLu {
+            factors,
+            pivots,
+            lower,
+            upper,
+            size:self.size,
+        })
+    }
+
+    pub fn solve(&self, b:Array<T>) -> Array<T> {
+        match self.try_solve(b) {
+            Ok(x) => x,
+            Err(e) => panic!("Couldn't solve the banded system. {}", e),
+        }
+    }
+
+    pub fn try_solve(&self, b:Array<T>) -> Result<Array<T>, AlgaeError> {
+        check_rhs(self.size, &b)?;
+        self.lu()?.try_solve(b)
+    }
+}
+
+impl<T: Float> BandedLu<T> {
+    fn at(&self, row:usize, col:usize) -> usize {
+        row * (self.lower + self.upper + 1) + col + self.lower - row
+    }
+
+    pub fn pivots(&self) -> &[usize] {
+        &self.pivots
+    }
+
+    pub fn solve(&self, b:Array<T>) -> Array<T> {
+        match self.try_solve(b) {
+            Ok(x) => x,
+            Err(e) => panic!("Couldn't solve the banded system. {}", e),
+        }
+    }
+
+    pub fn try_solve(&self, mut b:Array<T>) -> Result<Array<T>, AlgaeError> {
+        check_rhs(self.size, &b)?;
+        let n = self.size.0;
+        for col in 0..b.size.0 {
+            for k in 0..n {
+                let p = self.pivots[k];
+                if p != k {
+                    let temp = b[(k, col)];
+                    b[(k, col)] = b[(p, col)];
+                    b[(p, col)] = temp;
+                }
+                for row in (k + 1)..usize::min(n, k + self.lower + 1) {
+                    b[(row, col)] = b[(row, col)] - self.factors[self.at(row, k)] * b[(k, col)];
+                }
+            }
+            for row in (0..n).rev() {
+                let mut sum = b[(row, col)];
+                for k in (row + 1)..usize::min(n, row + self.upper + 1) {
+                    sum = sum - self.factors[self.at(row, k)] * b[(k, col)];
+                }
+                b[(row, col)] = sum / self.factors[self.at(row, row)];
+            }
+        }
+        Ok(b)
+    }
+}
+
+impl<T: Copy + Clone + Zero> Symmetric<T> {
+    pub fn new(lower:LowerTriangular<T>) -> Self {
+        let size = lower.size;
+        Symmetric {
+            lower,
+            size,
+        }
+    }
+
+    // Takes the lower triangle of a square array and mirrors it, the upper triangle is ignored.
+    pub fn from_array(array:&Array<T>) -> Result<Self, AlgaeError> {
+        Ok(Symmetric::new(LowerTriangular::from_array(array)?))
+    }
+
+    pub fn get(&self, row:usize, col:usize) -> T {
+        if row >= col { self.lower.get(row, col) } else { self.lower.get(col, row) }
+    }
+
+    pub fn to_array(&self) -> Array<T> {
+        let mut array = Array::new_filled(self.size, T::zero());
+        for row in 0..self.size.1 {
+            for col in 0..=row {
+                let value = self.lower.get(row, col);
+                array[(row, col)] = value;
+                array[(col, row)] = value;
+            }
+        }
+        array
+    }
+}
+
+impl<T: Copy + Clone + Zero + Add<Output = T> + Mul<Output = T>> Symmetric<T> {
+    // Every stored entry is used twice, for (i, j) and for (j, i).
+    pub fn try_mul_dense(&self, other:&Array<T>) -> Result<Array<T>, AlgaeError> {
+        check_mul(self.size, other)?;
+        let mut array = Array::new_filled((other.size.0, self.size.1), T::zero());
+        for row in 0..self.size.1 {
+            let start = self.lower.offset(row);
+            for k in 0..=row {
+                let value = self.lower.packed[start + k];
+                for col in 0..other.size.0 {
+                    array[(row, col)] = array[(row, col)] + value * other[(k, col)];
+                    if k != row {
+                        array[(k, col)] = array[(k, col)] + value * other[(row, col)];
+                    }
+                }
+            }
+        }
+        Ok(array)
+    }
+}
+
+impl<T: Float> Symmetric<T> {
+    // Bunch-Kaufman pivoting on a copy of the packed lower triangle, without ever forming the
+    // dense matrix.
+    pub fn ldlt(&self) -> LdltFactorization<T> {
+        LdltFactorization::from_packed(self.lower.packed.clone(), self.size.0)
+    }
+
+    pub fn solve(&self, b:Array<T>) -> Array<T> {
+        match self.try_solve(b) {
+            Ok(x) => x,
+            Err(e) => panic!("Couldn't solve the symmetric system. {}", e),
+        }
+    }
+
+    pub fn try_solve(&self, b:Array<T>) -> Result<Array<T>, AlgaeError> {
+        check_rhs(self.size, &b)?;
+        self.ldlt().try_solve(b)
+    }
+}
+
+impl<T: Copy + Clone + Zero> Toeplitz<T> {
+    // A first_col.len() x first_row.len() matrix. The diagonal is taken from first_col,
+    // first_row[0] is ignored.
+    pub fn new(first_col:Vec<T>, first_row:Vec<T>) -> Self {
+        Toeplitz {
+            size:(first_row.len(), first_col.len()),
+            first_col,
+            first_row,
+        }
+    }
+
+    pub fn symmetric(first_col:Vec<T>) -> Self {
+        let first_row = first_col.clone();
+        Toeplitz::new(first_col, first_row)
+    }
+
+    pub fn get(&self, row:usize, col:usize) -> T {
+        if let Err(e) = check_index((row, col), self.size) {
+            panic!("{}", e);
+        }
+        if row >= col { self.first_col[row - col] } else { self.first_row[col - row] }
+    }
+
+    pub fn to_array(&self) -> Array<T> {
+        let mut array = Array::new_filled(self.size, T::zero());
+        for row in 0..self.size.1 {
+            for col in 0..self.size.0 {
+                array[(row, col)] = self.get(row, col);
+            }
+        }
+        array
+    }
+}
+
+impl<T: Copy + Clone + Zero + Add<Output = T> + Mul<Output = T>> Toeplitz<T> {
+    pub fn try_mul_dense(&self, other:&Array<T>) -> Result<Array<T>, AlgaeError> {
+        check_mul(self.size, other)?;
+        let mut array = Array::new_filled((other.size.0, self.size.1), T::zero());
+        for row in 0..self.size.1 {
+            for k in 0..self.size.0 {
+                let value = self.get(row, k);
+                for col in 0..other.size.0 {
+                    array[(row, col)] = array[(row, col)] + value * other[(k, col)];
+                }
+            }
+        }
+        Ok(array)
+    }
+}
+
+impl<T: Copy + Clone + Zero + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Div<Output = T>> Toeplitz<T> {
+    pub fn solve(&self, b:Array<T>) -> Array<T> {
+        match self.try_solve(b) {
+            Ok(x) => x,
+            Err(e) => panic!("Couldn't solve the Toeplitz system. {}", e),
+        }
+    }
+
+    // Levinson recursion for general square Toeplitz matrices, O(n^2) per right hand side.
+    // It works on the leading principal submatrices, so a singular one is reported as a
+    // zero pivot even if the whole matrix is regular.
+    pub fn try_solve(&self, b:Array<T>) -> Result<Array<T>, AlgaeError> {
+        if self.size.0 != self.size.1 {
+            return Err(AlgaeError::NotSquare {
+                size:self.size,
+            });
+        }
+        check_rhs(self.size, &b)?;
+        let mut x = Array::new_filled(b.size, T::zero());
+        for col in 0..b.size.0 {
+            let y = (0..b.size.1).map(|row| b[(row, col)]).collect::<Vec<T>>();
+            for (row, value) in self.levinson(&y)?.into_iter().enumerate() {
+                x[(row, col)] = value;
+            }
+        }
+        Ok(x)
+    }
+
+    // The entry on the diagonal with offset row - col.
+    fn diagonal(&self, offset:isize) -> T {
+        if offset >= 0 { self.first_col[offset as usize] } else { self.first_row[offset.unsigned_abs()] }
+    }
+
+    // g and h solve the forward and backward systems of growing order, x is updated from them.
+    #[allow(clippy::needless_range_loop)]
+    fn levinson(&self, y:&[T]) -> Result<Vec<T>, AlgaeError> {
+        let n = y.len();
+        let r = |offset:isize| self.diagonal(offset);
+        let mut x = vec![T::zero(); n];
+        if n == 0 {
+            return Ok(x);
+        }
+        if r(0).is_zero() {
+            return Err(AlgaeError::ZeroPivot {
+                index:0,
+            });
+        }
+        x[0] = y[0] / r(0);
+        if n == 1 {
+            return Ok(x);
+        }
+        let mut g = vec![T::zero(); n];
+        let mut h = vec![T::zero(); n];
+        g[0] = r(-1) / r(0);
+        h[0] = r(1) / r(0);
+        for m in 1..n {
+            let mut numerator = T::zero() - y[m];
+            let mut denominator = T::zero() - r(0);
+            for j in 0..m {
+                numerator = numerator + r((m - j) as isize) * x[j];
+                denominator = denominator + r((m - j) as isize) * g[m - 1 - j];
+            }
+            if denominator.is_zero() {
+                return Err(AlgaeError::ZeroPivot {
+                    index:m,
+                });
+            }
+            x[m] = numerator / denominator;
+            for j in 0..m {
+                x[j] = x[j] - x[m] * g[m - 1 - j];
+            }
+            if m + 1 == n {
+                break;
+            }
+            let mut g_numerator = T::zero() - r(-(m as isize + 1));
+            let mut h_numerator = T::zero() - r(m as isize + 1);
+            let mut g_denominator = T::zero() - r(0);
+            for j in 0..m {
+                g_numerator = g_numerator + r(j as isize - m as isize) * g[j];
+                h_numerator = h_numerator + r((m - j) as isize) * h[j];
+                g_denominator = g_denominator + r(j as isize - m as isize) * h[m - 1 - j];
+            }
+            if g_denominator.is_zero() {
+                return Err(AlgaeError::ZeroPivot {
+                    index:m,
+                });
+            }
+            g[m] = g_numerator / g_denominator;
+            h[m] = h_numerator / denominator;
+            let (gm, hm) = (g[m], h[m]);
+            let mut k = m - 1;
+            for j in 0..m.div_ceil(2) {
+                let (g_j, g_k, h_j, h_k) = (g[j], g[k], h[j], h[k]);
+                g[j] = g_j - gm * h_k;
+                g[k] = g_k - gm * h_j;
+                h[j] = h_j - hm * g_k;
+                h[k] = h_k - hm * g_j;
+                k = k.saturating_sub(1);
+            }
+        }
+        Ok(x)
+    }
+}
+
+impl<'b, T: Copy + Clone + Zero + Add<Output = T> + Mul<Output = T>> Mul<&'b Array<T>> for &Diagonal<T> {
+    type Output = Array<T>;
+    fn mul(self, other:&'b Array<T>) -> Array<T> {
+        match self.try_mul_dense(other) {
+            Ok(array) => array,
+            Err(e) => panic!("To multiply two matrices the first width has to be equal to the seconds height. {}", e),
+        }
+    }
+}
+
+impl<'b, T: Copy + Clone + Zero + Add<Output = T> + Mul<Output = T>> Mul<&'b Array<T>> for &UpperTriangular<T> {
+    type Output = Array<T>;
+    fn mul(self, other:&'b Array<T>) -> Array<T> {
+        match self.try_mul_dense(other) {
+            Ok(array) => array,
+            Err(e) => panic!("To multiply two matrices the first width has to be equal to the seconds height. {}", e),
+        }
+    }
+}
+
+impl<'b, T: Copy + Clone + Zero + Add<Output = T> + Mul<Output = T>> Mul<&'b Array<T>> for &LowerTriangular<T> {
+    type Output = Array<T>;
+    fn mul(self, other:&'b Array<T>) -> Array<T> {
+        match self.try_mul_dense(other) {
+            Ok(array) => array,
+            Err(e) => panic!("To multiply two matrices the first width has to be equal to the seconds height. {}", e),
+        }
+    }
+}
+
+impl<'b, T: Copy + Clone + Zero + Add<Output = T> + Mul<Output = T>> Mul<&'b Array<T>> for &Banded<T> {
+    type Output = Array<T>;
+    fn mul(self, other:&'b Array<T>) -> Array<T> {
+        match self.try_mul_dense(other) {
+            Ok(array) => array,
+            Err(e) => panic!("To multiply two matrices the first width has to be equal to the seconds height. {}", e),
+        }
+    }
+}
+
+impl<'b, T: Copy + Clone + Zero + Add<Output = T> + Mul<Output = T>> Mul<&'b Array<T>> for &Symmetric<T> {
+    type Output = Array<T>;
+    fn mul(self, other:&'b Array<T>) -> Array<T> {
+        match self.try_mul_dense(other) {
+            Ok(array) => array,
+            Err(e) => panic!("To multiply two matrices the first width has to be equal to the seconds height. {}", e),
+        }
+    }
+}
+
+impl<'b, T: Copy + Clone + Zero + Add<Output = T> + Mul<Output = T>> Mul<&'b Array<T>> for &Toeplitz<T> {
+    type Output = Array<T>;
+    fn mul(self, other:&'b Array<T>) -> Array<T> {
+        match self.try_mul_dense(other) {
+            Ok(array) => array,
+            Err(e) => panic!("To multiply two matrices the first width has to be equal to the seconds height. {}", e),
+        }
+    }
+}
+
+impl<T: Copy + Clone + Zero + Add<Output = T> + Mul<Output = T>> LinearOperator<T> for Diagonal<T> {
+    fn dimension(&self) -> usize {
+        self.size.1
+    }
+
+    fn apply(&self, x:&Array<T>) -> Array<T> {
+        self * x
+    }
+}
+
+impl<T: Copy + Clone + Zero + Add<Output = T> + Mul<Output = T>> LinearOperator<T> for Banded<T> {
+    fn dimension(&self) -> usize {
+        self.size.1
+    }
+
+    fn apply(&self, x:&Array<T>) -> Array<T> {
+        self * x
+    }
+}
+
+impl<T: Copy + Clone + Zero + Add<Output = T> + Mul<Output = T>> LinearOperator<T> for UpperTriangular<T> {
+    fn dimension(&self) -> usize {
+        self.size.1
+    }
+
+    fn apply(&self, x:&Array<T>) -> Array<T> {
+        self * x
+    }
+}
+
+impl<T: Copy + Clone + Zero + Add<Output = T> + Mul<Output = T>> LinearOperator<T> for LowerTriangular<T> {
+    fn dimension(&self) -> usize {
+        self.size.1
+    }
+
+    fn apply(&self, x:&Array<T>) -> Array<T> {
+        self * x
+    }
+}
+
+impl<T: Copy + Clone + Zero + Add<Output = T> + Mul<Output = T>> LinearOperator<T> for Symmetric<T> {
+    fn dimension(&self) -> usize {
+        self.size.1
+    }
+
+    fn apply(&self, x:&Array<T>) -> Array<T> {
+        self * x
+    }
+}
+
+// The only structured type that may be rectangular.
+impl<T: Copy + Clone + Zero + Add<Output = T> + Mul<Output = T>> LinearOperator<T> for Toeplitz<T> {
+    fn dimension(&self) -> usize {
+        self.size.1
+    }
+
+    fn size(&self) -> (usize, usize) {
+        self.size
+    }
+
+    fn apply(&self, x:&Array<T>) -> Array<T> {
+        self * x
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::array::structured::{Diagonal, UpperTriangular, LowerTriangular, Banded, Symmetric, Toeplitz};
+    use crate::array::structured::Array;
+    use crate::array::checks::assert_close;
+    use crate::array::factorizations::LdltFactorization;
+    use crate::array::krylov::{conjugate_gradient, gmres, SolverOptions};
+    use crate::error::error::AlgaeError;
+
+    #[test]
+    fn diagonal() {
+        let d = Diagonal::new(vec![2.0, -1.0, 4.0]);
+        let b = Array::new_mat(vec![vec![1.0, 2.0], vec![3.0, 4.0], vec![5.0, 6.0]]);
+        assert_eq!(d.to_array() * b.clone(), &d * &b);
+        assert_close(&b, &(&d * &d.solve(b.clone())), 1e-12);
+        assert_eq!(Err(AlgaeError::Singular), Diagonal::new(vec![1.0, 0.0]).try_solve(Array::new_vec(vec![1.0, 1.0])));
+        assert_eq!(d, Diagonal::from_array(&d.to_array()).unwrap());
+    }
+
+    #[test]
+    fn triangular() {
+        let a = Array::new_mat(vec![
+            vec![2.0, 1.0, -1.0],
+            vec![4.0, 3.0, 0.5],
+            vec![-2.0, 6.0, 5.0],
+        ]);
+        let upper = UpperTriangular::from_array(&a).unwrap();
+        let lower = LowerTriangular::from_array(&a).unwrap();
+        let expected_upper = Array::new_mat(vec![vec![2.0, 1.0, -1.0], vec![0.0, 3.0, 0.5], vec![0.0, 0.0, 5.0]]);
+        let expected_lower = Array::new_mat(vec![vec![2.0, 0.0, 0.0], vec![4.0, 3.0, 0.0], vec![-2.0, 6.0, 5.0]]);
+        assert_eq!(expected_upper, upper.to_array());
+        assert_eq!(expected_lower, lower.to_array());
+        assert_eq!(expected_upper.transpose(), upper.transpose().to_array());
+        assert_eq!(expected_lower.transpose(), lower.transpose().to_array());
+
+        let b = Array::new_mat(vec![vec![1.0, 0.0], vec![2.0, 1.0], vec![3.0, -1.0]]);
+        assert_eq!(expected_upper.clone() * b.clone(), &upper * &b);
+        assert_eq!(expected_lower.clone() * b.clone(), &lower * &b);
+        assert_close(&b, &(&upper * &upper.solve(b.clone())), 1e-12);
+        assert_close(&b, &(&lower * &lower.solve(b.clone())), 1e-12);
+        let rhs = Array::new_vec(vec![1.0, 2.0, 3.0]);
+        let (x, _) = gmres(&upper, &rhs, None, 3, None, &SolverOptions::default()).unwrap();
+        assert_close(&upper.solve(rhs.clone()), &x, 1e-12);
+        let (x, _) = gmres(&lower, &rhs, None, 3, None, &SolverOptions::default()).unwrap();
+        assert_close(&lower.solve(rhs.clone()), &x, 1e-12);
+
+        assert!(UpperTriangular::new(vec![vec![1.0, 2.0], vec![3.0, 4.0]]).is_err());
+        let singular = LowerTriangular::new(vec![vec![1.0], vec![2.0, 0.0]]).unwrap();
+        assert_eq!(Err(AlgaeError::Singular), singular.try_solve(Array::new_vec(vec![1.0, 1.0])));
+    }
+
+    #[test]
+    fn banded() {
+        // Tridiagonal plus one extra superdiagonal, the first pivot forces a row interchange.
+        let a = Array::new_mat(vec![
+            vec![0.0, 2.0, 1.0, 0.0, 0.0],
+            vec![3.0, 1.0, 4.0, -1.0, 0.0],
+            vec![0.0, 1.0, -2.0, 3.0, 2.0],
+            vec![0.0, 0.0, 5.0, 1.0, 1.0],
+            vec![0.0, 0.0, 0.0, 2.0, 6.0],
+        ]);
+        let banded = Banded::from_array(&a, 1, 2).unwrap();
+        assert_eq!(a, banded.to_array());
+        assert_eq!((1, 2), banded.bandwidth());
+        let b = Array::new_mat(vec![vec![1.0], vec![-2.0], vec![0.5], vec![3.0], vec![1.0]]);
+        assert_eq!(a.clone() * b.clone(), &banded * &b);
+        let x = banded.solve(b.clone());
+        assert_close(&b, &(a * x), 1e-12);
+        assert_eq!(1, banded.lu().unwrap().pivots()[0]);
+
+        let singular = Banded::new(1, 1, vec![vec![0.0, 1.0, 1.0], vec![1.0, 1.0, 0.0]]).unwrap();
+        assert_eq!(Err(AlgaeError::ZeroPivot { index:1 }), singular.try_solve(Array::new_vec(vec![1.0, 1.0])));
+    }
+
+    #[test]
+    fn symmetric() {
+        let lower = LowerTriangular::new(vec![vec![4.0], vec![1.0, -3.0], vec![2.0, 0.5, 2.0]]).unwrap();
+        let s = Symmetric::new(lower);
+        let dense = s.to_array();
+        assert_eq!(dense.transpose(), dense);
+        assert_eq!(0.5, s.get(1, 2));
+        let b = Array::new_mat(vec![vec![1.0, 2.0], vec![0.0, 1.0], vec![-1.0, 3.0]]);
+        assert_eq!(dense.clone() * b.clone(), &s * &b);
+        assert_close(&b, &(&s * &s.solve(b.clone())), 1e-12);
+
+        // A zero diagonal forces a 2x2 pivot and a row interchange.
+        let indefinite = Symmetric::from_array(&Array::new_mat(vec![
+            vec![0.0, 1.0, 2.0, 0.0],
+            vec![1.0, 0.0, 3.0, 1.0],
+            vec![2.0, 3.0, 0.0, -1.0],
+            vec![0.0, 1.0, -1.0, 4.0],
+        ])).unwrap();
+        let ldlt = indefinite.ldlt();
+        assert_ne!(vec![0, 1, 2, 3], ldlt.permutation().to_vec());
+        let expected = LdltFactorization::new(indefinite.to_array()).unwrap();
+        assert_eq!(expected.permutation(), ldlt.permutation());
+        let b = Array::new_mat(vec![vec![1.0], vec![-2.0], vec![0.5], vec![3.0]]);
+        assert_close(&expected.solve(b.clone()), &ldlt.solve(b.clone()), 1e-12);
+        assert_close(&b, &(&indefinite * &indefinite.solve(b.clone())), 1e-12);
+
+        let singular = Symmetric::new(LowerTriangular::new(vec![vec![1.0], vec![1.0, 1.0]]).unwrap());
+        assert_eq!(Err(AlgaeError::Singular), singular.try_solve(Array::new_vec(vec![1.0, 0.0])));
+    }
+
+    #[test]
+    fn toeplitz() {
+        let t = Toeplitz::new(vec![4.0, 1.0, 0.5, 0.25], vec![4.0, -1.0, 2.0, 0.0]);
+        let dense = t.to_array();
+        assert_eq!(Array::new_mat(vec![
+            vec![4.0, -1.0, 2.0, 0.0],
+            vec![1.0, 4.0, -1.0, 2.0],
+            vec![0.5, 1.0, 4.0, -1.0],
+            vec![0.25, 0.5, 1.0, 4.0],
+        ]), dense);
+        let b = Array::new_mat(vec![vec![1.0, 0.0], vec![2.0, 1.0], vec![-1.0, 0.0], vec![0.5, 2.0]]);
+        assert_eq!(dense.clone() * b.clone(), &t * &b);
+        assert_close(&b, &(dense * t.solve(b.clone())), 1e-12);
+        assert_eq!(Toeplitz::new(vec![1.0, 2.0], vec![1.0, 5.0]).to_array(), Toeplitz::new(vec![1.0, 2.0], vec![3.0, 5.0]).to_array());
+
+        // Symmetric positive definite, usable as an operator for the Krylov solvers.
+        let kernel = Toeplitz::symmetric(vec![2.0, -1.0, 0.0, 0.0, 0.0]);
+        let b = Array::new_vec(vec![1.0, 0.0, 0.0, 0.0, 1.0]);
+        let x = kernel.solve(b.clone());
+        assert_close(&Array::new_vec(vec![1.0; 5]), &x, 1e-12);
+        let (cg, _) = conjugate_gradient(&kernel, &b, None, None, &SolverOptions::default()).unwrap();
+        assert_close(&x, &cg, 1e-12);
+
+        let rectangular = Toeplitz::new(vec![2.0, 1.0, 0.5], vec![2.0, -1.0]);
+        let rhs = Array::new_vec(vec![1.0, 0.0, 0.0]);
+        assert!(matches!(gmres(&rectangular, &rhs, None, 3, None, &SolverOptions::default()),
+            Err((AlgaeError::NotSquare { size:(2, 3) }, _, _))));
+
+        let zero_minor = Toeplitz::symmetric(vec![0.0, 1.0]);
+        assert_eq!(Err(AlgaeError::ZeroPivot { index:0 }), zero_minor.try_solve(Array::new_vec(vec![1.0, 1.0])));
+    }
+}
@@ -0,0 +1,331 @@
+use crate::array::array::Array;
+use crate::array::orthogonal::{complete_basis, dot, from_columns};
+use crate::error::error::AlgaeError;
+use std::fmt;
+use num::traits::Float;
+
+const MAX_SWEEPS:usize = 100;
+
+// A = U * Σ * V^T, computed with one-sided Jacobi rotations, which yields small singular values
+// to high relative accuracy. The thin factors are stored: with k = min(m, n),
+// U is m x k, the singular values are sorted descending and V^T is k x n.
+pub struct Svd<T> {
+    u:Array<T>,
+    s:Vec<T>,
+    vt:Array<T>,
+    pub size:(usize, usize),
+}
+
+impl<T: fmt::Display> fmt::Display for Svd<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = self.s.iter().map(|s| s.to_string()).collect::<Vec<String>>().join(", ");
+        write!(f, "{}\n[{}]\n{}", self.u, s, self.vt)
+    }
+}
+
+impl<T: Float> Svd<T> {
+    pub fn new(a:&Array<T>) -> Result<Self, AlgaeError> {
+        let (n, m) = a.size;
+        if m < n {
+            // A^T = V * Σ * U^T
+            let svd = Self::new(&a.transpose())?;
+            return Ok(Svd {
+                u:svd.vt.transpose(),
+                s:svd.s,
+                vt:svd.u.transpose(),
+                size:a.size,
+            });
+        }
+        let mut u = (0..n).map(|col| (0..m).map(|row| a[(row, col)]).collect::<Vec<T>>()).collect::<Vec<_>>();
+        let mut v = (0..n).map(|col| {
+            let mut e = vec![T::zero(); n];
+            e[col] = T::one();
+            e
+        }).collect::<Vec<_>>();
+        let tolerance = T::epsilon() * match T::from(m) {
+            Some(m) => m,
+            None => T::one(),
+        };
+        let mut converged = n < 2;
+        let mut off = T::zero();
+        for _ in 0..MAX_SWEEPS {
+            if converged {
+                break;
+            }
+            converged = true;
+            off = T::zero();
+            for p in 0..n {
+                for q in (p + 1)..n {
+                    let alpha = dot(&u[p], &u[p]);
+                    let beta = dot(&u[q], &u[q]);
+                    let gamma = dot(&u[p], &u[q]);
+                    if gamma == T::zero() || gamma.abs() <= tolerance * (alpha * beta).sqrt() {
+                        continue;
+                    }
+                    converged = false;
+                    off = off.max(gamma.abs() / (alpha * beta).sqrt());
+                    let two = T::one() + T::one();
+                    let zeta = (beta - alpha) / (two * gamma);
+                    let t = zeta.signum() / (zeta.abs() + (T::one() + zeta * zeta).sqrt());
+                    let c = T::one() / (T::one() + t * t).sqrt();
+                    let s = c * t;
+                    for cols in [&mut u, &mut v] {
+                        for i in 0..cols[p].len() {
+                            let (x, y) = (cols[p][i], cols[q][i]);
+                            cols[p][i] = c * x - s * y;
+                            cols[q][i] = s * x + c * y;
+                        }
+                    }
+                }
+            }
+        }
+        if !converged {
+            return Err(AlgaeError::NoConvergence {
+                iterations:MAX_SWEEPS,
+                last_estimate:off.to_f64().unwrap_or(f64::NAN),
+            });
+        }
+
+        let mut order = (0..n).map(|col| (dot(&u[col], &u[col]).sqrt(), col)).collect::<Vec<(T, usize)>>();
+        order.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(std::cmp::Ordering::Equal));
+        let s = order.iter().map(|(s, _)| *s).collect::<Vec<T>>();
+        let largest = s.first().copied().unwrap_or(T::zero());
+        let mut u_cols = Vec::<Vec<T>>::with_capacity(n);
+        let mut missing = Vec::<usize>::new();
+        for (i, (sigma, col)) in order.iter().enumerate() {
+            if *sigma > largest * T::epsilon() && *sigma > T::zero() {
+                u_cols.push(u[*col].iter().map(|x| *x / *sigma).collect());
+            } else {
+                missing.push(i);
+            }
+        }
+        // Columns belonging to vanishing singular values are replaced by an orthonormal completion.
+        let filled = complete_basis(u_cols, m, n);
+        let mut u_sorted = Vec::<Vec<T>>::with_capacity(n);
+        let (mut present, mut extra) = (0, n - missing.len());
+        for i in 0..n {
+            if missing.contains(&i) {
+                u_sorted.push(filled[extra].clone());
+                extra += 1;
+            } else {
+                u_sorted.push(filled[present].clone());
+                present += 1;
+            }
+        }
+        let v_sorted = order.iter().map(|(_, col)| v[*col].clone()).collect::<Vec<_>>();
+        Ok(Svd {
+            u:from_columns(&u_sorted, m),
+            s,
+            vt:from_columns(&v_sorted, n).transpose(),
+            size:a.size,
+        })
+    }
+
+    pub fn u(&self) -> &Array<T> {
+        &self.u
+    }
+
+    pub fn singular_values(&self) -> &[T] {
+        &self.s
+    }
+
+    // The k x k diagonal matrix of singular values.
+    pub fn sigma(&self) -> Array<T> {
+        let k = self.s.len();
+        let mut sigma = Array::new_filled((k, k), T::zero());
+        for (i, s) in self.s.iter().enumerate() {
+            sigma[(i, i)] = *s;
+        }
+        sigma
+    }
+
+    pub fn vt(&self) -> &Array<T> {
+        &self.vt
+    }
+
+    // The m x m U of the full decomposition.
+    pub fn full_u(&self) -> Array<T> {
+        let m = self.size.1;
+        let cols = (0..self.u.size.0).map(|col| (0..m).map(|row| self.u[(row, col)]).collect()).collect();
+        from_columns(&complete_basis(cols, m, m), m)
+    }
+
+    // The m x n Σ of the full decomposition.
+    pub fn full_sigma(&self) -> Array<T> {
+        let mut sigma = Array::new_filled(self.size, T::zero());
+        for (i, s) in self.s.iter().enumerate() {
+            sigma[(i, i)] = *s;
+        }
+        sigma
+    }
+
+    // The n x n V^T of the full decomposition.
+    pub fn full_vt(&self) -> Array<T> {
+        let n = self.size.0;
+        let rows = (0..self.vt.size.1).map(|row| (0..n).map(|col| self.vt[(row, col)]).collect()).collect();
+        from_columns(&complete_basis(rows, n, n), n).transpose()
+    }
+
+    // Keeps the k largest singular values, the result is the best rank k approximation.
+    pub fn truncate(&self, k:usize) -> Svd<T> {
+        let k = usize::min(k, self.s.len());
+        Svd {
+            u:self.u.slice(.., ..k).to_array(),
+            s:self.s[..k].to_vec(),
+            vt:self.vt.slice(..k, ..).to_array(),
+            size:self.size,
+        }
+    }
+
+    pub fn to_array(&self) -> Array<T> {
+        &(&self.u * &self.sigma()) * &self.vt
+    }
+
+    // Singular values up to this tolerance are treated as zero: max(m, n) * epsilon * σ_max.
+    pub fn default_tolerance(&self) -> T {
+        let max_dim = T::from(usize::max(self.size.0, self.size.1)).unwrap_or(T::one());
+        max_dim * T::epsilon() * self.s.first().copied().unwrap_or(T::zero())
+    }
+
+    pub fn rank(&self, tolerance:Option<T>) -> usize {
+        let tolerance = tolerance.unwrap_or(self.default_tolerance());
+        self.s.iter().filter(|s| **s > tolerance).count()
+    }
+
+    pub fn pinv(&self, tolerance:Option<T>) -> Array<T> {
+        let rank = self.rank(tolerance);
+        let mut v = self.vt.slice(..rank, ..).t().to_array();
+        for col in 0..rank {
+            for row in 0..v.size.1 {
+                v[(row, col)] = v[(row, col)] / self.s[col];
+            }
+        }
+        &v * &self.u.slice(.., ..rank).t().to_array()
+    }
+
+    // An orthonormal basis of the null space as columns, taken from the rows of the full V^T
+    // that belong to vanishing singular values.
+    pub fn null_space(&self, tolerance:Option<T>) -> Array<T> {
+        let rank = self.rank(tolerance);
+        self.full_vt().slice(rank.., ..).t().to_array()
+    }
+}
+
+impl<T: Float> Array<T> {
+    pub fn svd(&self) -> Result<Svd<T>, AlgaeError> {
+        Svd::new(self)
+    }
+
+    fn svd_or_panic(&self) -> Svd<T> {
+        match Svd::new(self) {
+            Ok(svd) => svd,
+            Err(e) => panic!("The singular value decomposition failed. {}", e),
+        }
+    }
+
+    // The Moore-Penrose pseudo-inverse.
+    pub fn pinv(&self) -> Array<T> {
+        self.svd_or_panic().pinv(None)
+    }
+
+    // Unlike rank, small singular values caused by rounding don't count.
+    pub fn numerical_rank(&self, tolerance:Option<T>) -> usize {
+        self.svd_or_panic().rank(tolerance)
+    }
+
+    // The 2-norm condition number σ_max / σ_min, infinite for rank deficient matrices.
+    pub fn cond(&self) -> T {
+        let svd = self.svd_or_panic();
+        match (svd.s.first(), svd.s.last()) {
+            (Some(max), Some(min)) if *min > T::zero() => *max / *min,
+            _ => T::infinity(),
+        }
+    }
+
+    pub fn spectral_norm(&self) -> T {
+        self.svd_or_panic().s.first().copied().unwrap_or(T::zero())
+    }
+
+    pub fn nuclear_norm(&self) -> T {
+        self.svd_or_panic().s.iter().fold(T::zero(), |acc, s| acc + *s)
+    }
+
+    pub fn low_rank_approximation(&self, k:usize) -> Array<T> {
+        self.svd_or_panic().truncate(k).to_array()
+    }
+
+    pub fn null_space_svd(&self, tolerance:Option<T>) -> Array<T> {
+        self.svd_or_panic().null_space(tolerance)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::array::svd::Array;
+    use crate::array::checks::assert_close;
+
+    #[test]
+    fn svd() {
+        let a = Array::new_mat(vec![vec![3.0, 2.0, 2.0], vec![2.0, 3.0, -2.0]]);
+        let svd = a.svd().unwrap();
+        assert!(f64::abs(svd.singular_values()[0] - 5.0) < 1e-12);
+        assert!(f64::abs(svd.singular_values()[1] - 3.0) < 1e-12);
+        assert_eq!((2, 2), svd.u().size);
+        assert_eq!((3, 2), svd.vt().size);
+        assert_close(&a, &svd.to_array(), 1e-12);
+        let (u, vt) = (svd.full_u(), svd.full_vt());
+        assert_close(&Array::identity(3), &(&vt * &vt.transpose()), 1e-12);
+        assert_close(&a, &(&(&u * &svd.full_sigma()) * &vt), 1e-12);
+    }
+
+    #[test]
+    fn svd_tall_rank_deficient() {
+        let a = Array::new_mat(vec![vec![1.0, 2.0], vec![2.0, 4.0], vec![3.0, 6.0]]);
+        let svd = a.svd().unwrap();
+        assert_eq!(1, svd.rank(None));
+        assert_close(&Array::identity(2), &(&svd.u().transpose() * svd.u()), 1e-12);
+        assert_close(&a, &svd.to_array(), 1e-12);
+        assert_eq!(1, a.numerical_rank(None));
+        assert!(a.cond().is_infinite());
+        let null_space = a.null_space_svd(None);
+        assert_eq!((1, 2), null_space.size);
+        assert!((&a * &null_space).norm() < 1e-12);
+    }
+
+    // The missing direction of U is (1, ..., 1) / sqrt(m), which is equally far from every
+    // standard basis vector.
+    #[test]
+    fn svd_centering_matrix() {
+        let centering = |m:usize| &Array::identity(m) - &Array::new_filled((m, m), 1.0 / m as f64);
+        let a = centering(5);
+        let svd = a.svd().unwrap();
+        assert_close(&Array::identity(5), &(&svd.u().transpose() * svd.u()), 1e-12);
+        assert_close(&a, &svd.to_array(), 1e-12);
+        assert_close(&Array::identity(5), &(&svd.full_u().transpose() * &svd.full_u()), 1e-12);
+        let null_space = centering(6).null_space_svd(None);
+        assert_eq!((1, 6), null_space.size);
+        assert_close(&Array::new_filled((1, 6), 0.0), &(&centering(6) * &null_space), 1e-12);
+    }
+
+    #[test]
+    fn pinv() {
+        let a = Array::new_mat(vec![vec![1.0, 0.0], vec![1.0, 1.0], vec![1.0, 2.0]]);
+        let b = Array::new_vec(vec![1.0, 2.0, 2.0]);
+        let (x, _) = Array::least_squares(a.clone(), &b).unwrap();
+        assert_close(&x, &(&a.pinv() * &b), 1e-12);
+        let a = Array::new_mat(vec![vec![1.0, 2.0], vec![2.0, 4.0]]);
+        let pinv = a.pinv();
+        assert_close(&a, &(&(&a * &pinv) * &a), 1e-12);
+    }
+
+    #[test]
+    fn norms_and_truncation() {
+        let a = Array::new_mat(vec![vec![3.0, 2.0, 2.0], vec![2.0, 3.0, -2.0]]);
+        assert!(f64::abs(a.spectral_norm() - 5.0) < 1e-12);
+        assert!(f64::abs(a.nuclear_norm() - 8.0) < 1e-12);
+        assert!(f64::abs(a.cond() - 5.0 / 3.0) < 1e-12);
+        let rank_one = a.low_rank_approximation(1);
+        assert_eq!(1, rank_one.numerical_rank(None));
+        assert!(f64::abs((&a - &rank_one).spectral_norm() - 3.0) < 1e-12);
+    }
+}
@@ -0,0 +1,450 @@
+use crate::array::array::Array;
+use crate::error::error::AlgaeError;
+use std::ops::{Index, IndexMut, Add, Div};
+use num::traits::{Zero, FromPrimitive};
+
+// Unlike Array::size, the shape of a tensor lists its axes from the outermost to the innermost,
+// so a matrix of size (width, height) becomes a tensor of shape [height, width].
+// Permuting axes only permutes the strides, the buffer is copied lazily when needed.
+#[derive(Debug)]
+pub struct Tensor<T> {
+    pub(in crate::array) content:Vec<T>,
+    pub(in crate::array) shape:Vec<usize>,
+    pub(in crate::array) strides:Vec<usize>,
+}
+
+fn standard_strides(shape:&[usize]) -> Vec<usize> {
+    let mut strides = vec![1; shape.len()];
+    for axis in (0..shape.len().saturating_sub(1)).rev() {
+        strides[axis] = strides[axis + 1] * shape[axis + 1];
+    }
+    strides
+}
+
+// Advances a multi-index in row-major order, returns false once every index was visited.
+fn next_index(index:&mut [usize], shape:&[usize]) -> bool {
+    for axis in (0..shape.len()).rev() {
+        index[axis] += 1;
+        if index[axis] < shape[axis] {
+            return true;
+        }
+        index[axis] = 0;
+    }
+    false
+}
+
+impl<T: Clone> Clone for Tensor<T> {
+    fn clone(&self) -> Self {
+        Tensor::<T> {
+            content:self.content.clone(),
+            shape:self.shape.clone(),
+            strides:self.strides.clone(),
+        }
+    }
+}
+
+impl<T> Tensor<T> {
+    pub fn shape(&self) -> &[usize] {
+        &self.shape
+    }
+
+    pub fn rank(&self) -> usize {
+        self.shape.len()
+    }
+
+    pub fn len(&self) -> usize {
+        self.shape.iter().product()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn offset(&self, index:&[usize]) -> usize {
+        index.iter().zip(self.strides.iter()).map(|(i, s)| i * s).sum()
+    }
+
+    fn is_standard_layout(&self) -> bool {
+        self.strides == standard_strides(&self.shape)
+    }
+
+    fn check_axis(&self, axis:usize) -> Result<(), AlgaeError> {
+        if axis >= self.rank() {
+            return Err(AlgaeError::InvalidAxis {
+                axis,
+                rank:self.rank(),
+            });
+        }
+        Ok(())
+    }
+
+    pub fn permute(self, axes:&[usize]) -> Result<Tensor<T>, AlgaeError> {
+        let mut seen = vec![false; self.rank()];
+        for axis in axes {
+            self.check_axis(*axis)?;
+            seen[*axis] = true;
+        }
+        if axes.len() != self.rank() || seen.iter().any(|s| !s) {
+            return Err(AlgaeError::TensorShapeMismatch {
+                expected:(0..self.rank()).collect(),
+                found:axes.to_vec(),
+            });
+        }
+        let shape = axes.iter().map(|a| self.shape[*a]).collect();
+        let strides = axes.iter().map(|a| self.strides[*a]).collect();
+        Ok(Tensor {
+            content:self.content,
+            shape,
+            strides,
+        })
+    }
+
+    pub fn transpose(self, a:usize, b:usize) -> Result<Tensor<T>, AlgaeError> {
+        self.check_axis(a)?;
+        self.check_axis(b)?;
+        let mut axes = (0..self.rank()).collect::<Vec<usize>>();
+        axes.swap(a, b);
+        self.permute(&axes)
+    }
+}
+
+impl<T: Copy + Clone> Tensor<T> {
+    pub fn new(content:Vec<T>, shape:Vec<usize>) -> Self {
+        match Self::try_new(content, shape) {
+            Ok(tensor) => tensor,
+            Err(e) => panic!("Invalid tensor initialisation: {}", e),
+        }
+    }
+
+    pub fn try_new(content:Vec<T>, shape:Vec<usize>) -> Result<Self, AlgaeError> {
+        if content.len() != shape.iter().product::<usize>() {
+            return Err(AlgaeError::TensorShapeMismatch {
+                expected:shape,
+                found:vec![content.len()],
+            });
+        }
+        let strides = standard_strides(&shape);
+        Ok(Tensor {
+            content,
+            shape,
+            strides,
+        })
+    }
+
+    pub fn new_filled(shape:Vec<usize>, value:T) -> Self {
+        let len = shape.iter().product();
+        Self::new(vec![value; len], shape)
+    }
+
+    // Copies the elements into row-major order, which is a no-op for tensors that weren't permuted.
+    pub fn to_standard_layout(&self) -> Tensor<T> {
+        if self.is_standard_layout() {
+            return self.clone();
+        }
+        let mut content = Vec::<T>::with_capacity(self.len());
+        if !self.is_empty() {
+            let mut index = vec![0; self.rank()];
+            loop {
+                content.push(self.content[self.offset(&index)]);
+                if !next_index(&mut index, &self.shape) {
+                    break;
+                }
+            }
+        }
+        Tensor::new(content, self.shape.clone())
+    }
+
+    pub fn reshape(&self, shape:Vec<usize>) -> Result<Tensor<T>, AlgaeError> {
+        if shape.iter().product::<usize>() != self.len() {
+            return Err(AlgaeError::TensorShapeMismatch {
+                expected:self.shape.clone(),
+                found:shape,
+            });
+        }
+        let content = self.to_standard_layout().content;
+        let strides = standard_strides(&shape);
+        Ok(Tensor {
+            content,
+            shape,
+            strides,
+        })
+    }
+
+    // Takes the sub-tensor at position `index` along `axis`, the result has one axis less.
+    pub fn select(&self, axis:usize, index:usize) -> Result<Tensor<T>, AlgaeError> {
+        self.check_axis(axis)?;
+        if index >= self.shape[axis] {
+            return Err(AlgaeError::AxisIndexOutOfBounds {
+                axis,
+                index,
+                shape:self.shape.clone(),
+            });
+        }
+        let mut shape = self.shape.clone();
+        shape.remove(axis);
+        let mut content = Vec::<T>::with_capacity(shape.iter().product());
+        let mut full_index = vec![0; self.rank()];
+        full_index[axis] = index;
+        let mut sub_index = vec![0; shape.len()];
+        if shape.iter().all(|s| *s > 0) {
+            loop {
+                for (i, value) in sub_index.iter().enumerate() {
+                    full_index[if i < axis { i } else { i + 1 }] = *value;
+                }
+                content.push(self.content[self.offset(&full_index)]);
+                if !next_index(&mut sub_index, &shape) {
+                    break;
+                }
+            }
+        }
+        Ok(Tensor::new(content, shape))
+    }
+
+    // Folds every lane along `axis` into a single value, the result has one axis less.
+    pub fn fold_axis<F: Fn(T, T) -> T>(&self, axis:usize, init:T, f:F) -> Result<Tensor<T>, AlgaeError> {
+        self.check_axis(axis)?;
+        let mut shape = self.shape.clone();
+        shape.remove(axis);
+        let mut result = Tensor::new_filled(shape, init);
+        if self.is_empty() {
+            return Ok(result);
+        }
+        let mut index = vec![0; self.rank()];
+        loop {
+            let reduced = index.iter()
+                .enumerate()
+                .filter(|(i, _)| *i != axis)
+                .map(|(_, value)| *value)
+                .collect::<Vec<usize>>();
+            let offset = result.offset(&reduced);
+            result.content[offset] = f(result.content[offset], self.content[self.offset(&index)]);
+            if !next_index(&mut index, &self.shape) {
+                break;
+            }
+        }
+        Ok(result)
+    }
+
+    // Only rank 2 tensors convert. The error suggests the shape to reshape to: the innermost axis
+    // stays the columns and the outer axes are folded into the rows, a vector becomes a column.
+    pub fn to_array(&self) -> Result<Array<T>, AlgaeError> {
+        if self.rank() != 2 {
+            let expected = match self.shape.split_last() {
+                Some((last, rest)) if !rest.is_empty() => vec![rest.iter().product(), *last],
+                Some((last, _)) => vec![*last, 1],
+                None => vec![1, 1],
+            };
+            return Err(AlgaeError::TensorShapeMismatch {
+                expected,
+                found:self.shape.clone(),
+            });
+        }
+        let content = self.to_standard_layout().content;
+        Ok(Array::from_content(content, (self.shape[1], self.shape[0])))
+    }
+}
+
+impl<T: Copy + Clone + Zero + Add<Output = T>> Tensor<T> {
+    pub fn sum_axis(&self, axis:usize) -> Result<Tensor<T>, AlgaeError> {
+        self.fold_axis(axis, T::zero(), |a, b| a + b)
+    }
+}
+
+impl<T: Copy + Clone + Zero + Add<Output = T> + Div<Output = T> + FromPrimitive> Tensor<T> {
+    // Returns None for empty axes, like Array::mean_axis.
+    pub fn mean_axis(&self, axis:usize) -> Result<Option<Tensor<T>>, AlgaeError> {
+        let sum = self.sum_axis(axis)?;
+        if self.shape[axis] == 0 {
+            return Ok(None);
+        }
+        let count = match T::from_usize(self.shape[axis]) {
+            Some(count) => count,
+            None => panic!("The length of axis {} isn't representable by the element type", axis),
+        };
+        let content = sum.content.iter().map(|s| *s / count).collect();
+        Ok(Some(Tensor::new(content, sum.shape)))
+    }
+}
+
+impl<T: Copy + Clone + PartialOrd> Tensor<T> {
+    // Returns None for empty axes.
+    pub fn max_axis(&self, axis:usize) -> Result<Option<Tensor<T>>, AlgaeError> {
+        self.check_axis(axis)?;
+        if self.shape[axis] == 0 {
+            return Ok(None);
+        }
+        let first = self.select(axis, 0)?;
+        let mut result = first.content.clone();
+        for i in 1..self.shape[axis] {
+            let lane = self.select(axis, i)?;
+            for (r, v) in result.iter_mut().zip(lane.content.iter()) {
+                if *v > *r {
+                    *r = *v;
+                }
+            }
+        }
+        Ok(Some(Tensor::new(result, first.shape)))
+    }
+
+    pub fn min_axis(&self, axis:usize) -> Result<Option<Tensor<T>>, AlgaeError> {
+        self.check_axis(axis)?;
+        if self.shape[axis] == 0 {
+            return Ok(None);
+        }
+        let first = self.select(axis, 0)?;
+        let mut result = first.content.clone();
+        for i in 1..self.shape[axis] {
+            let lane = self.select(axis, i)?;
+            for (r, v) in result.iter_mut().zip(lane.content.iter()) {
+                if *v < *r {
+                    *r = *v;
+                }
+            }
+        }
+        Ok(Some(Tensor::new(result, first.shape)))
+    }
+}
+
+impl<T: PartialEq> PartialEq for Tensor<T> {
+    fn eq(&self, other:&Self) -> bool {
+        if self.shape != other.shape {
+            return false;
+        }
+        if self.shape.contains(&0) {
+            return true;
+        }
+        let mut index = vec![0; self.rank()];
+        loop {
+            if self.content[self.offset(&index)] != other.content[other.offset(&index)] {
+                return false;
+            }
+            if !next_index(&mut index, &self.shape) {
+                return true;
+            }
+        }
+    }
+}
+
+impl<T> Index<&[usize]> for Tensor<T> {
+    type Output = T;
+
+    fn index(&self, index:&[usize]) -> &Self::Output {
+        if index.len() != self.rank() || index.iter().zip(self.shape.iter()).any(|(i, s)| i >= s) {
+            panic!("Index out of bounds: the shape is {:?} but the index is {:?}", self.shape, index);
+        }
+        &self.content[self.offset(index)]
+    }
+}
+
+impl<T> IndexMut<&[usize]> for Tensor<T> {
+    fn index_mut(&mut self, index:&[usize]) -> &mut Self::Output {
+        if index.len() != self.rank() || index.iter().zip(self.shape.iter()).any(|(i, s)| i >= s) {
+            panic!("Index out of bounds: the shape is {:?} but the index is {:?}", self.shape, index);
+        }
+        let offset = self.offset(index);
+        &mut self.content[offset]
+    }
+}
+
+impl<T: Copy + Clone> From<Array<T>> for Tensor<T> {
+    fn from(array:Array<T>) -> Self {
+        let shape = vec![array.size.1, array.size.0];
+        Tensor::new(array.content, shape)
+    }
+}
+
+impl<T: Copy + Clone> TryFrom<Tensor<T>> for Array<T> {
+    type Error = AlgaeError;
+
+    fn try_from(tensor:Tensor<T>) -> Result<Self, Self::Error> {
+        tensor.to_array()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::array::tensor::{Array, Tensor};
+    use crate::error::error::AlgaeError;
+
+    fn counting(shape:Vec<usize>) -> Tensor<i32> {
+        let len = shape.iter().product::<usize>() as i32;
+        Tensor::new((0..len).collect(), shape)
+    }
+
+    #[test]
+    fn index() {
+        let tensor = counting(vec![2, 3, 4]);
+        assert_eq!(23, tensor[&[1, 2, 3][..]]);
+        assert_eq!(6, tensor[&[0, 1, 2][..]]);
+    }
+
+    #[test]
+    fn reshape() {
+        let expected = Tensor::new(vec![0, 1, 2, 3, 4, 5], vec![3, 2]);
+        let actual = counting(vec![2, 3]).reshape(vec![3, 2]).unwrap();
+        assert_eq!(expected, actual);
+        assert!(counting(vec![2, 3]).reshape(vec![4, 2]).is_err());
+    }
+
+    #[test]
+    fn permute() {
+        let tensor = counting(vec![2, 3, 4]);
+        let permuted = tensor.clone().permute(&[2, 0, 1]).unwrap();
+        assert_eq!(vec![4, 2, 3], permuted.shape());
+        for i in 0..2 {
+            for j in 0..3 {
+                for k in 0..4 {
+                    assert_eq!(tensor[&[i, j, k][..]], permuted[&[k, i, j][..]]);
+                }
+            }
+        }
+        assert!(tensor.permute(&[0, 0, 1]).is_err());
+    }
+
+    #[test]
+    fn reshape_after_permute() {
+        let expected = Tensor::new(vec![0, 3, 1, 4, 2, 5], vec![6]);
+        let actual = counting(vec![2, 3]).transpose(0, 1).unwrap().reshape(vec![6]).unwrap();
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn sum_axis() {
+        let tensor = counting(vec![2, 3, 4]);
+        let expected = Tensor::new(vec![12, 14, 16, 18, 20, 22, 24, 26, 28, 30, 32, 34], vec![3, 4]);
+        assert_eq!(expected, tensor.sum_axis(0).unwrap());
+        let expected = Tensor::new(vec![6, 22, 38, 54, 70, 86], vec![2, 3]);
+        assert_eq!(expected, tensor.sum_axis(2).unwrap());
+        assert!(tensor.sum_axis(3).is_err());
+    }
+
+    #[test]
+    fn mean_and_max_axis() {
+        let tensor = Tensor::new(vec![1.0, 4.0, 3.0, 2.0], vec![2, 2]);
+        assert_eq!(Some(Tensor::new(vec![2.0, 3.0], vec![2])), tensor.mean_axis(0).unwrap());
+        assert_eq!(None, Tensor::<f64>::new(vec![], vec![2, 0]).mean_axis(1).unwrap());
+        assert_eq!(Err(AlgaeError::InvalidAxis { axis:2, rank:2 }), tensor.mean_axis(2));
+        assert_eq!(Tensor::new(vec![4.0, 3.0], vec![2]), tensor.max_axis(1).unwrap().unwrap());
+        assert_eq!(Tensor::new(vec![1.0, 2.0], vec![2]), tensor.min_axis(0).unwrap().unwrap());
+    }
+
+    #[test]
+    fn select() {
+        let expected = Tensor::new(vec![4, 5, 6, 7, 16, 17, 18, 19], vec![2, 4]);
+        let actual = counting(vec![2, 3, 4]).select(1, 1).unwrap();
+        assert_eq!(expected, actual);
+        assert_eq!(Err(AlgaeError::AxisIndexOutOfBounds { axis:1, index:3, shape:vec![2, 3, 4] }), counting(vec![2, 3, 4]).select(1, 3));
+        assert_eq!(Err(AlgaeError::InvalidAxis { axis:3, rank:3 }), counting(vec![2, 3, 4]).select(3, 0));
+    }
+
+    #[test]
+    fn array_round_trip() {
+        let array = Array::new_mat(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+        let tensor = Tensor::from(array.clone());
+        assert_eq!(vec![2, 3], tensor.shape());
+        assert_eq!(6, tensor[&[1, 2][..]]);
+        assert_eq!(array.transpose(), Array::try_from(tensor.transpose(0, 1).unwrap()).unwrap());
+        let expected = AlgaeError::TensorShapeMismatch { expected:vec![6, 4], found:vec![2, 3, 4] };
+        assert_eq!(Err(expected), Array::try_from(counting(vec![2, 3, 4])));
+    }
+}
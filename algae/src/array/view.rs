@@ -0,0 +1,544 @@
+use crate::array::array::Array;
+use crate::array::float_eq::{FloatEq, Tolerance};
+use crate::array::gemm::gemm_kernel;
+use crate::error::error::AlgaeError;
+use std::ops::{Index, IndexMut, Add, Sub, Neg, Mul, RangeBounds, Bound};
+use num::traits::Zero;
+
+// A view borrows the buffer of an Array and walks it with its own size and strides,
+// so sub-blocks, single rows/columns and transposes can be handed around without copying.
+// `content` always starts at the element (0, 0) of the view.
+#[derive(Debug)]
+pub struct ArrayView<'a, T> {
+    pub(in crate::array) content:&'a [T],
+    pub size:(usize, usize),
+    pub(in crate::array) strides:(usize, usize),
+}
+
+impl<'a, T> Clone for ArrayView<'a, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<'a, T> Copy for ArrayView<'a, T> {}
+
+#[derive(Debug)]
+pub struct ArrayViewMut<'a, T> {
+    pub(in crate::array) content:&'a mut [T],
+    pub size:(usize, usize),
+    pub(in crate::array) strides:(usize, usize),
+}
+
+pub(in crate::array) fn resolve_range<R: RangeBounds<usize>>(range:R, len:usize) -> (usize, usize) {
+    let start = match range.start_bound() {
+        Bound::Included(s) => *s,
+        Bound::Excluded(s) => *s + 1,
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(e) => *e + 1,
+        Bound::Excluded(e) => *e,
+        Bound::Unbounded => len,
+    };
+    if start > end {
+        panic!("Invalid slice: the range starts at {} but ends at {}", start, end);
+    }
+    if end > len {
+        panic!("Index out of bounds: the length is {} but the range ends at {}", len, end);
+    }
+    (start, end)
+}
+
+// Returns the offset of the first element and the size of the sliced block.
+fn slice_bounds<R: RangeBounds<usize>, C: RangeBounds<usize>>
+(size:(usize, usize), strides:(usize, usize), rows:R, cols:C) -> (usize, (usize, usize)) {
+    let (row_start, row_end) = resolve_range(rows, size.1);
+    let (col_start, col_end) = resolve_range(cols, size.0);
+    let size = (col_end - col_start, row_end - row_start);
+    if size.0 == 0 || size.1 == 0 {
+        (0, size)
+    } else {
+        (row_start * strides.1 + col_start * strides.0, size)
+    }
+}
+
+fn block_len(size:(usize, usize), strides:(usize, usize)) -> usize {
+    if size.0 == 0 || size.1 == 0 {
+        0
+    } else {
+        (size.1 - 1) * strides.1 + (size.0 - 1) * strides.0 + 1
+    }
+}
+
+// NumPy rules for two dimensions: every axis has to agree or be of length one.
+pub(in crate::array) fn broadcast_size(a:(usize, usize), b:(usize, usize)) -> Result<(usize, usize), AlgaeError> {
+    fn axis(a:usize, b:usize) -> Option<usize> {
+        if a == b || b == 1 {
+            Some(a)
+        } else if a == 1 {
+            Some(b)
+        } else {
+            None
+        }
+    }
+    match (axis(a.0, b.0), axis(a.1, b.1)) {
+        (Some(width), Some(height)) => Ok((width, height)),
+        _ => Err(AlgaeError::BroadcastMismatch {
+            left:a,
+            right:b,
+        }),
+    }
+}
+
+pub(in crate::array) fn broadcast_zip<T: Clone, F: Fn(T, T) -> T>
+(a:ArrayView<T>, b:ArrayView<T>, f:F) -> Result<Array<T>, AlgaeError> {
+    let size = broadcast_size(a.size, b.size)?;
+    let (a, b) = (a.broadcast_to(size)?, b.broadcast_to(size)?);
+    let mut content = Vec::<T>::with_capacity(size.0 * size.1);
+    for row in 0..size.1 {
+        for col in 0..size.0 {
+            content.push(f(a.content[a.offset(row, col)].clone(), b.content[b.offset(row, col)].clone()));
+        }
+    }
+    Ok(Array::from_content(content, size))
+}
+
+fn check_index(size:(usize, usize), i:(usize, usize)) {
+    if i.0 >= size.1 {
+        panic!("Index out of bounds: the height is {} but the index is {}", size.1, i.0);
+    } else if i.1 >= size.0 {
+        panic!("Index out of bounds: the width is {} but the index is {}", size.0, i.1);
+    }
+}
+
+impl<T> Array<T> {
+    pub fn view(&self) -> ArrayView<'_, T> {
+        ArrayView {
+            content:&self.content,
+            size:self.size,
+            strides:self.strides,
+        }
+    }
+
+    pub fn view_mut(&mut self) -> ArrayViewMut<'_, T> {
+        ArrayViewMut {
+            content:&mut self.content,
+            size:self.size,
+            strides:self.strides,
+        }
+    }
+
+    pub fn slice<R: RangeBounds<usize>, C: RangeBounds<usize>>(&self, rows:R, cols:C) -> ArrayView<'_, T> {
+        self.view().into_slice(rows, cols)
+    }
+
+    pub fn slice_mut<R: RangeBounds<usize>, C: RangeBounds<usize>>(&mut self, rows:R, cols:C) -> ArrayViewMut<'_, T> {
+        self.view_mut().into_slice_mut(rows, cols)
+    }
+
+    pub fn row(&self, index:usize) -> ArrayView<'_, T> {
+        self.view().into_row(index)
+    }
+
+    pub fn col(&self, index:usize) -> ArrayView<'_, T> {
+        self.view().into_col(index)
+    }
+
+    pub fn t(&self) -> ArrayView<'_, T> {
+        self.view().t()
+    }
+}
+
+impl<'a, T> ArrayView<'a, T> {
+    #[inline]
+    pub(in crate::array) fn offset(&self, row:usize, col:usize) -> usize {
+        row * self.strides.1 + col * self.strides.0
+    }
+
+    // Consumes the view to keep the lifetime of the underlying buffer.
+    pub fn into_slice<R: RangeBounds<usize>, C: RangeBounds<usize>>(self, rows:R, cols:C) -> ArrayView<'a, T> {
+        let (start, size) = slice_bounds(self.size, self.strides, rows, cols);
+        ArrayView {
+            content:&self.content[start..(start + block_len(size, self.strides))],
+            size,
+            strides:self.strides,
+        }
+    }
+
+    pub fn slice<R: RangeBounds<usize>, C: RangeBounds<usize>>(&self, rows:R, cols:C) -> ArrayView<'a, T> {
+        self.into_slice(rows, cols)
+    }
+
+    pub fn into_row(self, index:usize) -> ArrayView<'a, T> {
+        if index >= self.size.1 {
+            panic!("Index out of bounds: the height is {} but the index is {}", self.size.1, index);
+        }
+        self.into_slice(index..(index + 1), ..)
+    }
+
+    pub fn row(&self, index:usize) -> ArrayView<'a, T> {
+        self.into_row(index)
+    }
+
+    pub fn into_col(self, index:usize) -> ArrayView<'a, T> {
+        if index >= self.size.0 {
+            panic!("Index out of bounds: the width is {} but the index is {}", self.size.0, index);
+        }
+        self.into_slice(.., index..(index + 1))
+    }
+
+    pub fn col(&self, index:usize) -> ArrayView<'a, T> {
+        self.into_col(index)
+    }
+
+    pub fn t(&self) -> ArrayView<'a, T> {
+        ArrayView {
+            content:self.content,
+            size:(self.size.1, self.size.0),
+            strides:(self.strides.1, self.strides.0),
+        }
+    }
+
+    // Stretches axes of length one to the requested size by giving them a stride of zero.
+    pub fn broadcast_to(&self, size:(usize, usize)) -> Result<ArrayView<'a, T>, AlgaeError> {
+        let error = AlgaeError::BroadcastMismatch {
+            left:self.size,
+            right:size,
+        };
+        let stride = |own:usize, target:usize, stride:usize| {
+            if own == target {
+                Some(stride)
+            } else if own == 1 {
+                Some(0)
+            } else {
+                None
+            }
+        };
+        match (stride(self.size.0, size.0, self.strides.0), stride(self.size.1, size.1, self.strides.1)) {
+            (Some(col_stride), Some(row_stride)) => Ok(ArrayView {
+                content:self.content,
+                size,
+                strides:(col_stride, row_stride),
+            }),
+            _ => Err(error),
+        }
+    }
+}
+
+impl<'a, T: Clone> ArrayView<'a, T> {
+    pub fn to_array(&self) -> Array<T> {
+        let mut content = Vec::<T>::with_capacity(self.size.0 * self.size.1);
+        for row in 0..self.size.1 {
+            for col in 0..self.size.0 {
+                content.push(self.content[self.offset(row, col)].clone());
+            }
+        }
+        Array::from_content(content, self.size)
+    }
+}
+
+impl<'a, T> ArrayViewMut<'a, T> {
+    #[inline]
+    pub(in crate::array) fn offset(&self, row:usize, col:usize) -> usize {
+        row * self.strides.1 + col * self.strides.0
+    }
+
+    pub fn view(&self) -> ArrayView<'_, T> {
+        ArrayView {
+            content:&*self.content,
+            size:self.size,
+            strides:self.strides,
+        }
+    }
+
+    pub fn reborrow(&mut self) -> ArrayViewMut<'_, T> {
+        ArrayViewMut {
+            content:&mut *self.content,
+            size:self.size,
+            strides:self.strides,
+        }
+    }
+
+    pub fn into_slice_mut<R: RangeBounds<usize>, C: RangeBounds<usize>>(self, rows:R, cols:C) -> ArrayViewMut<'a, T> {
+        let (start, size) = slice_bounds(self.size, self.strides, rows, cols);
+        let len = block_len(size, self.strides);
+        ArrayViewMut {
+            content:&mut self.content[start..(start + len)],
+            size,
+            strides:self.strides,
+        }
+    }
+
+    pub fn slice_mut<R: RangeBounds<usize>, C: RangeBounds<usize>>(&mut self, rows:R, cols:C) -> ArrayViewMut<'_, T> {
+        self.reborrow().into_slice_mut(rows, cols)
+    }
+
+    pub fn row_mut(&mut self, index:usize) -> ArrayViewMut<'_, T> {
+        if index >= self.size.1 {
+            panic!("Index out of bounds: the height is {} but the index is {}", self.size.1, index);
+        }
+        self.slice_mut(index..(index + 1), ..)
+    }
+
+    pub fn col_mut(&mut self, index:usize) -> ArrayViewMut<'_, T> {
+        if index >= self.size.0 {
+            panic!("Index out of bounds: the width is {} but the index is {}", self.size.0, index);
+        }
+        self.slice_mut(.., index..(index + 1))
+    }
+
+    pub fn t_mut(&mut self) -> ArrayViewMut<'_, T> {
+        ArrayViewMut {
+            content:&mut *self.content,
+            size:(self.size.1, self.size.0),
+            strides:(self.strides.1, self.strides.0),
+        }
+    }
+
+    pub fn swap_rows(&mut self, a:usize, b:usize) {
+        if a == b {
+            return;
+        }
+        for col in 0..self.size.0 {
+            let (i, j) = (self.offset(a, col), self.offset(b, col));
+            self.content.swap(i, j);
+        }
+    }
+}
+
+impl<'a, T: Clone> ArrayViewMut<'a, T> {
+    pub fn to_array(&self) -> Array<T> {
+        self.view().to_array()
+    }
+
+    pub fn assign(&mut self, other:ArrayView<T>) {
+        if self.size != other.size {
+            panic!("To assign to a view both sizes must be equal");
+        }
+        for row in 0..self.size.1 {
+            for col in 0..self.size.0 {
+                let offset = self.offset(row, col);
+                self.content[offset] = other[(row, col)].clone();
+            }
+        }
+    }
+}
+
+impl<'a, T> Index<(usize, usize)> for ArrayView<'a, T> {
+    type Output = T;
+
+    fn index(&self, i:(usize, usize)) -> &Self::Output {
+        check_index(self.size, i);
+        &self.content[self.offset(i.0, i.1)]
+    }
+}
+
+impl<'a, T> Index<(usize, usize)> for ArrayViewMut<'a, T> {
+    type Output = T;
+
+    fn index(&self, i:(usize, usize)) -> &Self::Output {
+        check_index(self.size, i);
+        &self.content[self.offset(i.0, i.1)]
+    }
+}
+
+impl<'a, T> IndexMut<(usize, usize)> for ArrayViewMut<'a, T> {
+    fn index_mut(&mut self, i:(usize, usize)) -> &mut Self::Output {
+        check_index(self.size, i);
+        let offset = self.offset(i.0, i.1);
+        &mut self.content[offset]
+    }
+}
+
+impl<'a, 'b, T: PartialEq> PartialEq<ArrayView<'b, T>> for ArrayView<'a, T> {
+    fn eq(&self, other:&ArrayView<'b, T>) -> bool {
+        if self.size != other.size {
+            return false;
+        }
+        for row in 0..self.size.1 {
+            for col in 0..self.size.0 {
+                if self.content[self.offset(row, col)] != other.content[other.offset(row, col)] {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+}
+
+impl<'a, T: Clone + FloatEq> FloatEq for ArrayView<'a, T> {
+    fn approx_eq_with(&self, other:&Self, tolerance:Tolerance) -> bool {
+        if self.size != other.size {return false;}
+        for i in 0..self.size.0 {
+            for j in 0..self.size.1 {
+                if !self[(j, i)].approx_eq_with(&other[(j, i)], tolerance) {return false;}
+            }
+        }
+        true
+    }
+
+    fn standard_tolerance() -> Tolerance {
+        T::standard_tolerance()
+    }
+}
+
+impl<'a, T: Clone> ArrayView<'a, T> {
+    fn map_elements<F: Fn(T) -> T>(&self, f:F) -> Array<T> {
+        let mut content = Vec::<T>::with_capacity(self.size.0 * self.size.1);
+        for row in 0..self.size.1 {
+            for col in 0..self.size.0 {
+                content.push(f(self[(row, col)].clone()));
+            }
+        }
+        Array::from_content(content, self.size)
+    }
+}
+
+impl<'a, 'b, T: Clone + Add<Output = T>> Add<ArrayView<'b, T>> for ArrayView<'a, T> {
+    type Output = Array<T>;
+    fn add(self, other:ArrayView<'b, T>) -> Array<T> {
+        match broadcast_zip(self, other, |a, b| a + b) {
+            Ok(array) => array,
+            Err(e) => panic!("To add two arrays their sizes must be broadcastable. {}", e),
+        }
+    }
+}
+
+impl<'a, 'b, T: Clone + Sub<Output = T>> Sub<ArrayView<'b, T>> for ArrayView<'a, T> {
+    type Output = Array<T>;
+    fn sub(self, other:ArrayView<'b, T>) -> Array<T> {
+        match broadcast_zip(self, other, |a, b| a - b) {
+            Ok(array) => array,
+            Err(e) => panic!("To subtract two arrays their sizes must be broadcastable. {}", e),
+        }
+    }
+}
+
+impl<'a, T: Clone + Neg<Output = T>> Neg for ArrayView<'a, T> {
+    type Output = Array<T>;
+    fn neg(self) -> Array<T> {
+        self.map_elements(|a| -a)
+    }
+}
+
+impl<'a, T: Clone + Add<Output = T> + Mul<Output = T>> Mul<T> for ArrayView<'a, T> {
+    type Output = Array<T>;
+    fn mul(self, other:T) -> Array<T> {
+        self.map_elements(|a| a * other.clone())
+    }
+}
+
+impl<'a, 'b, T: Clone + Zero + Add<Output = T> + Mul<Output = T>> Mul<ArrayView<'b, T>> for ArrayView<'a, T> {
+    type Output = Array<T>;
+    fn mul(self, other:ArrayView<'b, T>) -> Array<T> {
+        if self.size.0 != other.size.1 {
+            panic!("To multiply two arrays the first width has to be equal to the seconds height.");
+        }
+        let mut array = Array::new_filled((other.size.0, self.size.1), T::zero());
+        gemm_kernel(None, self, other, &mut array.view_mut());
+        array
+    }
+}
+
+impl<'a, 'b, T: Clone + Mul<Output = T>> ArrayView<'a, T> {
+    pub fn hadamard_product(self, other:ArrayView<'b, T>) -> Array<T> {
+        match broadcast_zip(self, other, |a, b| a * b) {
+            Ok(array) => array,
+            Err(e) => panic!("The factors of a hadamard_product need to have broadcastable sizes. {}", e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::array::view::Array;
+    use crate::array::float_eq::FloatEq;
+
+    #[test]
+    fn slice() {
+        let expected = Array::new_mat(vec![vec![4, 5], vec![7, 8]]);
+        let array = Array::new_mat(vec![vec![0, 1, 2], vec![3, 4, 5], vec![6, 7, 8]]);
+        let actual = array.slice(1..3, 1..).to_array();
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn slice_of_slice() {
+        let expected = Array::new_mat(vec![vec![7]]);
+        let array = Array::new_mat(vec![vec![0, 1, 2], vec![3, 4, 5], vec![6, 7, 8]]);
+        let actual = array.slice(1.., ..).slice(1..=1, 1..2).to_array();
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn row_and_col() {
+        let array = Array::new_mat(vec![vec![1, 2], vec![0, 1]]);
+        assert_eq!(array.get_row(1), array.row(1).to_array());
+        assert_eq!(array.get_col(1), array.col(1).to_array());
+    }
+
+    #[test]
+    fn transposed_view() {
+        let array = Array::new_mat(vec![vec![1, 0, 4], vec![2, 1, 3]]);
+        let expected = array.transpose();
+        let actual = array.t().to_array();
+        assert_eq!(expected, actual);
+        assert_eq!(array.slice(.., 1..).t().to_array(), Array::new_mat(vec![vec![0, 1], vec![4, 3]]));
+    }
+
+    #[test]
+    fn slice_mut() {
+        let expected = Array::new_mat(vec![vec![1, 2], vec![0, 9]]);
+        let actual = {
+            let mut temp = Array::new_mat(vec![vec![1, 2], vec![0, 1]]);
+            temp.slice_mut(1.., 1..)[(0, 0)] = 9;
+            temp
+        };
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn swap_rows_in_view() {
+        let expected = Array::new_mat(vec![vec![1, 2, 3], vec![4, 8, 9], vec![7, 5, 6]]);
+        let actual = {
+            let mut temp = Array::new_mat(vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 9]]);
+            temp.slice_mut(1.., 1..).swap_rows(0, 1);
+            temp
+        };
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn view_arithmetic() {
+        let a = Array::new_mat(vec![vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0]]);
+        let b = Array::new_mat(vec![vec![1.0, 1.0], vec![2.0, 2.0]]);
+        assert!((a.slice(.., 1..) + b.view()).float_eq(&Array::new_mat(vec![vec![3.0, 4.0], vec![7.0, 8.0]])));
+        assert!((a.slice(.., 1..) - b.view()).float_eq(&Array::new_mat(vec![vec![1.0, 2.0], vec![3.0, 4.0]])));
+        assert!((a.t() * b.view()).float_eq(&(a.transpose() * b.clone())));
+        assert!((-a.row(0) * 2.0).float_eq(&Array::new_mat(vec![vec![-2.0, -4.0, -6.0]])));
+    }
+
+    #[test]
+    fn broadcast_to() {
+        let expected = Array::new_mat(vec![vec![1, 2, 3], vec![1, 2, 3]]);
+        let row = Array::new_mat(vec![vec![1, 2, 3]]);
+        assert_eq!(expected, row.view().broadcast_to((3, 2)).unwrap().to_array());
+        assert!(row.view().broadcast_to((2, 2)).is_err());
+    }
+
+    #[test]
+    fn view_broadcasting() {
+        let a = Array::new_mat(vec![vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0]]);
+        let means = Array::new_mat(vec![vec![2.5, 3.5, 4.5]]);
+        let expected = Array::new_mat(vec![vec![-1.5, -1.5, -1.5], vec![1.5, 1.5, 1.5]]);
+        assert!((a.view() - means.view()).float_eq(&expected));
+    }
+
+    #[test]
+    fn view_float_eq() {
+        let a = Array::new_mat(vec![vec![1.0, 2.0], vec![3.0, 4.0]]);
+        let b = Array::new_mat(vec![vec![2.0], vec![4.0]]);
+        assert!(a.col(1).float_eq(&b.view()));
+        assert!(!a.col(0).float_eq(&b.view()));
+    }
+}
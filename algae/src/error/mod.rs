@@ -0,0 +1,147 @@
+pub mod error {
+    use std::fmt;
+
+    // Sizes follow the convention of Array::size, i.e. (width, height).
+    #[derive(Debug, Clone, PartialEq)]
+    pub enum AlgaeError {
+        ShapeMismatch {
+            expected:(usize, usize),
+            found:(usize, usize),
+        },
+        Singular,
+        NotSquare {
+            size:(usize, usize),
+        },
+        ZeroPivot {
+            index:usize,
+        },
+        NoConvergence {
+            iterations:usize,
+            last_estimate:f64,
+        },
+        IndexOutOfBounds {
+            index:(usize, usize),
+            size:(usize, usize),
+        },
+        BroadcastMismatch {
+            left:(usize, usize),
+            right:(usize, usize),
+        },
+        // Tensor shapes list the axes from the outermost to the innermost.
+        TensorShapeMismatch {
+            expected:Vec<usize>,
+            found:Vec<usize>,
+        },
+        InvalidAxis {
+            axis:usize,
+            rank:usize,
+        },
+        // The position along an axis is at least the length of that axis.
+        AxisIndexOutOfBounds {
+            axis:usize,
+            index:usize,
+            shape:Vec<usize>,
+        },
+        NotPositiveDefinite {
+            index:usize,
+        },
+        // A sparse matrix has an entry outside of the pattern its factorisation was analysed for.
+        PatternMismatch {
+            index:(usize, usize),
+        },
+        // A real matrix function whose value for this input is complex, e.g. the square root
+        // of a matrix with negative eigenvalues.
+        NotReal,
+        // The entry at this position of a permutation repeats an earlier one or exceeds its length.
+        InvalidPermutation {
+            index:usize,
+        },
+        // The entry at this position differs from its mirror image across the diagonal.
+        NotSymmetric {
+            index:(usize, usize),
+        },
+        // A scalar argument outside of the range the method is defined for.
+        InvalidParameter {
+            name:&'static str,
+            value:f64,
+        },
+    }
+
+    impl fmt::Display for AlgaeError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                AlgaeError::ShapeMismatch { expected, found } => {
+                    write!(f, "Shape mismatch: expected size {:?} but found {:?}", expected, found)
+                },
+                AlgaeError::Singular => {
+                    write!(f, "The matrix is singular")
+                },
+                AlgaeError::NotSquare { size } => {
+                    write!(f, "The matrix must be square but has size {:?}", size)
+                },
+                AlgaeError::ZeroPivot { index } => {
+                    write!(f, "Encountered a zero pivot in row {}", index)
+                },
+                AlgaeError::NoConvergence { iterations, last_estimate } => {
+                    write!(f, "No convergence after {} iterations, last estimate: {}", iterations, last_estimate)
+                },
+                AlgaeError::IndexOutOfBounds { index, size } => {
+                    write!(f, "Index out of bounds: the index is {:?} but the size is {:?}", index, size)
+                },
+                AlgaeError::BroadcastMismatch { left, right } => {
+                    write!(f, "Arrays of size {:?} and {:?} can't be broadcast together", left, right)
+                },
+                AlgaeError::TensorShapeMismatch { expected, found } => {
+                    write!(f, "Tensor shape mismatch: expected shape {:?} but found {:?}", expected, found)
+                },
+                AlgaeError::InvalidAxis { axis, rank } => {
+                    write!(f, "Axis {} doesn't exist in a tensor of rank {}", axis, rank)
+                },
+                AlgaeError::AxisIndexOutOfBounds { axis, index, shape } => {
+                    write!(f, "Index {} is out of bounds for axis {} of a tensor of shape {:?}", index, axis, shape)
+                },
+                AlgaeError::NotPositiveDefinite { index } => {
+                    write!(f, "The matrix is not positive definite, encountered a non-positive pivot in row {}", index)
+                },
+                AlgaeError::PatternMismatch { index } => {
+                    write!(f, "The entry {:?} lies outside of the analysed sparsity pattern", index)
+                },
+                AlgaeError::NotReal => {
+                    write!(f, "The result has a significant imaginary part and isn't a real matrix")
+                },
+                AlgaeError::InvalidPermutation { index } => {
+                    write!(f, "Entry {} doesn't form a permutation with the entries before it", index)
+                },
+                AlgaeError::NotSymmetric { index } => {
+                    write!(f, "The matrix must be symmetric but the entry {:?} differs from its transpose", index)
+                },
+                AlgaeError::InvalidParameter { name, value } => {
+                    write!(f, "The parameter {} can't be {}", name, value)
+                },
+            }
+        }
+    }
+
+    impl std::error::Error for AlgaeError {}
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::error::AlgaeError;
+
+    #[test]
+    fn display() {
+        let expected = "Shape mismatch: expected size (2, 3) but found (3, 2)";
+        let actual = AlgaeError::ShapeMismatch {
+            expected:(2, 3),
+            found:(3, 2),
+        }.to_string();
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn boxed() {
+        let actual:Box<dyn std::error::Error> = Box::new(AlgaeError::Singular);
+        assert_eq!("The matrix is singular", actual.to_string());
+    }
+}
@@ -2,6 +2,7 @@ use std::env;
 
 pub mod array;
 pub mod ml;
+pub mod error;
 mod signal_processing;
 mod util;
 
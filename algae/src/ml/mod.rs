@@ -99,162 +99,44 @@ pub mod ml {
         fn softplus_derivative(value:f64) -> f64 {
             value.exp() / (value.exp() + 1.)
         }
-    }
 
-    #[macro_export]
-    macro_rules! apply {
-        ( $function:expr, $arr:expr ) => {
-            match $function {
-                ActivationFunction::Perceptron => {
-                    for row in 0..$arr.size.1 {
-                        for col in 0..$arr.size.0 {
-                            $arr[(row, col)] = ActivationFunction::perceptron($arr[(row, col)]);
-                        }
-                    }
-                    $arr
-                },
-                ActivationFunction::Sigmoid => {
-                    for row in 0..$arr.size.1 {
-                        for col in 0..$arr.size.0 {
-                            $arr[(row, col)] = ActivationFunction::sigmoid($arr[(row, col)]);
-                        }
-                    }       
-                    $arr         
-                },
-                ActivationFunction::Tanh => {
-                    for row in 0..$arr.size.1 {
-                        for col in 0..$arr.size.0 {
-                            $arr[(row, col)] = ActivationFunction::tanh($arr[(row, col)]);
-                        }
-                    }    
-                    $arr              
-                },
-                ActivationFunction::ReLU => {
-                    for row in 0..$arr.size.1 {
-                        for col in 0..$arr.size.0 {
-                            $arr[(row, col)] = ActivationFunction::relu($arr[(row, col)]);
-                        }
-                    }   
-                    $arr               
-                },
-                ActivationFunction::LeakyReLu => {
-                    for row in 0..$arr.size.1 {
-                        for col in 0..$arr.size.0 {
-                            $arr[(row, col)] = ActivationFunction::leaky_relu($arr[(row, col)]);
-                        }
-                    }      
-                    $arr            
-                },
-                ActivationFunction::Elu => {
-                    for row in 0..$arr.size.1 {
-                        for col in 0..$arr.size.0 {
-                            $arr[(row, col)] = ActivationFunction::elu($arr[(row, col)]);
-                        }
-                    }
-                    $arr
-                },
-                ActivationFunction::Softmax => {
-                    let mut denominator = 0.0;
-                    for row in 0..$arr.size.1 {
-                        for col in 0..$arr.size.0 {
-                            denominator = $arr[(row, col)].exp();
-                        }
-                    }
-                    for row in 0..$arr.size.1 {
-                        for col in 0..$arr.size.0 {
-                            $arr[(row, col)] = ActivationFunction::softmax($arr[(row, col)], denominator);
-                        }
-                    }
-                    $arr
-                },
-                ActivationFunction::Softplus => {
-                    for row in 0..$arr.size.1 {
-                        for col in 0..$arr.size.0 {
-                            $arr[(row, col)] = ActivationFunction::softplus($arr[(row, col)]);
-                        }
-                    }    
-                    $arr              
-                },
+        // Every column of a batch is one sample, so the denominator is summed per column.
+        fn per_column(arr:&Array<f64>, function:fn(f64, f64) -> f64) -> Array<f64> {
+            let mut res = arr.clone();
+            for col in 0..arr.size.0 {
+                let denominator = (0..arr.size.1).fold(0.0, |acc, row| acc + arr[(row, col)].exp());
+                for row in 0..arr.size.1 {
+                    res[(row, col)] = function(arr[(row, col)], denominator);
+                }
             }
-        };
-    }
-    
-    #[macro_export]
-    macro_rules! derivative {
-        ( $function:expr, $arr:expr ) => {
-            match $function {
-                ActivationFunction::Perceptron => {
-                    for row in 0..$arr.size.1 {
-                        for col in 0..$arr.size.0 {
-                            $arr[(row, col)] = ActivationFunction::perceptron_derivative($arr[(row, col)]);
-                        }
-                    }
-                    $arr
-                },
-                ActivationFunction::Sigmoid => {
-                    for row in 0..$arr.size.1 {
-                        for col in 0..$arr.size.0 {
-                            $arr[(row, col)] = ActivationFunction::sigmoid_derivative($arr[(row, col)]);
-                        }
-                    }       
-                    $arr         
-                },
-                ActivationFunction::Tanh => {
-                    for row in 0..$arr.size.1 {
-                        for col in 0..$arr.size.0 {
-                            $arr[(row, col)] = ActivationFunction::tanh_derivative($arr[(row, col)]);
-                        }
-                    }    
-                    $arr              
-                },
-                ActivationFunction::ReLU => {
-                    for row in 0..$arr.size.1 {
-                        for col in 0..$arr.size.0 {
-                            $arr[(row, col)] = ActivationFunction::relu_derivative($arr[(row, col)]);
-                        }
-                    }   
-                    $arr               
-                },
-                ActivationFunction::LeakyReLu => {
-                    for row in 0..$arr.size.1 {
-                        for col in 0..$arr.size.0 {
-                            $arr[(row, col)] = ActivationFunction::leaky_relu_derivative($arr[(row, col)]);
-                        }
-                    }      
-                    $arr            
-                },
-                ActivationFunction::Elu => {
-                    for row in 0..$arr.size.1 {
-                        for col in 0..$arr.size.0 {
-                            $arr[(row, col)] = ActivationFunction::elu_derivative($arr[(row, col)]);
-                        }
-                    }
-                    $arr
-                },
-                ActivationFunction::Softmax => {
-                    let mut denominator = 0.0;
-                    for row in 0..$arr.size.1 {
-                        for col in 0..$arr.size.0 {
-                            denominator = $arr[(row, col)].exp();
-                        }
-                    }
-                    for row in 0..$arr.size.1 {
-                        for col in 0..$arr.size.0 {
-                            $arr[(row, col)] = ActivationFunction::softmax_derivative($arr[(row, col)], denominator);
-                        }
-                    }
-                    $arr
-                },
-                ActivationFunction::Softplus => {
-                    for row in 0..$arr.size.1 {
-                        for col in 0..$arr.size.0 {
-                            $arr[(row, col)] = ActivationFunction::softplus_derivative($arr[(row, col)]);
-                        }
-                    }    
-                    $arr              
-                },
+            res
+        }
+
+        pub fn apply(&self, arr:&Array<f64>) -> Array<f64> {
+            match self {
+                ActivationFunction::Perceptron => arr.map(ActivationFunction::perceptron),
+                ActivationFunction::Sigmoid => arr.map(ActivationFunction::sigmoid),
+                ActivationFunction::Tanh => arr.map(ActivationFunction::tanh),
+                ActivationFunction::ReLU => arr.map(ActivationFunction::relu),
+                ActivationFunction::LeakyReLu => arr.map(ActivationFunction::leaky_relu),
+                ActivationFunction::Elu => arr.map(ActivationFunction::elu),
+                ActivationFunction::Softmax => ActivationFunction::per_column(arr, ActivationFunction::softmax),
+                ActivationFunction::Softplus => arr.map(ActivationFunction::softplus),
             }
-        };
+        }
+
+        pub fn derivative(&self, arr:&Array<f64>) -> Array<f64> {
+            match self {
+                ActivationFunction::Perceptron => arr.map(ActivationFunction::perceptron_derivative),
+                ActivationFunction::Sigmoid => arr.map(ActivationFunction::sigmoid_derivative),
+                ActivationFunction::Tanh => arr.map(ActivationFunction::tanh_derivative),
+                ActivationFunction::ReLU => arr.map(ActivationFunction::relu_derivative),
+                ActivationFunction::LeakyReLu => arr.map(ActivationFunction::leaky_relu_derivative),
+                ActivationFunction::Elu => arr.map(ActivationFunction::elu_derivative),
+                ActivationFunction::Softmax => ActivationFunction::per_column(arr, ActivationFunction::softmax_derivative),
+                ActivationFunction::Softplus => arr.map(ActivationFunction::softplus_derivative),
+            }
+        }
     }
 
     pub struct NeuralNetwork {
@@ -288,7 +170,7 @@ pub mod ml {
                 Array::new_filled((depth, output_size), 1.0)
             );
             let mut biases = Vec::<Array<f64>>::with_capacity(layers + 2);
-            while biases.len() < layers {
+            while biases.len() < layers + 1 {
                 biases.push(
                     Array::new_filled((1, depth), 1.0)
                 );
@@ -325,14 +207,20 @@ pub mod ml {
         }
 
         pub fn propagate_forward(&mut self) {
-            let mut temp = self.input.clone();
+            self.output = self.propagate_forward_batch(&self.input);
+        }
+
+        // Every column of inputs is one sample, the bias vectors are broadcast across the batch.
+        pub fn propagate_forward_batch(&self, inputs:&Array<f64>) -> Array<f64> {
+            if inputs.size.1 != self.input.size.1 {
+                panic!("Error: Wrong input size, expected '{}', actually '{}'.", self.input.size.1, inputs.size.1);
+            }
+            let mut temp = inputs.clone();
             for i in 0..self.weights.len() {
-                temp = apply!(
-                    self.activation_function,
-                    self.weights[i].clone() * temp.clone() + self.biases[i].clone()
-                );
+                let z = &self.weights[i] * &temp + &self.biases[i];
+                temp = self.activation_function.apply(&z);
             }
-            self.output = temp;
+            temp
         }
 
         pub fn propagate_backwards(&mut self, target:Vec<f64>, learning_rate:f64) -> Vec<f64> {
@@ -342,14 +230,14 @@ pub mod ml {
             }
 
             for i in (0..self.weights.len()).rev() {
-                let delta_weights = error.hadamard_product(derivative!(self.activation_function, self.output.clone()));
+                let delta_weights = error.hadamard_product(self.activation_function.derivative(&self.output));
                 let weights_gradient = delta_weights.clone().hadamard_product(self.weights[i].transpose());
 
-                self.weights[i] = self.weights[i].clone() - weights_gradient * learning_rate;
+                self.weights[i] -= &(weights_gradient * learning_rate);
 
                 let biases_gradient = delta_weights.clone();
 
-                self.biases[i] = self.biases[i].clone() - biases_gradient * learning_rate;
+                self.biases[i] -= &(biases_gradient * learning_rate);
 
                 error = delta_weights.hadamard_product(self.weights[i].clone());
             }
@@ -452,4 +340,58 @@ pub mod ml {
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use crate::array::array::Array;
+    use crate::ml::ml::{NeuralNetwork, ActivationFunction};
+
+    #[test]
+    fn every_layer_has_a_bias() {
+        let mut network = NeuralNetwork::new(2, 1, 2, 1, ActivationFunction::ReLU);
+        network.set_input(vec![1.0, 1.0]);
+        network.propagate_forward();
+        assert_eq!(vec![15.0], network.get_output());
+    }
+
+    #[test]
+    fn propagate_forward_batch() {
+        let expected = Array::new_mat(vec![vec![15.0, 7.0]]);
+        let actual = {
+            let network = NeuralNetwork::new(2, 1, 2, 1, ActivationFunction::ReLU);
+            network.propagate_forward_batch(&Array::new_mat(vec![vec![1.0, 0.0], vec![1.0, 0.0]]))
+        };
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn propagate_forward_matches_batch() {
+        let mut network = NeuralNetwork::new(2, 1, 2, 1, ActivationFunction::ReLU);
+        network.set_input(vec![1.0, 1.0]);
+        network.propagate_forward();
+        assert_eq!(vec![15.0], network.get_output());
+    }
+
+    #[test]
+    fn softmax_normalises_every_sample() {
+        let batch = Array::new_mat(vec![vec![1.0, 0.0], vec![2.0, 0.0], vec![3.0, 0.0]]);
+        let actual = ActivationFunction::Softmax.apply(&batch);
+        for col in 0..2 {
+            let sum = (0..3).fold(0.0, |acc, row| acc + actual[(row, col)]);
+            assert!((sum - 1.0).abs() < 1e-12);
+        }
+        let single = ActivationFunction::Softmax.apply(&Array::new_vec(vec![1.0, 2.0, 3.0]));
+        for row in 0..3 {
+            assert_eq!(single[(row, 0)], actual[(row, 0)]);
+            assert!((actual[(row, 1)] - 1.0 / 3.0).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn softmax_sums_to_one() {
+        let actual = ActivationFunction::Softmax.apply(&Array::new_vec(vec![1.0, 2.0, 3.0]));
+        assert!(f64::abs(actual.iter().sum::<f64>() - 1.0) < 1e-12);
+        assert!(actual[(0, 0)] < actual[(1, 0)] && actual[(1, 0)] < actual[(2, 0)]);
+    }
+}
+
 mod generall_intelligence;